@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Request bodies encoded by the backend instead of being sent verbatim.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum BodySpec {
+    UrlEncoded {
+        fields: Vec<FormField>,
+    },
+    Multipart {
+        fields: Vec<FormField>,
+    },
+    /// Raw body streamed from a file on disk.
+    File {
+        path: String,
+    },
+}
+
+/// A form field; for multipart file parts `value` is the path of the file to upload.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormField {
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    file: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EncodedBody {
+    pub body: PreparedBody,
+    pub content_type: String,
+}
+
+/// A body that can be attached again to every hop of a redirect chain.
+#[derive(Debug, Clone)]
+pub enum PreparedBody {
+    Bytes(Vec<u8>),
+    File { path: PathBuf, len: u64 },
+}
+
+impl PreparedBody {
+    pub fn len(&self) -> u64 {
+        match self {
+            PreparedBody::Bytes(bytes) => bytes.len() as u64,
+            PreparedBody::File { len, .. } => *len,
+        }
+    }
+
+    pub async fn to_body(&self) -> Result<reqwest::Body, String> {
+        match self {
+            PreparedBody::Bytes(bytes) => Ok(bytes.clone().into()),
+            PreparedBody::File { path, .. } => tokio::fs::File::open(path)
+                .await
+                .map(reqwest::Body::from)
+                .map_err(|err| format!("failed to open {}: {err}", path.display())),
+        }
+    }
+
+    /// Loads the whole body, e.g. to compress it before sending.
+    pub async fn read_all(self) -> Result<Vec<u8>, String> {
+        match self {
+            PreparedBody::Bytes(bytes) => Ok(bytes),
+            PreparedBody::File { path, .. } => tokio::fs::read(&path)
+                .await
+                .map_err(|err| format!("failed to read {}: {err}", path.display())),
+        }
+    }
+}
+
+impl BodySpec {
+    /// Form bodies always carry their own Content-Type; a file only fills it in when
+    /// the request does not set one.
+    pub fn replaces_content_type(&self) -> bool {
+        !matches!(self, BodySpec::File { .. })
+    }
+
+    pub async fn encode(&self) -> Result<EncodedBody, String> {
+        match self {
+            BodySpec::UrlEncoded { fields } => Ok(EncodedBody {
+                body: PreparedBody::Bytes(encode_urlencoded(fields).into_bytes()),
+                content_type: "application/x-www-form-urlencoded".to_string(),
+            }),
+            BodySpec::Multipart { fields } => encode_multipart(fields).await,
+            BodySpec::File { path } => {
+                let info = file_info(path.clone()).await?;
+                Ok(EncodedBody {
+                    body: PreparedBody::File {
+                        path: PathBuf::from(path.trim()),
+                        len: info.size,
+                    },
+                    content_type: info.mime,
+                })
+            }
+        }
+    }
+}
+
+/// What the body panel shows about a file selected as the request body.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    name: String,
+    size: u64,
+    mime: String,
+}
+
+#[tauri::command]
+pub async fn file_info(path: String) -> Result<FileInfo, String> {
+    let path = Path::new(path.trim());
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    Ok(FileInfo {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size: metadata.len(),
+        mime: mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string(),
+    })
+}
+
+fn encode_urlencoded(fields: &[FormField]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}={}", form_escape(&field.name), form_escape(&field.value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// `application/x-www-form-urlencoded` byte serializer.
+fn form_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                escaped.push(byte as char)
+            }
+            b' ' => escaped.push('+'),
+            _ => escaped.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    escaped
+}
+
+async fn encode_multipart(fields: &[FormField]) -> Result<EncodedBody, String> {
+    let boundary = boundary();
+    let mut bytes = Vec::new();
+    for field in fields {
+        bytes.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        if field.file {
+            let path = Path::new(field.value.trim());
+            let contents = tokio::fs::read(path)
+                .await
+                .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mime = mime_guess::from_path(path).first_or_octet_stream();
+            bytes.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {mime}\r\n\r\n",
+                    quote(&field.name),
+                    quote(&file_name)
+                )
+                .as_bytes(),
+            );
+            bytes.extend_from_slice(&contents);
+        } else {
+            bytes.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                    quote(&field.name)
+                )
+                .as_bytes(),
+            );
+            bytes.extend_from_slice(field.value.as_bytes());
+        }
+        bytes.extend_from_slice(b"\r\n");
+    }
+    bytes.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    Ok(EncodedBody {
+        body: PreparedBody::Bytes(bytes),
+        content_type: format!("multipart/form-data; boundary={boundary}"),
+    })
+}
+
+/// Escapes a name for a quoted `Content-Disposition` parameter, as browsers do.
+fn quote(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("----RustmanBoundary{nanos:016x}{count:04x}")
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cookies::CookieJars;
+use crate::proxy::EffectiveProxy;
+use crate::timing::{TimedConnectLayer, TimedResolver};
+use crate::tls::{self, TlsOptions};
+
+/// Connection settings that require a dedicated client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOptions {
+    #[serde(default)]
+    pub tls: TlsOptions,
+    pub proxy: EffectiveProxy,
+    pub http_version: HttpVersion,
+    /// Unix domain socket every connection is dialed on instead of TCP.
+    pub unix_socket: Option<PathBuf>,
+}
+
+/// Protocol selection for a request. TLS connections advertise the matching ALPN
+/// protocols; `Http2` also speaks HTTP/2 to plain-text servers without an upgrade.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    Http2,
+}
+
+impl HttpVersion {
+    fn alpn_protocols(self) -> Vec<Vec<u8>> {
+        match self {
+            HttpVersion::Auto => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            HttpVersion::Http1 => vec![b"http/1.1".to_vec()],
+            HttpVersion::Http2 => vec![b"h2".to_vec()],
+        }
+    }
+}
+
+/// Reusable `reqwest` clients, one per server and connection settings, so each keeps
+/// its cookie jar and connection pool across requests.
+#[derive(Default)]
+pub struct ClientPool {
+    clients: Mutex<HashMap<String, reqwest::Client>>,
+}
+
+impl ClientPool {
+    pub fn client_for(
+        &self,
+        jars: &CookieJars,
+        server: &str,
+        options: &ClientOptions,
+    ) -> Result<reqwest::Client, String> {
+        let key = format!(
+            "{server}\n{}",
+            serde_json::to_string(options).map_err(|err| err.to_string())?
+        );
+        let mut clients = self.clients.lock().map_err(|err| err.to_string())?;
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let mut tls_config = tls::client_config(&options.tls)?;
+        tls_config.alpn_protocols = options.http_version.alpn_protocols();
+        let builder = reqwest::Client::builder()
+            .cookie_provider(jars.jar(server))
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(TimedResolver))
+            .connector_layer(TimedConnectLayer)
+            .use_preconfigured_tls(tls_config);
+        let builder = match options.http_version {
+            HttpVersion::Auto => builder,
+            HttpVersion::Http1 => builder.http1_only(),
+            HttpVersion::Http2 => builder.http2_prior_knowledge(),
+        };
+        let builder = match options.unix_socket.as_deref() {
+            Some(path) => unix_socket(builder, path)?,
+            None => options.proxy.apply(builder)?,
+        };
+        let client = builder
+            .build()
+            .map_err(|err| format!("failed to build client: {err}"))?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Drops every cached client so the next request picks up changed app settings.
+    pub fn clear(&self) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.clear();
+        }
+    }
+}
+
+#[cfg(unix)]
+fn unix_socket(
+    builder: reqwest::ClientBuilder,
+    path: &std::path::Path,
+) -> Result<reqwest::ClientBuilder, String> {
+    Ok(builder.unix_socket(path))
+}
+
+#[cfg(not(unix))]
+fn unix_socket(
+    _builder: reqwest::ClientBuilder,
+    path: &std::path::Path,
+) -> Result<reqwest::ClientBuilder, String> {
+    Err(format!(
+        "cannot connect to {}: unix sockets are not supported on this platform",
+        path.display()
+    ))
+}
+
+/// Splits a request aimed at a unix socket into the socket path and the HTTP URL sent
+/// over it. The socket is either the server's own `unix:///path/to.sock` URL, with the
+/// request path following it, or a socket path set in the server settings.
+pub fn unix_target(
+    url: &str,
+    server_url: Option<&str>,
+    socket_path: Option<&str>,
+) -> Result<(String, Option<PathBuf>), String> {
+    if let Some(server_url) = server_url.filter(|server| server.starts_with("unix:")) {
+        let rest = url
+            .strip_prefix(server_url.trim_end_matches('/'))
+            .ok_or_else(|| format!("{url} is not under the socket {server_url}"))?;
+        let path = reqwest::Url::parse(server_url)
+            .ok()
+            .and_then(|socket| socket.to_file_path().ok())
+            .ok_or_else(|| format!("invalid unix socket URL: {server_url}"))?;
+        let rest = if rest.starts_with('/') {
+            rest.to_string()
+        } else {
+            format!("/{rest}")
+        };
+        // The host only fills the `Host` header; nothing is resolved.
+        return Ok((format!("http://localhost{rest}"), Some(path)));
+    }
+    let path = socket_path
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    Ok((url.to_string(), path))
+}
@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::storage::{stable_hash, write_atomic};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    #[serde(default)]
+    pub host_only: bool,
+    pub path: String,
+    /// Expiry as unix seconds; `None` for session cookies.
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub same_site: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookieKey {
+    pub name: String,
+    pub domain: String,
+    pub path: String,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn has_key(&self, name: &str, domain: &str, path: &str) -> bool {
+        self.name == name && self.domain.eq_ignore_ascii_case(domain) && self.path == path
+    }
+
+    fn matches(&self, url: &Url, now: i64) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(&host, &self.domain)
+        };
+        let secure_ok = !self.secure || url.scheme() == "https" || host == "localhost";
+        domain_ok && secure_ok && path_match(url.path(), &self.path) && !self.is_expired(now)
+    }
+}
+
+/// Cookies received for one server, persisted to their own file in the app data dir.
+pub struct CookieJar {
+    server: String,
+    file: PathBuf,
+    cookies: Mutex<Vec<StoredCookie>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JarFile {
+    server: String,
+    cookies: Vec<StoredCookie>,
+}
+
+impl CookieJar {
+    fn list(&self) -> Vec<StoredCookie> {
+        let now = now_seconds();
+        let Ok(mut cookies) = self.cookies.lock() else {
+            return Vec::new();
+        };
+        cookies.retain(|cookie| !cookie.is_expired(now));
+        cookies.clone()
+    }
+
+    fn upsert(&self, cookie: StoredCookie, previous: Option<&CookieKey>) -> Result<(), String> {
+        let mut cookies = self.cookies.lock().map_err(|err| err.to_string())?;
+        if let Some(key) = previous {
+            cookies.retain(|existing| !existing.has_key(&key.name, &key.domain, &key.path));
+        }
+        store_cookie(&mut cookies, cookie, now_seconds());
+        self.persist(&cookies)
+    }
+
+    fn remove(&self, key: &CookieKey) -> Result<bool, String> {
+        let mut cookies = self.cookies.lock().map_err(|err| err.to_string())?;
+        let before = cookies.len();
+        cookies.retain(|cookie| !cookie.has_key(&key.name, &key.domain, &key.path));
+        let removed = cookies.len() != before;
+        if removed {
+            self.persist(&cookies)?;
+        }
+        Ok(removed)
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        let mut cookies = self.cookies.lock().map_err(|err| err.to_string())?;
+        cookies.clear();
+        self.persist(&cookies)
+    }
+
+    fn persist(&self, cookies: &[StoredCookie]) -> Result<(), String> {
+        let file = JarFile {
+            server: self.server.clone(),
+            cookies: cookies.to_vec(),
+        };
+        let json = serde_json::to_vec_pretty(&file).map_err(|err| err.to_string())?;
+        write_atomic(&self.file, &json)
+    }
+}
+
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let now = now_seconds();
+        let parsed: Vec<StoredCookie> = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| parse_set_cookie(value, url, now))
+            .collect();
+        if parsed.is_empty() {
+            return;
+        }
+        let Ok(mut cookies) = self.cookies.lock() else {
+            return;
+        };
+        for cookie in parsed {
+            store_cookie(&mut cookies, cookie, now);
+        }
+        let _ = self.persist(&cookies);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let now = now_seconds();
+        let cookies = self.cookies.lock().ok()?;
+        let mut matching: Vec<&StoredCookie> = cookies
+            .iter()
+            .filter(|cookie| cookie.matches(url, now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // Longer paths first, as browsers do.
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        let header = matching
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+/// Every server's cookie jar, loaded from `<app data>/cookies` on startup.
+pub struct CookieJars {
+    dir: PathBuf,
+    jars: Mutex<HashMap<String, Arc<CookieJar>>>,
+}
+
+impl CookieJars {
+    pub fn load(dir: PathBuf) -> Self {
+        let mut jars = HashMap::new();
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let file = entry.path();
+                if file.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(contents) = fs::read(&file).ok() else {
+                    continue;
+                };
+                let Ok(stored) = serde_json::from_slice::<JarFile>(&contents) else {
+                    continue;
+                };
+                let jar = CookieJar {
+                    server: stored.server.clone(),
+                    file,
+                    cookies: Mutex::new(stored.cookies),
+                };
+                jars.insert(stored.server, Arc::new(jar));
+            }
+        }
+        Self {
+            dir,
+            jars: Mutex::new(jars),
+        }
+    }
+
+    pub fn jar(&self, server: &str) -> Arc<CookieJar> {
+        let mut jars = match self.jars.lock() {
+            Ok(jars) => jars,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        jars.entry(server.to_string())
+            .or_insert_with(|| {
+                Arc::new(CookieJar {
+                    server: server.to_string(),
+                    file: self.dir.join(format!("{:016x}.json", stable_hash(server))),
+                    cookies: Mutex::new(Vec::new()),
+                })
+            })
+            .clone()
+    }
+}
+
+#[tauri::command]
+pub fn list_cookies(jars: State<'_, CookieJars>, server_url: String) -> Vec<StoredCookie> {
+    jars.jar(&server_url).list()
+}
+
+#[tauri::command]
+pub fn save_cookie(
+    jars: State<'_, CookieJars>,
+    server_url: String,
+    cookie: StoredCookie,
+    previous: Option<CookieKey>,
+) -> Result<(), String> {
+    if cookie.name.trim().is_empty() {
+        return Err("cookie name is required".to_string());
+    }
+    let mut cookie = cookie;
+    cookie.domain = cookie
+        .domain
+        .trim()
+        .trim_start_matches('.')
+        .to_ascii_lowercase();
+    if cookie.domain.is_empty() {
+        return Err("cookie domain is required".to_string());
+    }
+    if !cookie.path.starts_with('/') {
+        cookie.path = format!("/{}", cookie.path);
+    }
+    jars.jar(&server_url).upsert(cookie, previous.as_ref())
+}
+
+#[tauri::command]
+pub fn delete_cookie(
+    jars: State<'_, CookieJars>,
+    server_url: String,
+    cookie: CookieKey,
+) -> Result<bool, String> {
+    jars.jar(&server_url).remove(&cookie)
+}
+
+#[tauri::command]
+pub fn clear_cookies(jars: State<'_, CookieJars>, server_url: String) -> Result<(), String> {
+    jars.jar(&server_url).clear()
+}
+
+fn store_cookie(cookies: &mut Vec<StoredCookie>, cookie: StoredCookie, now: i64) {
+    cookies.retain(|existing| {
+        !existing.has_key(&cookie.name, &cookie.domain, &cookie.path) && !existing.is_expired(now)
+    });
+    // An already-expired cookie is how servers delete one.
+    if !cookie.is_expired(now) {
+        cookies.push(cookie);
+    }
+}
+
+fn parse_set_cookie(header: &str, url: &Url, now: i64) -> Option<StoredCookie> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut cookie = StoredCookie {
+        name: name.to_string(),
+        value: value.trim().to_string(),
+        domain: host.clone(),
+        host_only: true,
+        path: default_path(url),
+        expires: None,
+        secure: false,
+        http_only: false,
+        same_site: None,
+    };
+    let mut max_age = None;
+
+    for attribute in parts {
+        let (key, value) = match attribute.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (attribute.trim(), ""),
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "domain" => {
+                let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                if domain.is_empty() {
+                    continue;
+                }
+                if !domain_match(&host, &domain) {
+                    return None;
+                }
+                cookie.domain = domain;
+                cookie.host_only = false;
+            }
+            "path" if value.starts_with('/') => cookie.path = value.to_string(),
+            "expires" => {
+                if let Ok(time) = httpdate::parse_http_date(value) {
+                    cookie.expires = Some(unix_seconds(time));
+                }
+            }
+            "max-age" => max_age = value.parse::<i64>().ok(),
+            "secure" => cookie.secure = true,
+            "httponly" => cookie.http_only = true,
+            "samesite" if !value.is_empty() => cookie.same_site = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if let Some(seconds) = max_age {
+        cookie.expires = Some(if seconds <= 0 {
+            now
+        } else {
+            now.saturating_add(seconds)
+        });
+    }
+
+    Some(cookie)
+}
+
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.len() > domain.len()
+            && host.ends_with(domain)
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.')
+}
+
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+fn default_path(url: &Url) -> String {
+    let path = url.path();
+    match path.rfind('/') {
+        Some(index) if index > 0 => path[..index].to_string(),
+        _ => "/".to_string(),
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(err) => -(err.duration().as_secs() as i64),
+    }
+}
+
+fn now_seconds() -> i64 {
+    unix_seconds(SystemTime::now())
+}
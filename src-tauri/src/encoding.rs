@@ -0,0 +1,157 @@
+use async_compression::tokio::write::{
+    BrotliDecoder, BrotliEncoder, GzipDecoder, GzipEncoder, ZlibDecoder, ZlibEncoder, ZstdDecoder,
+    ZstdEncoder,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+/// `Content-Encoding` codings the backend can decode and encode. HTTP `deflate` is
+/// zlib-wrapped deflate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    #[serde(rename = "br")]
+    Brotli,
+    Zstd,
+}
+
+impl ContentEncoding {
+    pub const ALL: [ContentEncoding; 4] = [
+        ContentEncoding::Gzip,
+        ContentEncoding::Deflate,
+        ContentEncoding::Brotli,
+        ContentEncoding::Zstd,
+    ];
+
+    pub fn token(self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    fn parse(token: &str) -> Option<Self> {
+        let token = token.trim();
+        if token.eq_ignore_ascii_case("x-gzip") {
+            return Some(ContentEncoding::Gzip);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|encoding| token.eq_ignore_ascii_case(encoding.token()))
+    }
+
+    pub async fn compress(self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        async fn run<W: tokio::io::AsyncWrite + Unpin>(
+            mut encoder: W,
+            bytes: &[u8],
+        ) -> std::io::Result<W> {
+            encoder.write_all(bytes).await?;
+            encoder.shutdown().await?;
+            Ok(encoder)
+        }
+        let result = match self {
+            ContentEncoding::Gzip => run(GzipEncoder::new(Vec::new()), bytes)
+                .await
+                .map(GzipEncoder::into_inner),
+            ContentEncoding::Deflate => run(ZlibEncoder::new(Vec::new()), bytes)
+                .await
+                .map(ZlibEncoder::into_inner),
+            ContentEncoding::Brotli => run(BrotliEncoder::new(Vec::new()), bytes)
+                .await
+                .map(BrotliEncoder::into_inner),
+            ContentEncoding::Zstd => run(ZstdEncoder::new(Vec::new()), bytes)
+                .await
+                .map(ZstdEncoder::into_inner),
+        };
+        result.map_err(|err| format!("failed to compress the body with {}: {err}", self.token()))
+    }
+}
+
+/// Value sent as `Accept-Encoding` when the request does not set one.
+pub fn accept_encoding(disabled: &[ContentEncoding]) -> Option<String> {
+    let tokens: Vec<&str> = ContentEncoding::ALL
+        .into_iter()
+        .filter(|encoding| !disabled.contains(encoding))
+        .map(ContentEncoding::token)
+        .collect();
+    (!tokens.is_empty()).then(|| tokens.join(", "))
+}
+
+/// Streaming decoder for a response body. Compressed chunks go in, decoded bytes
+/// come out as soon as they are available.
+pub enum Decoder {
+    Gzip(GzipDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>),
+    Brotli(BrotliDecoder<Vec<u8>>),
+    Zstd(ZstdDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    /// Decoder for a `Content-Encoding` header value. Stacked codings and codings that
+    /// are disabled or unknown are left alone, so the body is shown as received.
+    pub fn for_header(value: Option<&str>, disabled: &[ContentEncoding]) -> Option<Self> {
+        let encoding = ContentEncoding::parse(value?)?;
+        if disabled.contains(&encoding) {
+            return None;
+        }
+        Some(match encoding {
+            ContentEncoding::Gzip => Decoder::Gzip(GzipDecoder::new(Vec::new())),
+            ContentEncoding::Deflate => Decoder::Deflate(ZlibDecoder::new(Vec::new())),
+            ContentEncoding::Brotli => Decoder::Brotli(BrotliDecoder::new(Vec::new())),
+            ContentEncoding::Zstd => Decoder::Zstd(ZstdDecoder::new(Vec::new())),
+        })
+    }
+
+    pub fn encoding(&self) -> ContentEncoding {
+        match self {
+            Decoder::Gzip(_) => ContentEncoding::Gzip,
+            Decoder::Deflate(_) => ContentEncoding::Deflate,
+            Decoder::Brotli(_) => ContentEncoding::Brotli,
+            Decoder::Zstd(_) => ContentEncoding::Zstd,
+        }
+    }
+
+    /// Feeds a compressed chunk and returns whatever it decoded to so far.
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<Vec<u8>, String> {
+        let result = match self {
+            Decoder::Gzip(decoder) => decoder.write_all(chunk).await,
+            Decoder::Deflate(decoder) => decoder.write_all(chunk).await,
+            Decoder::Brotli(decoder) => decoder.write_all(chunk).await,
+            Decoder::Zstd(decoder) => decoder.write_all(chunk).await,
+        };
+        result.map_err(|err| self.error(err))?;
+        Ok(std::mem::take(self.output()))
+    }
+
+    /// Flushes the end of the stream once the whole body has been fed.
+    pub async fn finish(&mut self) -> Result<Vec<u8>, String> {
+        let result = match self {
+            Decoder::Gzip(decoder) => decoder.shutdown().await,
+            Decoder::Deflate(decoder) => decoder.shutdown().await,
+            Decoder::Brotli(decoder) => decoder.shutdown().await,
+            Decoder::Zstd(decoder) => decoder.shutdown().await,
+        };
+        result.map_err(|err| self.error(err))?;
+        Ok(std::mem::take(self.output()))
+    }
+
+    fn output(&mut self) -> &mut Vec<u8> {
+        match self {
+            Decoder::Gzip(decoder) => decoder.get_mut(),
+            Decoder::Deflate(decoder) => decoder.get_mut(),
+            Decoder::Brotli(decoder) => decoder.get_mut(),
+            Decoder::Zstd(decoder) => decoder.get_mut(),
+        }
+    }
+
+    fn error(&self, err: std::io::Error) -> String {
+        format!(
+            "failed to decode {} response: {err}",
+            self.encoding().token()
+        )
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use crate::storage::write_atomic;
+
+/// Sends kept once the file is trimmed.
+const MAX_ENTRIES: usize = 1000;
+/// Records allowed past `MAX_ENTRIES` before trimming, so the file is not rewritten
+/// on every send.
+const TRIM_SLACK: usize = 100;
+
+/// One send. `summary` is what the history panel lists and searches; `detail` holds the
+/// request and response and is only read back when an entry is opened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    id: String,
+    summary: Value,
+    detail: Value,
+}
+
+/// Record read without its detail, which is skipped rather than parsed.
+#[derive(Deserialize)]
+struct ListedRecord {
+    summary: Value,
+}
+
+#[derive(Deserialize)]
+struct RecordId {
+    id: String,
+}
+
+/// Send history in `<app data>/history.jsonl`, one record per line, oldest first.
+/// Records are appended, so a crash can at worst cut the last line, which is skipped.
+pub struct HistoryStore {
+    file: PathBuf,
+    /// Records in the file, counted on first use.
+    count: Mutex<Option<usize>>,
+}
+
+impl HistoryStore {
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            count: Mutex::new(None),
+        }
+    }
+
+    fn read<T: DeserializeOwned>(&self) -> Vec<T> {
+        let Ok(contents) = fs::read_to_string(&self.file) else {
+            return Vec::new();
+        };
+        contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    fn append(&self, record: &HistoryRecord) -> Result<(), String> {
+        let mut line = serde_json::to_vec(record).map_err(|err| err.to_string())?;
+        line.push(b'\n');
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("create {}: {err}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file)
+            .map_err(|err| format!("open {}: {err}", self.file.display()))?;
+        file.write_all(&line)
+            .and_then(|_| file.sync_data())
+            .map_err(|err| format!("write {}: {err}", self.file.display()))
+    }
+
+    /// Rewrites the file with only the newest `MAX_ENTRIES` records.
+    fn trim(&self) -> Result<usize, String> {
+        let records: Vec<HistoryRecord> = self.read();
+        let keep = &records[records.len().saturating_sub(MAX_ENTRIES)..];
+        let mut contents = Vec::new();
+        for record in keep {
+            serde_json::to_writer(&mut contents, record).map_err(|err| err.to_string())?;
+            contents.push(b'\n');
+        }
+        write_atomic(&self.file, &contents)?;
+        Ok(keep.len())
+    }
+}
+
+#[tauri::command]
+pub async fn append_history(
+    store: State<'_, HistoryStore>,
+    record: HistoryRecord,
+) -> Result<(), String> {
+    let mut count = store.count.lock().map_err(|err| err.to_string())?;
+    let current = match *count {
+        Some(current) => current,
+        None => store.read::<ListedRecord>().len(),
+    };
+    store.append(&record)?;
+    *count = Some(if current + 1 > MAX_ENTRIES + TRIM_SLACK {
+        store.trim()?
+    } else {
+        current + 1
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_history(store: State<'_, HistoryStore>) -> Result<Vec<Value>, String> {
+    let _count = store.count.lock().map_err(|err| err.to_string())?;
+    // Newest first.
+    let mut summaries: Vec<Value> = store
+        .read::<ListedRecord>()
+        .into_iter()
+        .map(|record| record.summary)
+        .collect();
+    summaries.reverse();
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub async fn get_history_entry(
+    store: State<'_, HistoryStore>,
+    id: String,
+) -> Result<Option<Value>, String> {
+    let _count = store.count.lock().map_err(|err| err.to_string())?;
+    let Ok(contents) = fs::read_to_string(&store.file) else {
+        return Ok(None);
+    };
+    Ok(contents
+        .lines()
+        .rev()
+        .filter(|line| serde_json::from_str::<RecordId>(line).is_ok_and(|record| record.id == id))
+        .find_map(|line| serde_json::from_str::<HistoryRecord>(line).ok())
+        .map(|record| record.detail))
+}
+
+#[tauri::command]
+pub fn clear_history(store: State<'_, HistoryStore>) -> Result<(), String> {
+    let mut count = store.count.lock().map_err(|err| err.to_string())?;
+    match fs::remove_file(&store.file) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("remove {}: {err}", store.file.display()))
+        }
+        _ => {
+            *count = Some(0);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,585 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tokio::sync::oneshot;
+
+use crate::body::{BodySpec, PreparedBody};
+use crate::client::{unix_target, ClientOptions, ClientPool, HttpVersion};
+use crate::cookies::CookieJars;
+use crate::encoding::{accept_encoding, ContentEncoding, Decoder};
+use crate::proxy::{EffectiveProxy, ServerProxy};
+use crate::retry::{RetryAttempt, RetryPolicy};
+use crate::settings::SettingsStore;
+use crate::spool::{self, ReadBody};
+use crate::sse;
+use crate::timing::{self, PhaseMarks, ResponseTiming};
+use crate::tls::TlsOptions;
+
+/// Request headers as `[name, value]` pairs in send order; a name may repeat.
+/// reqwest groups repeated names together, keeping the order of their first row.
+pub type HeaderList = Vec<(String, String)>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRequest {
+    method: String,
+    url: String,
+    headers: HeaderList,
+    body: Option<String>,
+    #[serde(default)]
+    request_id: Option<String>,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    /// Base URL of the selected server; picks the client and cookie jar to use.
+    #[serde(default)]
+    server_url: Option<String>,
+    #[serde(default)]
+    follow_redirects: Option<bool>,
+    #[serde(default)]
+    max_redirects: Option<usize>,
+    #[serde(default)]
+    tls: TlsOptions,
+    #[serde(default)]
+    proxy: ServerProxy,
+    #[serde(default)]
+    retry: RetryPolicy,
+    #[serde(default)]
+    http_version: HttpVersion,
+    /// Socket path from the server settings; `unix://` server URLs carry their own.
+    #[serde(default)]
+    unix_socket: Option<String>,
+    /// Response codings left encoded; the others are advertised and decoded here.
+    #[serde(default)]
+    decode_disabled: Vec<ContentEncoding>,
+    /// Compresses the request body and sets `Content-Encoding` accordingly.
+    #[serde(default)]
+    compress_body: Option<ContentEncoding>,
+    /// Form or file body prepared here; takes precedence over `body`.
+    #[serde(default)]
+    body_mode: Option<BodySpec>,
+}
+
+/// Redirects are followed manually so every hop can be reported; this matches
+/// reqwest's own default limit.
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Cancellation senders for in-flight `send_request` calls and open event streams,
+/// keyed by request id.
+#[derive(Default)]
+pub struct InFlightRequests(Arc<Mutex<HashMap<String, oneshot::Sender<()>>>>);
+
+#[derive(Debug, Serialize)]
+pub struct HttpResponse {
+    url: String,
+    status: u16,
+    ok: bool,
+    headers: HashMap<String, String>,
+    raw_headers: HashMap<String, Vec<String>>,
+    data: String,
+    body_base64: Option<String>,
+    body_kind: BodyKind,
+    content_type: Option<String>,
+    /// Decoded body size.
+    size: u64,
+    /// Body size as received, before decoding.
+    wire_size: u64,
+    /// Coding the body was decoded from.
+    content_encoding: Option<String>,
+    /// Temp file holding the full body when it exceeded the in-memory limit; `data` or
+    /// `body_base64` then only carry a preview.
+    body_file: Option<String>,
+    redirects: Vec<RedirectHop>,
+    /// Attempts retried under the server's retry policy, oldest first.
+    attempts: Vec<RetryAttempt>,
+    timing: ResponseTiming,
+    /// Negotiated protocol of the final response, e.g. `HTTP/1.1` or `HTTP/2.0`.
+    version: String,
+    /// The body is an event stream forwarded through `sse-event` events instead.
+    streaming: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RedirectHop {
+    url: String,
+    status: u16,
+    location: String,
+    headers: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyKind {
+    Text,
+    Image,
+    Pdf,
+    Binary,
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn send_request(
+    app: AppHandle,
+    in_flight: State<'_, InFlightRequests>,
+    clients: State<'_, ClientPool>,
+    cookie_jars: State<'_, CookieJars>,
+    settings: State<'_, SettingsStore>,
+    request: Option<HttpRequest>,
+    method: Option<String>,
+    url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<String>,
+) -> Result<HttpResponse, String> {
+    let mut request = match request {
+        Some(request) => request,
+        None => {
+            let method = method.ok_or_else(|| "missing request.method".to_string())?;
+            let url = url.ok_or_else(|| "missing request.url".to_string())?;
+            HttpRequest {
+                method,
+                url,
+                headers: headers.unwrap_or_default().into_iter().collect(),
+                body,
+                request_id: None,
+                timeout_ms: None,
+                server_url: None,
+                follow_redirects: None,
+                max_redirects: None,
+                tls: TlsOptions::default(),
+                proxy: ServerProxy::default(),
+                retry: RetryPolicy::default(),
+                http_version: HttpVersion::default(),
+                unix_socket: None,
+                decode_disabled: Vec::new(),
+                compress_body: None,
+                body_mode: None,
+            }
+        }
+    };
+
+    let server = request.server_url.clone().unwrap_or_default();
+    let (url, unix_socket) = unix_target(
+        &request.url,
+        request.server_url.as_deref(),
+        request.unix_socket.as_deref(),
+    )?;
+    request.url = url;
+    let options = ClientOptions {
+        tls: request.tls.clone(),
+        proxy: EffectiveProxy::resolve(&request.proxy, &settings.current().proxy),
+        http_version: request.http_version,
+        unix_socket,
+    };
+    let client = clients.client_for(&cookie_jars, &server, &options)?;
+    let body_limit = settings
+        .current()
+        .response_limit_mb
+        .unwrap_or(spool::DEFAULT_LIMIT_MB)
+        .saturating_mul(1024 * 1024);
+
+    let decode_disabled = request.decode_disabled.clone();
+
+    let Some(request_id) = request.request_id.clone() else {
+        return execute_request(&client, request, body_limit, false)
+            .await
+            .map(|(response, _)| response);
+    };
+
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    if let Ok(mut pending) = in_flight.0.lock() {
+        pending.insert(request_id.clone(), cancel_tx);
+    }
+
+    let result = tokio::select! {
+        result = execute_request(&client, request, body_limit, true) => result,
+        Ok(()) = cancel_rx => Err("request cancelled".to_string()),
+    };
+
+    if let Ok(mut pending) = in_flight.0.lock() {
+        pending.remove(&request_id);
+    }
+
+    let (response, stream) = result?;
+    if let Some(stream) = stream {
+        // The stream stays registered so `cancel_request` stops it.
+        let (stop_tx, stop_rx) = oneshot::channel();
+        if let Ok(mut pending) = in_flight.0.lock() {
+            pending.insert(request_id.clone(), stop_tx);
+        }
+        let pending = in_flight.0.clone();
+        let decoder = Decoder::for_header(content_encoding(stream.headers()), &decode_disabled);
+        tauri::async_runtime::spawn(async move {
+            sse::forward(app, request_id.clone(), stream, decoder, stop_rx).await;
+            if let Ok(mut pending) = pending.lock() {
+                pending.remove(&request_id);
+            }
+        });
+    }
+    Ok(response)
+}
+
+#[tauri::command]
+pub fn cancel_request(
+    in_flight: State<'_, InFlightRequests>,
+    request_id: String,
+) -> Result<bool, String> {
+    let sender = in_flight
+        .0
+        .lock()
+        .map_err(|err| err.to_string())?
+        .remove(&request_id);
+    Ok(sender
+        .map(|sender| sender.send(()).is_ok())
+        .unwrap_or(false))
+}
+
+/// The response, plus the still-open body when it is an event stream to forward.
+type Execution = (HttpResponse, Option<reqwest::Response>);
+
+async fn execute_request(
+    client: &reqwest::Client,
+    request: HttpRequest,
+    body_limit: u64,
+    allow_stream: bool,
+) -> Result<Execution, String> {
+    let marks = Arc::new(Mutex::new(PhaseMarks::default()));
+    timing::with_phases(
+        marks.clone(),
+        execute_hops(client, request, &marks, body_limit, allow_stream),
+    )
+    .await
+}
+
+async fn execute_hops(
+    client: &reqwest::Client,
+    request: HttpRequest,
+    marks: &Arc<Mutex<PhaseMarks>>,
+    body_limit: u64,
+    allow_stream: bool,
+) -> Result<Execution, String> {
+    let request_start = Instant::now();
+    let mut method = Method::from_bytes(request.method.as_bytes())
+        .map_err(|err| format!("invalid method: {err}"))?;
+    let timeout_ms = request.timeout_ms.filter(|value| *value > 0);
+    let deadline = timeout_ms.map(|timeout_ms| Instant::now() + Duration::from_millis(timeout_ms));
+    let max_redirects = if request.follow_redirects.unwrap_or(true) {
+        request.max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS)
+    } else {
+        0
+    };
+
+    let mut url = request.url;
+    let mut headers = request.headers;
+    let mut body = match request.body_mode.as_ref() {
+        Some(spec) => {
+            let encoded = spec.encode().await?;
+            let has_content_type = headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case("content-type"));
+            if spec.replaces_content_type() || !has_content_type {
+                headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-type"));
+                headers.push(("Content-Type".to_string(), encoded.content_type));
+            }
+            // Streamed bodies have no known length otherwise and would go out chunked.
+            headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-length"));
+            headers.push(("Content-Length".to_string(), encoded.body.len().to_string()));
+            Some(encoded.body)
+        }
+        None => request
+            .body
+            .map(|body| PreparedBody::Bytes(body.into_bytes())),
+    };
+    if let (Some(encoding), Some(prepared)) = (request.compress_body, body.take()) {
+        let compressed = encoding.compress(&prepared.read_all().await?).await?;
+        headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-encoding"));
+        headers.push(("Content-Encoding".to_string(), encoding.token().to_string()));
+        if let Some((_, length)) = headers
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        {
+            *length = compressed.len().to_string();
+        }
+        body = Some(PreparedBody::Bytes(compressed));
+    }
+    let has_accept_encoding = headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("accept-encoding"));
+    if let Some(accept) = accept_encoding(&request.decode_disabled).filter(|_| !has_accept_encoding)
+    {
+        headers.push(("Accept-Encoding".to_string(), accept));
+    }
+    let mut redirects = Vec::new();
+    let mut attempts = Vec::new();
+
+    let (response, hop_start) = loop {
+        let mut attempt = 1;
+        let (response, hop_start) = loop {
+            let hop_start = Instant::now();
+            timing::reset(marks);
+            let mut builder = client.request(method.clone(), &url);
+
+            for (key, value) in &headers {
+                builder = builder.header(key, value);
+            }
+
+            if let Some(body) = body.as_ref() {
+                builder = builder.body(body.to_body().await?);
+            }
+
+            let sent = within_deadline(deadline, timeout_ms, builder.send()).await?;
+            let retry = match &sent {
+                Ok(response) => request.retry.retries_status(response.status()),
+                Err(err) => request.retry.retries_error(err),
+            };
+            if !retry || !request.retry.allows_another(attempt) {
+                let context = if attempt > 1 {
+                    format!("request failed after {attempt} attempts")
+                } else {
+                    "request failed".to_string()
+                };
+                break (
+                    sent.map_err(|err| request_error(&context, &err, timeout_ms))?,
+                    hop_start,
+                );
+            }
+
+            let delay = request.retry.delay(
+                attempt,
+                sent.as_ref().ok().map(|response| response.headers()),
+            );
+            attempts.push(RetryAttempt {
+                url: url.clone(),
+                status: sent
+                    .as_ref()
+                    .ok()
+                    .map(|response| response.status().as_u16()),
+                error: sent.as_ref().err().map(|err| err.to_string()),
+                duration_ms: hop_start.elapsed().as_secs_f64() * 1000.0,
+                delay_ms: delay.as_millis() as u64,
+            });
+            drop(sent);
+            within_deadline(deadline, timeout_ms, tokio::time::sleep(delay)).await?;
+            attempt += 1;
+        };
+
+        let Some((location, next_url)) = redirect_target(&response) else {
+            break (response, hop_start);
+        };
+        if redirects.len() >= max_redirects {
+            break (response, hop_start);
+        }
+
+        let status = response.status();
+        redirects.push(RedirectHop {
+            url: response.url().to_string(),
+            status: status.as_u16(),
+            location,
+            headers: collect_headers(response.headers()).1,
+        });
+
+        if status == StatusCode::SEE_OTHER
+            || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND)
+                && method == Method::POST)
+        {
+            method = Method::GET;
+            body = None;
+            headers.retain(|(key, _)| {
+                !key.eq_ignore_ascii_case("content-type")
+                    && !key.eq_ignore_ascii_case("content-length")
+            });
+        }
+        if next_url.origin() != response.url().origin() {
+            headers.retain(|(key, _)| {
+                !["authorization", "cookie", "proxy-authorization"]
+                    .iter()
+                    .any(|sensitive| key.eq_ignore_ascii_case(sensitive))
+            });
+        }
+        url = next_url.to_string();
+    };
+
+    let headers_received = Instant::now();
+    let status = response.status();
+    let url = response.url().to_string();
+    let version = format!("{:?}", response.version());
+    let (headers, raw_headers) = collect_headers(response.headers());
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    let (body, stream) = if allow_stream && sse::is_event_stream(content_type.as_deref()) {
+        (ReadBody::default(), Some(response))
+    } else {
+        let decoder = Decoder::for_header(
+            content_encoding(response.headers()),
+            &request.decode_disabled,
+        );
+        let read = spool::read_body(response, body_limit, decoder);
+        let body = within_deadline(deadline, timeout_ms, read).await??;
+        (body, None)
+    };
+    let bytes = &body.bytes;
+    let body_received = Instant::now();
+    let timing = marks
+        .lock()
+        .map(|marks| {
+            ResponseTiming::from_marks(
+                &marks,
+                request_start,
+                hop_start,
+                headers_received,
+                body_received,
+            )
+        })
+        .unwrap_or_default();
+
+    let body_kind = detect_body_kind(content_type.as_deref(), bytes);
+    let (data, body_base64) = match body_kind {
+        BodyKind::Text => (String::from_utf8_lossy(bytes).to_string(), None),
+        _ => (String::new(), Some(STANDARD.encode(bytes))),
+    };
+
+    let response = HttpResponse {
+        url,
+        status: status.as_u16(),
+        ok: status.is_success(),
+        headers,
+        raw_headers,
+        data,
+        body_base64,
+        body_kind,
+        content_type,
+        size: body.size,
+        wire_size: body.wire_size,
+        content_encoding: body.decoded.map(|encoding| encoding.token().to_string()),
+        body_file: body
+            .file
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string()),
+        redirects,
+        attempts,
+        timing,
+        version,
+        streaming: stream.is_some(),
+    };
+    Ok((response, stream))
+}
+
+/// Applies the request timeout to one await point. Event streams are read outside of
+/// it, so the deadline is not attached to the reqwest request itself.
+async fn within_deadline<F: Future>(
+    deadline: Option<Instant>,
+    timeout_ms: Option<u64>,
+    future: F,
+) -> Result<F::Output, String> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.into(), future)
+            .await
+            .map_err(|_| {
+                format!(
+                    "request timed out after {} ms",
+                    timeout_ms.unwrap_or_default()
+                )
+            }),
+        None => Ok(future.await),
+    }
+}
+
+/// Returns the raw `Location` value and the resolved next URL when the response is a
+/// redirect that should be followed.
+fn redirect_target(response: &reqwest::Response) -> Option<(String, reqwest::Url)> {
+    if !matches!(
+        response.status(),
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    ) {
+        return None;
+    }
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)?
+        .to_str()
+        .ok()?
+        .to_string();
+    let next_url = response.url().join(&location).ok()?;
+    Some((location, next_url))
+}
+
+fn content_encoding(headers: &reqwest::header::HeaderMap) -> Option<&str> {
+    headers
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn collect_headers(
+    headers: &reqwest::header::HeaderMap,
+) -> (HashMap<String, String>, HashMap<String, Vec<String>>) {
+    let mut flat = HashMap::new();
+    let mut raw: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, value) in headers.iter() {
+        let name = name.to_string();
+        let value = value.to_str().unwrap_or("").to_string();
+        flat.insert(name.clone(), value.clone());
+        raw.entry(name).or_default().push(value);
+    }
+    (flat, raw)
+}
+
+fn request_error(context: &str, err: &reqwest::Error, timeout_ms: Option<u64>) -> String {
+    match timeout_ms {
+        Some(timeout_ms) if err.is_timeout() => format!("request timed out after {timeout_ms} ms"),
+        _ => format!("{context}: {err}"),
+    }
+}
+
+fn detect_body_kind(content_type: Option<&str>, bytes: &[u8]) -> BodyKind {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if mime.starts_with("image/") && mime != "image/svg+xml" {
+        return BodyKind::Image;
+    }
+    if mime == "application/pdf" {
+        return BodyKind::Pdf;
+    }
+    if mime.starts_with("text/")
+        || mime.ends_with("json")
+        || mime.ends_with("xml")
+        || mime == "application/javascript"
+        || mime == "application/x-www-form-urlencoded"
+    {
+        return BodyKind::Text;
+    }
+
+    if bytes.starts_with(b"%PDF-") {
+        return BodyKind::Pdf;
+    }
+    const IMAGE_SIGNATURES: [&[u8]; 4] =
+        [b"\x89PNG\r\n\x1a\n", b"\xff\xd8\xff", b"GIF87a", b"GIF89a"];
+    if IMAGE_SIGNATURES
+        .iter()
+        .any(|signature| bytes.starts_with(signature))
+        || (bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP")
+    {
+        return BodyKind::Image;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.contains('\0') => BodyKind::Text,
+        _ => BodyKind::Binary,
+    }
+}
@@ -1,19 +1,56 @@
-use std::collections::HashMap;
+mod body;
+mod client;
+mod cookies;
+mod encoding;
+mod history;
+mod http;
+mod proxy;
+mod retry;
+mod settings;
+mod spool;
+mod sse;
+mod storage;
+mod timing;
+mod tls;
+mod vault;
+mod workspace;
+mod ws;
+
 use std::sync::Arc;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use reqwest::Method;
-use serde::{Deserialize, Serialize};
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::webview::PageLoadEvent;
 use tauri::Emitter;
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Manager, RunEvent, WebviewUrl, WebviewWindowBuilder};
+
+use client::ClientPool;
+use cookies::CookieJars;
+use history::HistoryStore;
+use http::InFlightRequests;
+use settings::SettingsStore;
+use vault::VaultStore;
+use workspace::WorkspaceStore;
+use ws::WsConnections;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .manage(InFlightRequests::default())
+        .manage(ClientPool::default())
+        .manage(WsConnections::default())
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(CookieJars::load(data_dir.join("cookies")));
+            app.manage(SettingsStore::load(data_dir.join("settings.json")));
+            app.manage(WorkspaceStore::new(data_dir.join("workspace.json")));
+            app.manage(HistoryStore::new(data_dir.join("history.jsonl")));
+            app.manage(VaultStore::new(data_dir.join("vault.json")));
+            spool::clear();
+            Ok(())
+        })
         .menu(|handle| {
             let open = MenuItem::with_id(handle, "open", "Open", true, Some("cmdOrControl+O"))?;
             let save = MenuItem::with_id(
@@ -23,15 +60,45 @@ pub fn run() {
                 true,
                 Some("cmdOrControl+S"),
             )?;
+            let export_openapi = MenuItem::with_id(
+                handle,
+                "export-openapi",
+                "Export OpenAPI...",
+                true,
+                None::<&str>,
+            )?;
+            let settings = MenuItem::with_id(handle, "settings", "Settings...", true, None::<&str>)?;
             let close = MenuItem::with_id(handle, "close", "Close", true, Some("cmdOrControl+Q"))?;
-            let file_menu = Submenu::with_items(handle, "File", true, &[&open, &save, &close])?;
+            let file_menu = Submenu::with_items(
+                handle,
+                "File",
+                true,
+                &[
+                    &open,
+                    &save,
+                    &export_openapi,
+                    &PredefinedMenuItem::separator(handle)?,
+                    &settings,
+                    &close,
+                ],
+            )?;
+            // Undo and redo go to the focused window, which applies them to its own
+            // history or hands them to the focused text field.
+            let undo = MenuItem::with_id(handle, "undo", "Undo", true, Some("cmdOrControl+Z"))?;
+            let redo = MenuItem::with_id(
+                handle,
+                "redo",
+                "Redo",
+                true,
+                Some("cmdOrControl+Shift+Z"),
+            )?;
             let edit_menu = Submenu::with_items(
                 handle,
                 "Edit",
                 true,
                 &[
-                    &PredefinedMenuItem::undo(handle, None)?,
-                    &PredefinedMenuItem::redo(handle, None)?,
+                    &undo,
+                    &redo,
                     &PredefinedMenuItem::separator(handle)?,
                     &PredefinedMenuItem::cut(handle, None)?,
                     &PredefinedMenuItem::copy(handle, None)?,
@@ -48,105 +115,60 @@ pub fn run() {
             "open" => {
                 let _ = app.emit("menu-event", "open-event");
             }
+            "export-openapi" => {
+                let _ = app.emit("menu-event", "export-openapi-event");
+            }
+            "settings" => {
+                let _ = app.emit("menu-event", "settings-event");
+            }
+            id @ ("undo" | "redo") => {
+                let focused = app
+                    .webview_windows()
+                    .into_values()
+                    .find(|window| window.is_focused().unwrap_or(false));
+                if let Some(window) = focused {
+                    let _ = app.emit_to(window.label(), "menu-event", format!("{id}-event"));
+                }
+            }
             "close" => {
                 app.exit(0);
             }
             _ => {}
         })
         .invoke_handler(tauri::generate_handler![
-            send_request,
+            http::send_request,
+            http::cancel_request,
+            body::file_info,
+            spool::save_response_file,
+            spool::discard_response_file,
+            ws::ws_connect,
+            ws::ws_send,
+            ws::ws_close,
+            cookies::list_cookies,
+            cookies::save_cookie,
+            cookies::delete_cookie,
+            cookies::clear_cookies,
+            settings::get_app_settings,
+            settings::save_app_settings,
+            workspace::load_workspace,
+            workspace::save_workspace,
+            history::append_history,
+            history::list_history,
+            history::get_history_entry,
+            history::clear_history,
+            vault::load_vault,
+            vault::save_vault,
             open_preview,
             open_tools,
             set_window_title
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
-
-#[derive(Debug, Deserialize)]
-struct HttpRequest {
-    method: String,
-    url: String,
-    headers: HashMap<String, String>,
-    body: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-struct HttpResponse {
-    url: String,
-    status: u16,
-    ok: bool,
-    headers: HashMap<String, String>,
-    raw_headers: HashMap<String, Vec<String>>,
-    data: String,
-}
-
-#[tauri::command]
-async fn send_request(
-    request: Option<HttpRequest>,
-    method: Option<String>,
-    url: Option<String>,
-    headers: Option<HashMap<String, String>>,
-    body: Option<String>,
-) -> Result<HttpResponse, String> {
-    let request = match request {
-        Some(request) => request,
-        None => {
-            let method = method.ok_or_else(|| "missing request.method".to_string())?;
-            let url = url.ok_or_else(|| "missing request.url".to_string())?;
-            HttpRequest {
-                method,
-                url,
-                headers: headers.unwrap_or_default(),
-                body,
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_, event| {
+            if let RunEvent::Exit = event {
+                spool::clear();
             }
-        }
-    };
-
-    let method = Method::from_bytes(request.method.as_bytes())
-        .map_err(|err| format!("invalid method: {err}"))?;
-
-    let client = reqwest::Client::new();
-    let mut builder = client.request(method, &request.url);
-
-    for (key, value) in request.headers {
-        builder = builder.header(key, value);
-    }
-
-    if let Some(body) = request.body {
-        builder = builder.body(body);
-    }
-
-    let response = builder
-        .send()
-        .await
-        .map_err(|err| format!("request failed: {err}"))?;
-
-    let status = response.status();
-    let url = response.url().to_string();
-
-    let mut headers = HashMap::new();
-    let mut raw_headers: HashMap<String, Vec<String>> = HashMap::new();
-    for (name, value) in response.headers().iter() {
-        let name = name.to_string();
-        let value = value.to_str().unwrap_or("").to_string();
-        headers.insert(name.clone(), value.clone());
-        raw_headers.entry(name).or_default().push(value);
-    }
-
-    let data = response
-        .text()
-        .await
-        .map_err(|err| format!("read response failed: {err}"))?;
-
-    Ok(HttpResponse {
-        url,
-        status: status.as_u16(),
-        ok: status.is_success(),
-        headers,
-        raw_headers,
-        data,
-    })
+        });
 }
 
 #[tauri::command]
@@ -0,0 +1,89 @@
+use reqwest::{NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+
+/// An explicit proxy: `http://`, `https://`, `socks5://` or `socks5h://` URL.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    /// Comma separated hosts, domains and CIDR ranges that bypass the proxy.
+    #[serde(default)]
+    pub no_proxy: String,
+}
+
+/// Proxy selection stored on a server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum ServerProxy {
+    /// Use the global proxy settings.
+    #[default]
+    Inherit,
+    /// Connect directly, even if a global proxy is configured.
+    Direct,
+    Custom(ProxyConfig),
+}
+
+/// App-wide proxy settings, used by servers that inherit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalProxy {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    /// Honor `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` when no proxy is set.
+    #[serde(default)]
+    pub use_env: bool,
+}
+
+/// The proxy a client is actually built with.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum EffectiveProxy {
+    #[default]
+    Direct,
+    Environment,
+    Manual(ProxyConfig),
+}
+
+impl EffectiveProxy {
+    pub fn resolve(server: &ServerProxy, global: &GlobalProxy) -> Self {
+        match server {
+            ServerProxy::Direct => EffectiveProxy::Direct,
+            ServerProxy::Custom(config) if !config.url.trim().is_empty() => {
+                EffectiveProxy::Manual(config.clone())
+            }
+            ServerProxy::Custom(_) | ServerProxy::Inherit => {
+                if global.enabled && !global.proxy.url.trim().is_empty() {
+                    EffectiveProxy::Manual(global.proxy.clone())
+                } else if global.use_env {
+                    EffectiveProxy::Environment
+                } else {
+                    EffectiveProxy::Direct
+                }
+            }
+        }
+    }
+
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, String> {
+        match self {
+            // reqwest reads the proxy environment variables unless told otherwise.
+            EffectiveProxy::Environment => Ok(builder),
+            EffectiveProxy::Direct => Ok(builder.no_proxy()),
+            EffectiveProxy::Manual(config) => {
+                let mut proxy = Proxy::all(config.url.trim())
+                    .map_err(|err| format!("invalid proxy URL: {err}"))?;
+                if !config.username.is_empty() {
+                    proxy = proxy.basic_auth(&config.username, &config.password);
+                }
+                let no_proxy = NoProxy::from_string(&config.no_proxy);
+                Ok(builder.no_proxy().proxy(proxy.no_proxy(no_proxy)))
+            }
+        }
+    }
+}
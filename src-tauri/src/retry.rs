@@ -0,0 +1,149 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Longest `Retry-After` honored; a server asking for more is waited on this long. The
+/// request timeout, when set, still ends the wait first.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// Server retry policy. Attempts beyond the first are made for the listed statuses and,
+/// when enabled, for connection errors, waiting an exponential backoff with jitter or
+/// the server's `Retry-After` in between.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    #[serde(default = "one")]
+    pub max_attempts: u32,
+    #[serde(default)]
+    pub statuses: Vec<u16>,
+    #[serde(default)]
+    pub network_errors: bool,
+    #[serde(default)]
+    pub base_delay_ms: u64,
+    /// Caps the backoff; `Retry-After` is capped by `MAX_RETRY_AFTER` instead.
+    #[serde(default)]
+    pub max_delay_ms: u64,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            statuses: Vec::new(),
+            network_errors: false,
+            base_delay_ms: 0,
+            max_delay_ms: 0,
+        }
+    }
+}
+
+/// An attempt that was retried, as listed in the response debug view.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryAttempt {
+    pub url: String,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    /// Wait before the next attempt.
+    pub delay_ms: u64,
+}
+
+impl RetryPolicy {
+    /// Whether attempt number `attempt` (1-based) may be followed by another one.
+    pub fn allows_another(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    pub fn retries_status(&self, status: StatusCode) -> bool {
+        self.statuses.contains(&status.as_u16())
+    }
+
+    pub fn retries_error(&self, err: &reqwest::Error) -> bool {
+        self.network_errors && (err.is_connect() || err.is_request() || err.is_timeout())
+    }
+
+    /// Delay after attempt number `attempt`: `Retry-After` when the response carries
+    /// one, otherwise `base * 2^(attempt - 1)` with jitter over its upper half.
+    pub fn delay(&self, attempt: u32, headers: Option<&HeaderMap>) -> Duration {
+        if let Some(wait) = headers.and_then(retry_after) {
+            return wait.min(MAX_RETRY_AFTER);
+        }
+        let backoff = self
+            .base_delay_ms
+            .saturating_mul(1u64 << (attempt - 1).min(20))
+            .min(self.max_delay_ms.max(self.base_delay_ms));
+        let half = backoff / 2;
+        Duration::from_millis(half + jitter(backoff - half))
+    }
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Uniform-enough value in `0..=max`, seeded by the std hasher's random keys.
+fn jitter(max: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u32(elapsed.subsec_nanos());
+    }
+    hasher.finish() % (max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retry_after_headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn retry_after_is_not_capped_by_max_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            ..RetryPolicy::default()
+        };
+        let headers = retry_after_headers("120");
+        assert_eq!(policy.delay(1, Some(&headers)), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn retry_after_applies_without_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            ..RetryPolicy::default()
+        };
+        let headers = retry_after_headers("2");
+        assert_eq!(policy.delay(1, Some(&headers)), Duration::from_secs(2));
+        assert_eq!(policy.delay(1, None), Duration::ZERO);
+    }
+
+    #[test]
+    fn retry_after_is_capped_by_its_own_ceiling() {
+        let headers = retry_after_headers("86400");
+        let delay = RetryPolicy::default().delay(1, Some(&headers));
+        assert_eq!(delay, MAX_RETRY_AFTER);
+    }
+}
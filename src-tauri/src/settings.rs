@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::client::ClientPool;
+use crate::proxy::GlobalProxy;
+use crate::storage::write_atomic;
+
+/// App-wide preferences that are not part of a collection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    #[serde(default)]
+    pub proxy: GlobalProxy,
+    /// Responses larger than this many megabytes are spooled to a temp file.
+    #[serde(default)]
+    pub response_limit_mb: Option<u64>,
+    /// Saves the last response of each tab with the workspace.
+    #[serde(default)]
+    pub restore_responses: bool,
+}
+
+/// `AppSettings` persisted to `<app data>/settings.json`.
+pub struct SettingsStore {
+    file: PathBuf,
+    settings: Mutex<AppSettings>,
+}
+
+impl SettingsStore {
+    pub fn load(file: PathBuf) -> Self {
+        let settings = fs::read(&file)
+            .ok()
+            .and_then(|contents| serde_json::from_slice(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            file,
+            settings: Mutex::new(settings),
+        }
+    }
+
+    pub fn current(&self) -> AppSettings {
+        self.settings
+            .lock()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    fn replace(&self, next: AppSettings) -> Result<(), String> {
+        let json = serde_json::to_vec_pretty(&next).map_err(|err| err.to_string())?;
+        write_atomic(&self.file, &json)?;
+        *self.settings.lock().map_err(|err| err.to_string())? = next;
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn get_app_settings(store: State<'_, SettingsStore>) -> AppSettings {
+    store.current()
+}
+
+#[tauri::command]
+pub fn save_app_settings(
+    store: State<'_, SettingsStore>,
+    clients: State<'_, ClientPool>,
+    settings: AppSettings,
+) -> Result<(), String> {
+    store.replace(settings)?;
+    clients.clear();
+    Ok(())
+}
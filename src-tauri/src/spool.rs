@@ -0,0 +1,182 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::io::AsyncWriteExt;
+
+use crate::encoding::{ContentEncoding, Decoder};
+
+/// In-memory body limit used when the app settings do not set one.
+pub const DEFAULT_LIMIT_MB: u64 = 10;
+
+/// Bytes of a spooled body kept in memory and sent to the UI as a preview.
+const PREVIEW_BYTES: usize = 256 * 1024;
+
+/// A response body read up to the in-memory limit. Past the limit the whole body is
+/// written to a temp file and `bytes` only holds the preview.
+#[derive(Default)]
+pub struct ReadBody {
+    pub bytes: Vec<u8>,
+    /// Decoded size.
+    pub size: u64,
+    /// Bytes received before decoding.
+    pub wire_size: u64,
+    pub file: Option<PathBuf>,
+    /// Coding the body was decoded from, if any.
+    pub decoded: Option<ContentEncoding>,
+}
+
+/// Reads `response` into memory, decoding it on the way when a decoder is given, and
+/// switches to a temp file once the decoded body grows past `limit` bytes.
+pub async fn read_body(
+    mut response: reqwest::Response,
+    limit: u64,
+    mut decoder: Option<Decoder>,
+) -> Result<ReadBody, String> {
+    let mut sink = Sink {
+        limit,
+        ..Sink::default()
+    };
+    let mut wire_size = 0u64;
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| format!("read response failed: {err}"))?
+    {
+        wire_size += chunk.len() as u64;
+        match decoder.as_mut() {
+            Some(decoder) => sink.push(&decoder.write(&chunk).await?).await?,
+            None => sink.push(&chunk).await?,
+        }
+    }
+    // Bodiless responses (HEAD, 204, 304) may still name a coding.
+    if wire_size == 0 {
+        decoder = None;
+    }
+    if let Some(decoder) = decoder.as_mut() {
+        sink.push(&decoder.finish().await?).await?;
+    }
+
+    let file = match sink.spooled {
+        Some((path, mut file)) => {
+            file.flush()
+                .await
+                .map_err(|err| format!("write {}: {err}", path.display()))?;
+            trim_partial_char(&mut sink.bytes);
+            Some(path)
+        }
+        None => None,
+    };
+    Ok(ReadBody {
+        bytes: sink.bytes,
+        size: sink.size,
+        wire_size,
+        file,
+        decoded: decoder.map(|decoder| decoder.encoding()),
+    })
+}
+
+#[derive(Default)]
+struct Sink {
+    limit: u64,
+    bytes: Vec<u8>,
+    size: u64,
+    spooled: Option<(PathBuf, tokio::fs::File)>,
+}
+
+impl Sink {
+    async fn push(&mut self, data: &[u8]) -> Result<(), String> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.size += data.len() as u64;
+        match self.spooled.as_mut() {
+            Some((path, file)) => write(file, path, data).await?,
+            None if self.size > self.limit => {
+                let path = next_path()?;
+                let mut file = tokio::fs::File::create(&path)
+                    .await
+                    .map_err(|err| format!("create {}: {err}", path.display()))?;
+                write(&mut file, &path, &self.bytes).await?;
+                write(&mut file, &path, data).await?;
+                self.bytes.truncate(PREVIEW_BYTES);
+                self.spooled = Some((path, file));
+            }
+            None => {
+                self.bytes.extend_from_slice(data);
+                return Ok(());
+            }
+        }
+        // The preview keeps filling from each chunk until it is full.
+        let room = PREVIEW_BYTES.saturating_sub(self.bytes.len());
+        self.bytes.extend_from_slice(&data[..room.min(data.len())]);
+        Ok(())
+    }
+}
+
+/// Drops a UTF-8 character the preview cut in half, so a text body still reads as
+/// text. Previews that are not UTF-8 up to that point are left alone.
+fn trim_partial_char(preview: &mut Vec<u8>) {
+    if let Err(err) = std::str::from_utf8(preview) {
+        if err.error_len().is_none() {
+            preview.truncate(err.valid_up_to());
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn save_response_file(source: String, destination: String) -> Result<(), String> {
+    let source = spooled(&source)?;
+    tokio::fs::copy(source, destination.trim())
+        .await
+        .map(|_| ())
+        .map_err(|err| format!("failed to save {}: {err}", destination.trim()))
+}
+
+#[tauri::command]
+pub async fn discard_response_file(path: String) -> Result<(), String> {
+    let path = spooled(&path)?;
+    tokio::fs::remove_file(path)
+        .await
+        .map_err(|err| format!("remove {}: {err}", path.display()))
+}
+
+/// Removes the bodies this process spooled: on launch, in case an earlier process with
+/// the same id left some, and on exit. Other running instances keep theirs.
+pub fn clear() {
+    let _ = std::fs::remove_dir_all(spool_dir());
+}
+
+/// Spool directory of this process, so no instance deletes files another one shows.
+fn spool_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("rustman-responses")
+        .join(std::process::id().to_string())
+}
+
+fn spooled(path: &str) -> Result<&Path, String> {
+    let path = Path::new(path);
+    if path.parent() != Some(spool_dir().as_path()) {
+        return Err(format!("{} is not a spooled response", path.display()));
+    }
+    Ok(path)
+}
+
+fn next_path() -> Result<PathBuf, String> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let dir = spool_dir();
+    std::fs::create_dir_all(&dir).map_err(|err| format!("create {}: {err}", dir.display()))?;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ok(dir.join(format!("{nanos:016x}{count:04x}.body")))
+}
+
+async fn write(file: &mut tokio::fs::File, path: &Path, bytes: &[u8]) -> Result<(), String> {
+    file.write_all(bytes)
+        .await
+        .map_err(|err| format!("write {}: {err}", path.display()))
+}
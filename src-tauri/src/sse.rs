@@ -0,0 +1,165 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
+
+use crate::encoding::Decoder;
+
+/// Tauri event carrying each parsed server-sent event.
+const EVENT_CHANNEL: &str = "sse-event";
+/// Tauri event sent once when a stream ends, fails or is stopped.
+const CLOSED_CHANNEL: &str = "sse-closed";
+
+pub fn is_event_stream(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("text/event-stream"))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamEvent {
+    request_id: String,
+    id: Option<String>,
+    event: String,
+    data: String,
+    /// Milliseconds since the Unix epoch.
+    received_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamClosed {
+    request_id: String,
+    error: Option<String>,
+}
+
+/// Reads an event stream body until it ends or `stop` fires, emitting every event.
+pub async fn forward(
+    app: AppHandle,
+    request_id: String,
+    mut response: reqwest::Response,
+    mut decoder: Option<Decoder>,
+    stop: oneshot::Receiver<()>,
+) {
+    let mut parser = EventParser::default();
+    let reading = async {
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| format!("stream failed: {err}"))?
+        {
+            let chunk = match decoder.as_mut() {
+                Some(decoder) => decoder.write(&chunk).await?,
+                None => chunk.to_vec(),
+            };
+            for event in parser.feed(&chunk) {
+                let _ = app.emit(
+                    EVENT_CHANNEL,
+                    StreamEvent {
+                        request_id: request_id.clone(),
+                        id: event.id,
+                        event: event.event,
+                        data: event.data,
+                        received_at: now_millis(),
+                    },
+                );
+            }
+        }
+        Ok::<(), String>(())
+    };
+
+    let error = tokio::select! {
+        result = reading => result.err(),
+        _ = stop => None,
+    };
+    let _ = app.emit(CLOSED_CHANNEL, StreamClosed { request_id, error });
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+struct ParsedEvent {
+    id: Option<String>,
+    event: String,
+    data: String,
+}
+
+/// Incremental `text/event-stream` parser following the WHATWG interpretation rules.
+#[derive(Debug, Default)]
+struct EventParser {
+    line: Vec<u8>,
+    /// The previous chunk ended in `\r`, so a leading `\n` belongs to that line break.
+    after_cr: bool,
+    data: String,
+    has_data: bool,
+    event: Option<String>,
+    last_id: Option<String>,
+}
+
+impl EventParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<ParsedEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::take(&mut self.after_cr);
+            match byte {
+                b'\n' if after_cr => {}
+                b'\n' | b'\r' => {
+                    self.after_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<ParsedEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ParsedEvent> {
+        let event = self.event.take();
+        if !std::mem::take(&mut self.has_data) {
+            return None;
+        }
+        Some(ParsedEvent {
+            id: self.last_id.clone(),
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data),
+        })
+    }
+}
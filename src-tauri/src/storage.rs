@@ -0,0 +1,32 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Writes `contents` to a sibling temp file, syncs it and renames it over `path`,
+/// so a crash mid-write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("create {}: {err}", parent.display()))?;
+    }
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = Path::new(&tmp_name);
+    {
+        let mut file = fs::File::create(tmp_path)
+            .map_err(|err| format!("write {}: {err}", tmp_path.display()))?;
+        file.write_all(contents)
+            .and_then(|_| file.sync_all())
+            .map_err(|err| format!("write {}: {err}", tmp_path.display()))?;
+    }
+    fs::rename(tmp_path, path).map_err(|err| format!("replace {}: {err}", path.display()))
+}
+
+/// Stable FNV-1a hash, used to derive file names from arbitrary keys such as server URLs.
+pub fn stable_hash(value: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in value.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -0,0 +1,203 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use rustls::client::{ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue};
+use rustls::pki_types::ServerName;
+use rustls::NamedGroup;
+use serde::Serialize;
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    /// Connection phase marks for the request being driven by the current task.
+    static PHASES: Arc<Mutex<PhaseMarks>>;
+}
+
+/// Instants recorded by the resolver, connector and TLS hooks while a hop connects.
+/// All of them stay `None` when a pooled connection is reused.
+#[derive(Debug, Default, Clone)]
+pub struct PhaseMarks {
+    dns_start: Option<Instant>,
+    dns_end: Option<Instant>,
+    connect_start: Option<Instant>,
+    tls_start: Option<Instant>,
+    connect_end: Option<Instant>,
+}
+
+/// Phase durations in milliseconds for the final hop, plus time spent on redirects.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTiming {
+    pub dns_ms: Option<f64>,
+    pub connect_ms: Option<f64>,
+    pub tls_ms: Option<f64>,
+    pub ttfb_ms: f64,
+    pub download_ms: f64,
+    pub redirect_ms: f64,
+    pub total_ms: f64,
+    pub reused_connection: bool,
+}
+
+/// Runs `future` with a fresh set of phase marks visible to the timing hooks.
+pub async fn with_phases<F: Future>(marks: Arc<Mutex<PhaseMarks>>, future: F) -> F::Output {
+    PHASES.scope(marks, future).await
+}
+
+/// Clears the marks before the next hop of a redirect chain connects.
+pub fn reset(marks: &Arc<Mutex<PhaseMarks>>) {
+    if let Ok(mut marks) = marks.lock() {
+        *marks = PhaseMarks::default();
+    }
+}
+
+fn mark(update: impl FnOnce(&mut PhaseMarks)) {
+    let _ = PHASES.try_with(|marks| {
+        if let Ok(mut marks) = marks.lock() {
+            update(&mut marks);
+        }
+    });
+}
+
+impl ResponseTiming {
+    pub fn from_marks(
+        marks: &PhaseMarks,
+        request_start: Instant,
+        hop_start: Instant,
+        headers_received: Instant,
+        body_received: Instant,
+    ) -> Self {
+        let connect_start = marks.dns_start.or(marks.connect_start);
+        let tcp_start = marks.dns_end.or(marks.connect_start);
+        let tcp_end = marks.tls_start.or(marks.connect_end);
+        let ready = marks.connect_end.unwrap_or(hop_start);
+
+        Self {
+            dns_ms: between(marks.dns_start, marks.dns_end),
+            connect_ms: between(tcp_start, tcp_end),
+            tls_ms: between(marks.tls_start, marks.connect_end),
+            ttfb_ms: millis(ready.max(hop_start), headers_received),
+            download_ms: millis(headers_received, body_received),
+            redirect_ms: millis(request_start, hop_start),
+            total_ms: millis(request_start, body_received),
+            reused_connection: connect_start.is_none(),
+        }
+    }
+}
+
+fn between(start: Option<Instant>, end: Option<Instant>) -> Option<f64> {
+    Some(millis(start?, end?))
+}
+
+fn millis(start: Instant, end: Instant) -> f64 {
+    end.saturating_duration_since(start).as_secs_f64() * 1000.0
+}
+
+/// System resolver that records how long each lookup takes.
+#[derive(Debug, Default)]
+pub struct TimedResolver;
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            mark(|marks| marks.dns_start = Some(Instant::now()));
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            mark(|marks| marks.dns_end = Some(Instant::now()));
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Connector layer marking when connection establishment (TCP + TLS) starts and ends.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimedConnectLayer;
+
+impl<S> Layer<S> for TimedConnectLayer {
+    type Service = TimedConnect<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimedConnect { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimedConnect<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for TimedConnect<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            mark(|marks| {
+                marks.connect_start.get_or_insert_with(Instant::now);
+            });
+            let result = connecting.await;
+            mark(|marks| marks.connect_end = Some(Instant::now()));
+            result
+        })
+    }
+}
+
+/// Session store wrapper used only to learn when the TLS handshake begins: rustls asks
+/// for the key-exchange hint while building the ClientHello, right after TCP connects.
+#[derive(Debug)]
+pub struct TlsStartMarker(pub Arc<dyn ClientSessionStore>);
+
+impl ClientSessionStore for TlsStartMarker {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.0.set_kx_hint(server_name, group);
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        mark(|marks| {
+            marks.tls_start.get_or_insert_with(Instant::now);
+        });
+        self.0.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.0.set_tls12_session(server_name, value);
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        self.0.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.0.remove_tls12_session(server_name);
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.0.insert_tls13_ticket(server_name, value);
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        self.0.take_tls13_ticket(server_name)
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{ClientSessionMemoryCache, Resumption};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde::{Deserialize, Serialize};
+
+use crate::timing::TlsStartMarker;
+
+/// Per-server TLS settings. Certificates and keys are referenced by file path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsOptions {
+    /// PEM bundle trusted in addition to the built-in web roots.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    #[serde(default)]
+    pub pkcs12_path: Option<String>,
+    #[serde(default)]
+    pub pkcs12_password: Option<String>,
+    #[serde(default)]
+    pub allow_invalid_certs: bool,
+}
+
+/// Builds the rustls configuration for a client. The session store is wrapped so the
+/// timing hooks can report when each TLS handshake starts.
+pub fn client_config(options: &TlsOptions) -> Result<rustls::ClientConfig, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| format!("invalid TLS configuration: {err}"))?;
+
+    let builder = if options.allow_invalid_certs {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert(provider)))
+    } else {
+        builder.with_root_certificates(root_store(options)?)
+    };
+
+    let mut config = match client_identity(options)? {
+        Some((chain, key)) => builder
+            .with_client_auth_cert(chain, key)
+            .map_err(|err| format!("invalid client certificate: {err}"))?,
+        None => builder.with_no_client_auth(),
+    };
+    config.resumption = Resumption::store(Arc::new(TlsStartMarker(Arc::new(
+        ClientSessionMemoryCache::new(256),
+    ))));
+    Ok(config)
+}
+
+fn root_store(options: &TlsOptions) -> Result<rustls::RootCertStore, String> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    if let Some(path) = non_empty(&options.ca_cert_path) {
+        for cert in read_pem_certs(path)? {
+            roots
+                .add(cert)
+                .map_err(|err| format!("invalid CA certificate in {path}: {err}"))?;
+        }
+    }
+    Ok(roots)
+}
+
+type ClientIdentity = (Vec<CertificateDer<'static>>, PrivateKeyDer<'static>);
+
+fn client_identity(options: &TlsOptions) -> Result<Option<ClientIdentity>, String> {
+    if let Some(path) = non_empty(&options.pkcs12_path) {
+        return read_pkcs12(path, options.pkcs12_password.as_deref().unwrap_or("")).map(Some);
+    }
+
+    match (
+        non_empty(&options.client_cert_path),
+        non_empty(&options.client_key_path),
+    ) {
+        (Some(cert_path), Some(key_path)) => {
+            let chain = read_pem_certs(cert_path)?;
+            let key = PrivateKeyDer::from_pem_file(key_path)
+                .map_err(|err| format!("failed to read client key {key_path}: {err}"))?;
+            Ok(Some((chain, key)))
+        }
+        (Some(_), None) => Err("client certificate requires a private key".to_string()),
+        (None, Some(_)) => Err("client key requires a certificate".to_string()),
+        (None, None) => Ok(None),
+    }
+}
+
+fn read_pem_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| format!("failed to read certificates from {path}: {err}"))?;
+    if certs.is_empty() {
+        return Err(format!("no certificates found in {path}"));
+    }
+    Ok(certs)
+}
+
+fn read_pkcs12(path: &str, password: &str) -> Result<ClientIdentity, String> {
+    let data = fs::read(path).map_err(|err| format!("failed to read {path}: {err}"))?;
+    let keystore = p12_keystore::KeyStore::from_pkcs12(&data, password)
+        .map_err(|err| format!("failed to open PKCS#12 {path}: {err}"))?;
+    let (_, chain) = keystore
+        .private_key_chain()
+        .ok_or_else(|| format!("no private key found in {path}"))?;
+    let certs = chain
+        .chain()
+        .iter()
+        .map(|cert| CertificateDer::from(cert.as_der().to_vec()))
+        .collect();
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(chain.key().to_vec()));
+    Ok((certs, key))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Verifier for the "allow invalid certificates" toggle: skips chain and hostname checks
+/// but still verifies handshake signatures.
+#[derive(Debug)]
+struct AcceptAnyCert(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::State;
+
+use crate::storage::write_atomic;
+
+/// Encrypted secret vault in `<app data>/vault.json`. Encryption happens in the UI, so
+/// the backend only ever sees the sealed document.
+pub struct VaultStore {
+    file: PathBuf,
+    write: Mutex<()>,
+}
+
+impl VaultStore {
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            write: Mutex::new(()),
+        }
+    }
+}
+
+#[tauri::command]
+pub fn load_vault(store: State<'_, VaultStore>) -> Result<Option<Value>, String> {
+    let contents = match fs::read(&store.file) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("read {}: {err}", store.file.display())),
+    };
+    // Unlike the workspace, an unreadable vault is never set aside: a new one written
+    // over it would lose every secret for good.
+    serde_json::from_slice(&contents)
+        .map(Some)
+        .map_err(|err| format!("{}: {err}", store.file.display()))
+}
+
+#[tauri::command]
+pub async fn save_vault(store: State<'_, VaultStore>, vault: Value) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(&vault).map_err(|err| err.to_string())?;
+    let _guard = store.write.lock().map_err(|err| err.to_string())?;
+    write_atomic(&store.file, &json)
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::State;
+
+use crate::storage::write_atomic;
+
+/// Tabs and tree of the last session, persisted to `<app data>/workspace.json`. The UI
+/// owns the document's shape; the backend only keeps the writes whole and in order.
+pub struct WorkspaceStore {
+    file: PathBuf,
+    write: Mutex<()>,
+}
+
+impl WorkspaceStore {
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            write: Mutex::new(()),
+        }
+    }
+}
+
+#[tauri::command]
+pub fn load_workspace(store: State<'_, WorkspaceStore>) -> Option<Value> {
+    let contents = fs::read(&store.file).ok()?;
+    match serde_json::from_slice(&contents) {
+        Ok(workspace) => Some(workspace),
+        Err(_) => {
+            // Keep the unreadable file for inspection instead of overwriting it.
+            let mut aside = store.file.as_os_str().to_owned();
+            aside.push(".corrupt");
+            let _ = fs::rename(&store.file, aside);
+            None
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn save_workspace(
+    store: State<'_, WorkspaceStore>,
+    workspace: Value,
+) -> Result<(), String> {
+    let json = serde_json::to_vec(&workspace).map_err(|err| err.to_string())?;
+    let _guard = store.write.lock().map_err(|err| err.to_string())?;
+    write_atomic(&store.file, &json)
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use reqwest::cookie::CookieStore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
+
+use crate::cookies::CookieJars;
+use crate::http::HeaderList;
+use crate::tls::{self, TlsOptions};
+
+/// Tauri event carrying every frame sent or received on a connection.
+const MESSAGE_CHANNEL: &str = "ws-message";
+/// Tauri event sent once when a connection ends, fails or is closed.
+const CLOSED_CHANNEL: &str = "ws-closed";
+
+/// Used when the request does not set its own handshake timeout.
+const DEFAULT_HANDSHAKE_TIMEOUT_MS: u64 = 30_000;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Outgoing message queues of open WebSocket connections, keyed by connection id.
+#[derive(Default)]
+pub struct WsConnections(Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Message>>>>);
+
+impl WsConnections {
+    fn queue(&self, connection_id: &str, message: Message) -> Result<(), String> {
+        let connections = self.0.lock().map_err(|err| err.to_string())?;
+        let sender = connections
+            .get(connection_id)
+            .ok_or_else(|| "connection is not open".to_string())?;
+        sender
+            .send(message)
+            .map_err(|_| "connection is not open".to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsConnectRequest {
+    connection_id: String,
+    url: String,
+    #[serde(default)]
+    headers: HeaderList,
+    /// Base URL of the selected server; its cookie jar is sent with the handshake.
+    #[serde(default)]
+    server_url: Option<String>,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    #[serde(default)]
+    tls: TlsOptions,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsHandshake {
+    status: u16,
+    headers: HashMap<String, String>,
+    protocol: Option<String>,
+}
+
+/// Payload queued by `ws_send`. Binary and ping payloads arrive base64 encoded.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "lowercase")]
+pub enum WsOutgoing {
+    Text(String),
+    Binary(String),
+    Ping(String),
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Direction {
+    In,
+    Out,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WsFrame {
+    connection_id: String,
+    direction: Direction,
+    /// `text`, `binary`, `ping`, `pong` or `close`.
+    kind: &'static str,
+    /// Text payload, base64 for binary, ping and pong frames, or the close reason.
+    data: String,
+    size: usize,
+    close_code: Option<u16>,
+    /// Milliseconds since the Unix epoch.
+    timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WsClosed {
+    connection_id: String,
+    code: Option<u16>,
+    reason: String,
+    error: Option<String>,
+}
+
+#[tauri::command]
+pub async fn ws_connect(
+    app: AppHandle,
+    connections: State<'_, WsConnections>,
+    cookie_jars: State<'_, CookieJars>,
+    request: WsConnectRequest,
+) -> Result<WsHandshake, String> {
+    let mut handshake = request
+        .url
+        .as_str()
+        .into_client_request()
+        .map_err(|err| format!("invalid url: {err}"))?;
+    for (key, value) in &request.headers {
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|err| format!("invalid header {key}: {err}"))?;
+        let value =
+            HeaderValue::from_str(value).map_err(|err| format!("invalid header {key}: {err}"))?;
+        handshake.headers_mut().append(name, value);
+    }
+    if let Some(cookie) = jar_cookies(&cookie_jars, &request) {
+        handshake
+            .headers_mut()
+            .entry(tungstenite::http::header::COOKIE)
+            .or_insert(cookie);
+    }
+
+    let connector = Connector::Rustls(Arc::new(tls::client_config(&request.tls)?));
+    let timeout_ms = request
+        .timeout_ms
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_HANDSHAKE_TIMEOUT_MS);
+    let (socket, response) = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        tokio_tungstenite::connect_async_tls_with_config(handshake, None, false, Some(connector)),
+    )
+    .await
+    .map_err(|_| format!("handshake timed out after {timeout_ms} ms"))?
+    .map_err(|err| format!("handshake failed: {err}"))?;
+
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let protocol = headers.get("sec-websocket-protocol").cloned();
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let connection_id = request.connection_id;
+    if let Ok(mut open) = connections.0.lock() {
+        open.insert(connection_id.clone(), sender);
+    }
+    let open = connections.0.clone();
+    tauri::async_runtime::spawn(async move {
+        run(app, connection_id.clone(), socket, receiver).await;
+        if let Ok(mut open) = open.lock() {
+            open.remove(&connection_id);
+        }
+    });
+
+    Ok(WsHandshake {
+        status: response.status().as_u16(),
+        headers,
+        protocol,
+    })
+}
+
+#[tauri::command]
+pub fn ws_send(
+    connections: State<'_, WsConnections>,
+    connection_id: String,
+    message: WsOutgoing,
+) -> Result<(), String> {
+    let message = match message {
+        WsOutgoing::Text(text) => Message::text(text),
+        WsOutgoing::Binary(data) => Message::binary(decode_payload(&data)?),
+        WsOutgoing::Ping(data) => {
+            let payload = decode_payload(&data)?;
+            if payload.len() > 125 {
+                return Err("ping payload must be at most 125 bytes".to_string());
+            }
+            Message::Ping(payload.into())
+        }
+    };
+    connections.queue(&connection_id, message)
+}
+
+#[tauri::command]
+pub fn ws_close(
+    connections: State<'_, WsConnections>,
+    connection_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let reason = reason.unwrap_or_default();
+    if reason.len() > 123 {
+        return Err("close reason must be at most 123 bytes".to_string());
+    }
+    let frame = CloseFrame {
+        code: CloseCode::from(code.unwrap_or(1000)),
+        reason: reason.into(),
+    };
+    connections.queue(&connection_id, Message::Close(Some(frame)))
+}
+
+/// The server's cookies for the handshake URL, looked up under its http(s) equivalent.
+fn jar_cookies(jars: &CookieJars, request: &WsConnectRequest) -> Option<HeaderValue> {
+    let server = request.server_url.as_deref()?;
+    let mut url = reqwest::Url::parse(&request.url).ok()?;
+    let scheme = if url.scheme() == "wss" {
+        "https"
+    } else {
+        "http"
+    };
+    url.set_scheme(scheme).ok()?;
+    let cookies = jars.jar(server).cookies(&url)?;
+    HeaderValue::from_bytes(cookies.as_bytes()).ok()
+}
+
+fn decode_payload(data: &str) -> Result<Vec<u8>, String> {
+    STANDARD
+        .decode(data.trim())
+        .map_err(|err| format!("invalid base64 payload: {err}"))
+}
+
+/// Pumps the connection until either side closes it, emitting every frame both ways.
+async fn run(
+    app: AppHandle,
+    connection_id: String,
+    socket: Socket,
+    mut outgoing: mpsc::UnboundedReceiver<Message>,
+) {
+    let (mut sink, mut incoming) = socket.split();
+    let mut close: Option<(u16, String)> = None;
+    let emit = |direction: Direction, message: &Message| {
+        let _ = app.emit(MESSAGE_CHANNEL, frame(&connection_id, direction, message));
+    };
+
+    let error = loop {
+        tokio::select! {
+            message = incoming.next() => match message {
+                Some(Ok(message)) => {
+                    if let Message::Close(Some(frame)) = &message {
+                        close = Some((frame.code.into(), frame.reason.to_string()));
+                    }
+                    emit(Direction::In, &message);
+                }
+                Some(Err(tungstenite::Error::ConnectionClosed)) | None => break None,
+                Some(Err(err)) => break Some(format!("connection failed: {err}")),
+            },
+            message = outgoing.recv() => {
+                let Some(message) = message else {
+                    break None;
+                };
+                if let Message::Close(Some(frame)) = &message {
+                    close.get_or_insert((frame.code.into(), frame.reason.to_string()));
+                }
+                match sink.send(message.clone()).await {
+                    Ok(()) => emit(Direction::Out, &message),
+                    Err(err) => break Some(format!("send failed: {err}")),
+                }
+            }
+        }
+    };
+
+    let (code, reason) = match close {
+        Some((code, reason)) => (Some(code), reason),
+        None => (None, String::new()),
+    };
+    let _ = app.emit(
+        CLOSED_CHANNEL,
+        WsClosed {
+            connection_id,
+            code,
+            reason,
+            error,
+        },
+    );
+}
+
+fn frame(connection_id: &str, direction: Direction, message: &Message) -> WsFrame {
+    let (kind, data, close_code) = match message {
+        Message::Text(text) => ("text", text.to_string(), None),
+        Message::Binary(data) => ("binary", STANDARD.encode(data), None),
+        Message::Ping(data) => ("ping", STANDARD.encode(data), None),
+        Message::Pong(data) => ("pong", STANDARD.encode(data), None),
+        Message::Close(Some(frame)) => ("close", frame.reason.to_string(), Some(frame.code.into())),
+        Message::Close(None) => ("close", String::new(), None),
+        Message::Frame(frame) => ("binary", STANDARD.encode(frame.payload()), None),
+    };
+    WsFrame {
+        connection_id: connection_id.to_string(),
+        direction,
+        kind,
+        data,
+        size: message.len(),
+        close_code,
+        timestamp: now_millis(),
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
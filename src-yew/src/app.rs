@@ -1,22 +1,44 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use gloo::events::EventListener;
+use gloo::timers::callback::Timeout;
 use url::Url;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
+use crate::components::proxy_settings::ProxySettingsDialog;
 use crate::components::section::Section;
 use crate::components::side::Side;
 use crate::components::tools::ToolsPage;
+use crate::components::vault::VaultDialog;
+use crate::collection::{build_collection, parse_collection};
+use crate::environment::keep_secrets;
 use crate::openapi::{build_openapi_from_tree, build_tree_from_openapi};
-use crate::state::{Tab, TabAction, TabState, TreeAction, TreeNode, TreeState};
+use crate::state::{
+    Environment, HistoryAction, HistoryState, MethodEnum, Response, ServerEntry, StreamEvent, Tab,
+    TabAction, TabContent, TabState, TreeAction, TreeNode, TreeState, WsClosed, WsMessage, WsStatus,
+};
 use crate::tauri_api;
+use crate::vault::{extract_secrets, has_references, resolve_references, Vault};
+use crate::workspace::{SealedSecrets, Workspace};
+
+/// Quiet period after the last change before the workspace is written.
+const AUTOSAVE_DELAY_MS: u32 = 800;
+
+/// Longest a change waits to be saved while further changes keep arriving, e.g. the
+/// events of a stream.
+const AUTOSAVE_MAX_WAIT_MS: f64 = 5000.0;
 
 #[function_component(App)]
 pub fn app() -> Html {
     let tree_state = use_reducer(TreeState::default);
     let tab_state = use_reducer(TabState::default);
+    let history_state = use_reducer(HistoryState::default);
     let tree_state_ref = use_mut_ref(|| tree_state.clone());
+    let tab_state_ref = use_mut_ref(|| tab_state.clone());
     let app_ref = use_node_ref();
     let sidebar_width = use_state(|| 280.0);
     let dragging = use_state(|| false);
@@ -28,11 +50,145 @@ pub fn app() -> Html {
     let server_input = use_state(String::new);
     let pending_tab = use_state(|| None::<Tab>);
     let pending_tab_index = use_state(|| None::<usize>);
+    let settings_dialog_open = use_state(|| false);
+    let workspace_restored = use_state(|| false);
+    let autosave = use_mut_ref(Autosave::default);
+    // Unlocked on first need and kept for the session.
+    let vault = use_mut_ref(|| None::<Vault>);
+    let vault_prompt = use_state(|| None::<VaultAction>);
+    let live_sockets = use_mut_ref(Vec::<String>::new);
+    let spooled_bodies = use_mut_ref(Vec::<String>::new);
 
     {
         let mut state = tree_state_ref.borrow_mut();
         *state = tree_state.clone();
     }
+    {
+        let mut state = tab_state_ref.borrow_mut();
+        *state = tab_state.clone();
+    }
+
+    {
+        let tab_state = tab_state.clone();
+        let tree_state = tree_state.clone();
+        let workspace_restored = workspace_restored.clone();
+        let vault_prompt = vault_prompt.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(Some(value)) = tauri_api::load_workspace().await {
+                    match Workspace::from_value(value) {
+                        // The vault dialog finishes the restore.
+                        Ok(workspace) if workspace.has_references() => {
+                            vault_prompt.set(Some(VaultAction::Restore(Box::new(workspace))));
+                            return;
+                        }
+                        Ok(workspace) => restore_workspace(&workspace, &tab_state, &tree_state),
+                        Err(err) => show_alert(&format!("Workspace não restaurado: {err}")),
+                    }
+                }
+                workspace_restored.set(true);
+            });
+            || ()
+        });
+    }
+
+    {
+        let history_state = history_state.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(entries) = tauri_api::list_history().await {
+                    history_state.dispatch(HistoryAction::Load(entries));
+                }
+            });
+            || ()
+        });
+    }
+
+    {
+        // Nothing is saved until the previous workspace is back, so it cannot be
+        // overwritten by the empty initial state.
+        let autosave = autosave.clone();
+        let vault = vault.clone();
+        let vault_prompt = vault_prompt.clone();
+        use_effect_with(
+            (tab_state.clone(), tree_state.clone(), *workspace_restored),
+            move |(tab_state, tree_state, restored)| {
+                if *restored {
+                    let tab_state = tab_state.clone();
+                    let tree_state = tree_state.clone();
+                    let now = js_sys::Date::now();
+                    let mut pending = autosave.borrow_mut();
+                    let since = *pending.pending_since.get_or_insert(now);
+                    let delay = (since + AUTOSAVE_MAX_WAIT_MS - now)
+                        .clamp(0.0, f64::from(AUTOSAVE_DELAY_MS)) as u32;
+                    let autosave = autosave.clone();
+                    let vault = vault.clone();
+                    let vault_prompt = vault_prompt.clone();
+                    // Replacing the timer cancels the save scheduled by the previous change.
+                    pending.timer = Some(Timeout::new(delay, move || {
+                        autosave.borrow_mut().pending_since = None;
+                        spawn_local(save_workspace(
+                            tab_state,
+                            tree_state,
+                            autosave,
+                            vault,
+                            vault_prompt,
+                        ));
+                    }));
+                }
+                || ()
+            },
+        );
+    }
+
+    {
+        // A connection whose tab is gone, e.g. taken away by undo, is closed rather
+        // than left open with nothing to show it.
+        let live_sockets = live_sockets.clone();
+        use_effect_with(tab_state.clone(), move |tab_state| {
+            let sessions: Vec<_> = tab_state.tabs.iter().filter_map(Tab::socket).collect();
+            let owned = |id: &String| {
+                sessions
+                    .iter()
+                    .any(|session| session.connection_id.as_ref() == Some(id))
+            };
+            for connection_id in live_sockets.borrow().iter().filter(|id| !owned(id)) {
+                let connection_id = connection_id.clone();
+                spawn_local(async move {
+                    let _ = tauri_api::ws_close(&connection_id, Some(1001), "").await;
+                });
+            }
+            *live_sockets.borrow_mut() = sessions
+                .iter()
+                .filter(|session| {
+                    matches!(session.status, WsStatus::Connecting | WsStatus::Open)
+                })
+                .filter_map(|session| session.connection_id.clone())
+                .collect();
+            || ()
+        });
+    }
+
+    {
+        // A spooled body is deleted once no tab shows it: the tab was closed or its
+        // response replaced.
+        let spooled_bodies = spooled_bodies.clone();
+        use_effect_with(tab_state.clone(), move |tab_state| {
+            let held: Vec<String> = tab_state
+                .tabs
+                .iter()
+                .filter_map(|tab| tab.content.response.body_file.clone())
+                .collect();
+            for path in spooled_bodies.borrow().iter().filter(|path| !held.contains(path)) {
+                let path = path.clone();
+                spawn_local(async move {
+                    let _ = tauri_api::discard_response_file(&path).await;
+                });
+            }
+            *spooled_bodies.borrow_mut() = held;
+            || ()
+        });
+    }
 
     {
         let location_hash = location_hash.clone();
@@ -75,6 +231,10 @@ pub fn app() -> Html {
 
     {
         let tree_state_ref = tree_state_ref.clone();
+        let tab_state_ref = tab_state_ref.clone();
+        let settings_dialog_open = settings_dialog_open.clone();
+        let vault = vault.clone();
+        let vault_prompt = vault_prompt.clone();
         use_effect_with((), move |_| {
             let handler = Closure::wrap(Box::new(move |event: JsValue| {
                 let Some(payload) = event_payload(&event) else {
@@ -83,16 +243,33 @@ pub fn app() -> Html {
                 match payload.as_str() {
                     "open-event" => {
                         let tree_state = tree_state_ref.borrow().clone();
+                        let vault = vault.clone();
+                        let vault_prompt = vault_prompt.clone();
                         spawn_local(async move {
-                            open_openapi(tree_state).await;
+                            open_file(tree_state, vault, vault_prompt).await;
                         });
                     }
                     "save-event" => {
                         let tree_state = tree_state_ref.borrow().clone();
+                        let vault = vault.clone();
+                        let vault_prompt = vault_prompt.clone();
+                        spawn_local(async move {
+                            save_file(tree_state, FileFormat::Collection, vault, vault_prompt).await;
+                        });
+                    }
+                    "export-openapi-event" => {
+                        let tree_state = tree_state_ref.borrow().clone();
+                        let vault = vault.clone();
+                        let vault_prompt = vault_prompt.clone();
                         spawn_local(async move {
-                            export_openapi(tree_state).await;
+                            save_file(tree_state, FileFormat::OpenApi, vault, vault_prompt).await;
                         });
                     }
+                    "settings-event" => settings_dialog_open.set(true),
+                    "undo-event" if focus_takes_native_undo() => native_edit("undo"),
+                    "redo-event" if focus_takes_native_undo() => native_edit("redo"),
+                    "undo-event" => undo(&tree_state_ref.borrow(), &tab_state_ref.borrow()),
+                    "redo-event" => redo(&tree_state_ref.borrow(), &tab_state_ref.borrow()),
                     _ => {}
                 }
             }) as Box<dyn FnMut(JsValue)>);
@@ -103,6 +280,59 @@ pub fn app() -> Html {
         });
     }
 
+    {
+        let tab_state = tab_state.clone();
+        use_effect_with((), move |_| {
+            let on_event = {
+                let tab_state = tab_state.clone();
+                Closure::wrap(Box::new(move |event: JsValue| {
+                    if let Some(event) = event_payload_as::<StreamEvent>(&event) {
+                        tab_state.dispatch(TabAction::PushStreamEvent(event));
+                    }
+                }) as Box<dyn FnMut(JsValue)>)
+            };
+            let on_closed = Closure::wrap(Box::new(move |event: JsValue| {
+                if let Some(closed) = event_payload_as::<StreamClosed>(&event) {
+                    tab_state.dispatch(TabAction::CloseStream {
+                        request_id: closed.request_id,
+                        error: closed.error,
+                    });
+                }
+            }) as Box<dyn FnMut(JsValue)>);
+
+            let _ = tauri_api::event_listen("sse-event", on_event.as_ref());
+            let _ = tauri_api::event_listen("sse-closed", on_closed.as_ref());
+            on_event.forget();
+            on_closed.forget();
+            || ()
+        });
+    }
+
+    {
+        let tab_state = tab_state.clone();
+        use_effect_with((), move |_| {
+            let on_message = {
+                let tab_state = tab_state.clone();
+                Closure::wrap(Box::new(move |event: JsValue| {
+                    if let Some(message) = event_payload_as::<WsMessage>(&event) {
+                        tab_state.dispatch(TabAction::PushWsMessage(message));
+                    }
+                }) as Box<dyn FnMut(JsValue)>)
+            };
+            let on_closed = Closure::wrap(Box::new(move |event: JsValue| {
+                if let Some(closed) = event_payload_as::<WsClosed>(&event) {
+                    tab_state.dispatch(TabAction::WsClosed(closed));
+                }
+            }) as Box<dyn FnMut(JsValue)>);
+
+            let _ = tauri_api::event_listen("ws-message", on_message.as_ref());
+            let _ = tauri_api::event_listen("ws-closed", on_closed.as_ref());
+            on_message.forget();
+            on_closed.forget();
+            || ()
+        });
+    }
+
     let on_resize_start = {
         let dragging = dragging.clone();
         let sidebar_width = sidebar_width.clone();
@@ -193,6 +423,7 @@ pub fn app() -> Html {
     html! {
         <ContextProvider<UseReducerHandle<TreeState>> context={tree_state.clone()}>
             <ContextProvider<UseReducerHandle<TabState>> context={tab_state.clone()}>
+            <ContextProvider<UseReducerHandle<HistoryState>> context={history_state.clone()}>
                 <div class="app" ref={app_ref}>
                     <aside class="sidebar" style={format!("width: {}px;", *sidebar_width)}>
                         <Side
@@ -224,7 +455,7 @@ pub fn app() -> Html {
                                 match mode {
                                     ServerDialogMode::AddServer => {
                                         let Some(url) = normalize_server_url(&value) else {
-                                            show_alert("Invalid server URL. Use http:// or https://.");
+                                            show_alert("Invalid server URL. Use http://, https:// or unix://.");
                                             return;
                                         };
                                         let new_index = tree_state.servers.len();
@@ -240,23 +471,14 @@ pub fn app() -> Html {
                                         } else {
                                             value.clone()
                                         };
-                                        if tree_state
-                                            .root
-                                            .children
-                                            .iter()
-                                            .any(|child| child.content.is_none() && child.label == label)
-                                        {
+                                        let folders = folder_labels(&label);
+                                        if find_folder_path(&tree_state.root, &folders).is_some() {
                                             show_alert("Tag already exists.");
                                             return;
                                         }
-                                        tree_state.dispatch(TreeAction::AddChild {
-                                            path: vec![],
-                                            node: TreeNode {
-                                                label,
-                                                content: None,
-                                                expanded: true,
-                                                children: Vec::new(),
-                                            },
+                                        tree_state.dispatch(TreeAction::AddToFolder {
+                                            folders,
+                                            node: None,
                                         });
                                     }
                                 }
@@ -348,8 +570,7 @@ pub fn app() -> Html {
                                     return;
                                 };
 
-                                let tag_label = (*save_tag).trim().to_string();
-                                let use_tag = !tag_label.is_empty();
+                                let folders = folder_labels(&save_tag);
 
                                 let path_value = normalize_request_path(&tab.content.url);
                                 let path_label = strip_query(&path_value);
@@ -360,24 +581,37 @@ pub fn app() -> Html {
                                 let label = path_label.clone();
 
                                 let root = tree_state.root.clone();
-                                let existing_path = find_request_path_by_label(&root, &label);
+                                let method = tab.content.method;
+                                let existing_path =
+                                    find_request_in_folder(&root, &folders, method, &label);
                                 let selected_path = tree_state.selected_path.clone();
                                 let is_selected_same = selected_path
                                     .as_ref()
                                     .and_then(|path| node_at_path(&root, path))
-                                    .map(|node| node.content.is_some() && node.label == label)
+                                    .map(|node| {
+                                        node.label == label
+                                            && node
+                                                .content
+                                                .as_ref()
+                                                .is_some_and(|content| content.method == method)
+                                    })
                                     .unwrap_or(false);
 
                                 if let Some(path) = existing_path.as_ref() {
                                     if !is_selected_same || selected_path.as_ref() != Some(path) {
-                                        show_alert("Já existe uma request com esse path.");
+                                        show_alert("Já existe uma request com esse método e path nesta pasta.");
                                         return;
                                     }
                                 }
 
                                 let new_node = TreeNode {
                                     label: label.clone(),
-                                    content: Some(tab.content.clone()),
+                                    // The response stays in the tab; it may carry resolved
+                                    // credentials the tree must not keep.
+                                    content: Some(TabContent {
+                                        response: Response::default(),
+                                        ..tab.content.clone()
+                                    }),
                                     expanded: false,
                                     children: Vec::new(),
                                 };
@@ -411,33 +645,10 @@ pub fn app() -> Html {
                                     }
                                 }
 
-                                if use_tag {
-                                    let (tag_index, tag_exists) =
-                                        find_tag_index_by_label(&root, &tag_label);
-
-                                    if !tag_exists {
-                                        tree_state.dispatch(TreeAction::AddChild {
-                                            path: vec![],
-                                            node: TreeNode {
-                                                label: tag_label.clone(),
-                                                content: None,
-                                                expanded: true,
-                                                children: Vec::new(),
-                                            },
-                                        });
-                                    }
-
-                                    let tag_path = vec![tag_index];
-                                    tree_state.dispatch(TreeAction::AddChild {
-                                        path: tag_path,
-                                        node: new_node,
-                                    });
-                                } else {
-                                    tree_state.dispatch(TreeAction::AddChild {
-                                        path: vec![],
-                                        node: new_node,
-                                    });
-                                }
+                                tree_state.dispatch(TreeAction::AddToFolder {
+                                    folders,
+                                    node: Some(new_node),
+                                });
 
                                 if tab.content.url != label {
                                     tab_state.dispatch(TabAction::UpdateUrl {
@@ -565,6 +776,86 @@ pub fn app() -> Html {
                         html! {}
                     }
                 }
+                if *settings_dialog_open {
+                    <ProxySettingsDialog on_close={{
+                        let settings_dialog_open = settings_dialog_open.clone();
+                        Callback::from(move |_| settings_dialog_open.set(false))
+                    }} />
+                }
+                if let Some(action) = (*vault_prompt).clone() {
+                    <VaultDialog
+                        reason={match action {
+                            VaultAction::Save(_) => "Secrets are moved to the encrypted vault before saving.",
+                            VaultAction::Open(_) => "The collection references secrets stored in the vault.",
+                            VaultAction::Restore(_) => "The last session references secrets stored in the vault.",
+                            VaultAction::Keep => "Secrets are kept across restarts only in the encrypted vault.",
+                        }}
+                        on_unlock={{
+                            let tab_state = tab_state.clone();
+                            let tree_state = tree_state.clone();
+                            let autosave = autosave.clone();
+                            let workspace_restored = workspace_restored.clone();
+                            let vault = vault.clone();
+                            let vault_prompt = vault_prompt.clone();
+                            let action = action.clone();
+                            Callback::from(move |unlocked: Vault| {
+                                *vault.borrow_mut() = Some(unlocked);
+                                vault_prompt.set(None);
+                                match action.clone() {
+                                    VaultAction::Save(format) => {
+                                        let tree_state = tree_state.clone();
+                                        let vault = vault.clone();
+                                        let vault_prompt = vault_prompt.clone();
+                                        spawn_local(async move {
+                                            save_file(tree_state, format, vault, vault_prompt).await;
+                                        });
+                                    }
+                                    VaultAction::Open(pending) => {
+                                        finish_open(&tree_state, *pending, vault.borrow().as_ref());
+                                    }
+                                    VaultAction::Restore(mut workspace) => {
+                                        if let Some(vault) = vault.borrow().as_ref() {
+                                            workspace.resolve_references(vault);
+                                        }
+                                        restore_workspace(&workspace, &tab_state, &tree_state);
+                                        workspace_restored.set(true);
+                                    }
+                                    VaultAction::Keep => {
+                                        spawn_local(save_workspace(
+                                            tab_state.clone(),
+                                            tree_state.clone(),
+                                            autosave.clone(),
+                                            vault.clone(),
+                                            vault_prompt.clone(),
+                                        ));
+                                    }
+                                }
+                            })
+                        }}
+                        on_cancel={{
+                            let tab_state = tab_state.clone();
+                            let tree_state = tree_state.clone();
+                            let vault_prompt = vault_prompt.clone();
+                            let workspace_restored = workspace_restored.clone();
+                            Callback::from(move |_| {
+                                vault_prompt.set(None);
+                                // Opening and restoring still go ahead; the references stay
+                                // visible in place of the secrets.
+                                match action.clone() {
+                                    VaultAction::Open(pending) => {
+                                        finish_open(&tree_state, *pending, None);
+                                    }
+                                    VaultAction::Restore(workspace) => {
+                                        restore_workspace(&workspace, &tab_state, &tree_state);
+                                        workspace_restored.set(true);
+                                    }
+                                    VaultAction::Save(_) | VaultAction::Keep => {}
+                                }
+                            })
+                        }}
+                    />
+                }
+            </ContextProvider<UseReducerHandle<HistoryState>>>
             </ContextProvider<UseReducerHandle<TabState>>>
         </ContextProvider<UseReducerHandle<TreeState>>>
     }
@@ -576,6 +867,20 @@ enum ServerDialogMode {
     AddTag,
 }
 
+#[derive(Default)]
+struct Autosave {
+    timer: Option<Timeout>,
+    /// When the oldest unsaved change was made.
+    pending_since: Option<f64>,
+    /// What was last written, so changes to state the workspace does not keep, such as
+    /// stream events, cause no write.
+    last_saved: Option<Workspace>,
+    /// Secrets went into the vault but the sealed vault is not written yet.
+    vault_dirty: bool,
+    /// Whether the vault was asked for to keep the workspace secrets; once a session.
+    vault_asked: bool,
+}
+
 #[derive(Default)]
 struct DragState {
     start_x: f64,
@@ -588,6 +893,20 @@ fn event_payload(event: &JsValue) -> Option<String> {
         .and_then(|value| value.as_string())
 }
 
+fn event_payload_as<T: serde::de::DeserializeOwned>(event: &JsValue) -> Option<T> {
+    js_sys::Reflect::get(event, &JsValue::from_str("payload"))
+        .ok()
+        .and_then(|value| serde_wasm_bindgen::from_value(value).ok())
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamClosed {
+    request_id: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
 fn normalize_server_url(value: &str) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -597,6 +916,8 @@ fn normalize_server_url(value: &str) -> Option<String> {
     let url = Url::parse(trimmed).ok()?;
     match url.scheme() {
         "http" | "https" => {}
+        // The socket path: unix:///var/run/docker.sock
+        "unix" if !url.has_host() && url.path().len() > 1 => {}
         _ => return None,
     }
 
@@ -611,7 +932,89 @@ fn normalize_server_url(value: &str) -> Option<String> {
     }
 }
 
-async fn open_openapi(tree_state: UseReducerHandle<TreeState>) {
+/// Whether the focused element is a text field whose edits the reducers do not
+/// record: anything outside the request editor (marked `data-undo="app"`), or inside
+/// a dialog. Those keep the browser's own undo.
+fn focus_takes_native_undo() -> bool {
+    let Some(element) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.active_element())
+    else {
+        return false;
+    };
+    let editable = match element.tag_name().as_str() {
+        "TEXTAREA" => true,
+        "INPUT" => matches!(
+            element.get_attribute("type").unwrap_or_default().as_str(),
+            "" | "text" | "password" | "search" | "url" | "email" | "number" | "tel"
+        ),
+        _ => element
+            .dyn_ref::<web_sys::HtmlElement>()
+            .is_some_and(|element| element.is_content_editable()),
+    };
+    let tracked = element.closest("[data-undo=\"app\"]").ok().flatten().is_some()
+        && element.closest(".modal-backdrop").ok().flatten().is_none();
+    editable && !tracked
+}
+
+/// Runs the browser's undo or redo on the focused field.
+fn native_edit(command: &str) {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.dyn_into::<web_sys::HtmlDocument>().ok());
+    if let Some(document) = document {
+        let _ = document.exec_command(command);
+    }
+}
+
+/// Undoes the newest change, whichever of the tree and the tabs it was made in.
+fn undo(tree_state: &UseReducerHandle<TreeState>, tab_state: &UseReducerHandle<TabState>) {
+    match (tree_state.undo.undo_stamp(), tab_state.undo.undo_stamp()) {
+        (Some(tree), tab) if tab.is_none_or(|tab| tree > tab) => {
+            tree_state.dispatch(TreeAction::Undo)
+        }
+        (_, Some(_)) => tab_state.dispatch(TabAction::Undo),
+        _ => {}
+    }
+}
+
+/// Redoes the newest undo, whichever of the tree and the tabs it was made in.
+fn redo(tree_state: &UseReducerHandle<TreeState>, tab_state: &UseReducerHandle<TabState>) {
+    match (tree_state.undo.redo_stamp(), tab_state.undo.redo_stamp()) {
+        (Some(tree), tab) if tab.is_none_or(|tab| tree > tab) => {
+            tree_state.dispatch(TreeAction::Redo)
+        }
+        (_, Some(_)) => tab_state.dispatch(TabAction::Redo),
+        _ => {}
+    }
+}
+
+/// Work waiting on the vault before it is applied.
+#[derive(Clone)]
+enum VaultAction {
+    Save(FileFormat),
+    Open(Box<PendingOpen>),
+    /// The saved workspace, restored once its references can be resolved.
+    Restore(Box<Workspace>),
+    /// Unlocking lets the next workspace save keep its secrets.
+    Keep,
+}
+
+/// A parsed collection, with vault references still in place.
+#[derive(Clone)]
+struct PendingOpen {
+    path: String,
+    root: TreeNode,
+    servers: Vec<ServerEntry>,
+    /// `None` for OpenAPI documents, which carry no environments.
+    environments: Option<Vec<Environment>>,
+}
+
+async fn open_file(
+    tree_state: UseReducerHandle<TreeState>,
+    vault: Rc<RefCell<Option<Vault>>>,
+    vault_prompt: UseStateHandle<Option<VaultAction>>,
+) {
     let path = match tauri_api::dialog_open().await {
         Ok(Some(path)) => path,
         Ok(None) => return,
@@ -630,19 +1033,105 @@ async fn open_openapi(tree_state: UseReducerHandle<TreeState>) {
             return;
         }
     };
-    let (root, servers) = match build_tree_from_openapi(&text) {
+    // OpenAPI documents carry no environments, so the current ones stay.
+    let parsed = match parse_collection(&text) {
+        Some(collection) => collection
+            .map(|(root, servers, environments)| (root, servers, Some(environments)))
+            .map_err(|err| format!("Falha ao abrir coleção: {err}")),
+        None => build_tree_from_openapi(&text)
+            .map(|(root, servers)| (root, servers, None))
+            .map_err(|err| format!("Falha ao importar OpenAPI: {err}")),
+    };
+    let (root, servers, environments) = match parsed {
         Ok(result) => result,
         Err(err) => {
-            show_alert(&format!("Falha ao importar OpenAPI: {err}"));
+            show_alert(&err);
             return;
         }
     };
+    let pending = PendingOpen {
+        path,
+        root,
+        servers,
+        environments,
+    };
+    let references = has_references(
+        &pending.servers,
+        pending.environments.as_deref().unwrap_or_default(),
+    );
+    if references && vault.borrow().is_none() {
+        vault_prompt.set(Some(VaultAction::Open(Box::new(pending))));
+        return;
+    }
+    finish_open(&tree_state, pending, vault.borrow().as_ref());
+}
+
+/// Applies an opened file, filling in vault references when `vault` is unlocked.
+fn finish_open(tree_state: &UseReducerHandle<TreeState>, pending: PendingOpen, vault: Option<&Vault>) {
+    let PendingOpen {
+        path,
+        root,
+        mut servers,
+        mut environments,
+    } = pending;
+    if let Some(vault) = vault {
+        resolve_references(
+            &mut servers,
+            environments.as_deref_mut().unwrap_or_default(),
+            vault,
+        );
+    }
     tree_state.dispatch(TreeAction::SetTree { root, servers });
-    let title = filename_from_path(&path);
-    let _ = tauri_api::set_window_title(&title).await;
+    if let Some(mut environments) = environments {
+        keep_secrets(&mut environments, &tree_state.environments);
+        tree_state.dispatch(TreeAction::SetEnvironments { environments });
+    }
+    spawn_local(async move {
+        let title = filename_from_path(&path);
+        let _ = tauri_api::set_window_title(&title).await;
+    });
 }
 
-async fn export_openapi(tree_state: UseReducerHandle<TreeState>) {
+/// What File > Save and File > Export OpenAPI write.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    /// Lossless native format; JSON when the file name ends in `.json`, YAML otherwise.
+    Collection,
+    OpenApi,
+}
+
+async fn save_file(
+    tree_state: UseReducerHandle<TreeState>,
+    format: FileFormat,
+    vault: Rc<RefCell<Option<Vault>>>,
+    vault_prompt: UseStateHandle<Option<VaultAction>>,
+) {
+    let (servers, environments, secrets) =
+        extract_secrets(&tree_state.servers, &tree_state.environments);
+    if !secrets.is_empty() {
+        // The borrow ends here, before anything is awaited.
+        let sealed = vault.borrow_mut().as_mut().map(|vault| {
+            for (id, value) in secrets {
+                vault.insert(id, value);
+            }
+            vault.seal()
+        });
+        let Some(sealed) = sealed else {
+            vault_prompt.set(Some(VaultAction::Save(format)));
+            return;
+        };
+        let saved = match sealed {
+            Ok(sealed) => tauri_api::save_vault(&sealed)
+                .await
+                .map_err(|err| tauri_api::js_error_to_string(&err)),
+            Err(message) => Err(message),
+        };
+        if let Err(message) = saved {
+            show_alert(&format!("Falha ao salvar o cofre: {message}"));
+            return;
+        }
+    }
+
     let path = match tauri_api::dialog_save().await {
         Ok(Some(path)) => path,
         Ok(None) => return,
@@ -652,7 +1141,17 @@ async fn export_openapi(tree_state: UseReducerHandle<TreeState>) {
         }
     };
 
-    let text = match build_openapi_from_tree(&tree_state.root, &tree_state.servers) {
+    let target = ensure_file_extension(&path, format);
+    let built = match format {
+        FileFormat::Collection => build_collection(
+            &tree_state.root,
+            &servers,
+            &environments,
+            target.to_lowercase().ends_with(".json"),
+        ),
+        FileFormat::OpenApi => build_openapi_from_tree(&tree_state.root, &servers),
+    };
+    let text = match built {
         Ok(text) => text,
         Err(err) => {
             show_alert(&err);
@@ -660,7 +1159,6 @@ async fn export_openapi(tree_state: UseReducerHandle<TreeState>) {
         }
     };
 
-    let target = ensure_openapi_extension(&path);
     let create_new_options = r#"{"createNew":true,"create":true}"#;
     match tauri_api::fs_write_text_with_options(&target, &text, Some(create_new_options)).await {
         Ok(()) => {
@@ -690,12 +1188,15 @@ async fn export_openapi(tree_state: UseReducerHandle<TreeState>) {
     }
 }
 
-fn ensure_openapi_extension(path: &str) -> String {
+fn ensure_file_extension(path: &str, format: FileFormat) -> String {
     let lower = path.to_lowercase();
     if lower.ends_with(".yaml") || lower.ends_with(".yml") || lower.ends_with(".json") {
         return path.to_string();
     }
-    format!("{path}.yaml")
+    match format {
+        FileFormat::Collection => format!("{path}.rustman.yaml"),
+        FileFormat::OpenApi => format!("{path}.yaml"),
+    }
 }
 
 fn filename_from_path(path: &str) -> String {
@@ -708,31 +1209,48 @@ fn filename_from_path(path: &str) -> String {
         .to_string()
 }
 
+/// The selected folder, or the folder of the selected request, as `/`-separated labels.
 fn infer_tag_from_selection(root: &TreeNode, selected: Option<&Vec<usize>>) -> Option<String> {
     let path = selected?;
     let node = node_at_path(root, path)?;
-    if node.content.is_none() {
-        return Some(node.label.clone());
+    let folder = if node.content.is_none() {
+        &path[..]
+    } else {
+        path.split_last().map(|(_, parent)| parent)?
+    };
+    let mut labels = Vec::new();
+    let mut current = root;
+    for index in folder {
+        current = current.children.get(*index)?;
+        labels.push(current.label.clone());
     }
-    if let Some(parent_path) = path.split_last().map(|(_, parent)| parent) {
-        if parent_path.is_empty() {
-            return None;
-        }
-        let parent = node_at_path(root, parent_path)?;
-        if parent.content.is_none() {
-            return Some(parent.label.clone());
-        }
+    if labels.is_empty() {
+        return None;
     }
-    None
+    Some(labels.join("/"))
 }
 
-fn find_tag_index_by_label(root: &TreeNode, label: &str) -> (usize, bool) {
-    for (index, child) in root.children.iter().enumerate() {
-        if child.content.is_none() && child.label == label {
-            return (index, true);
-        }
+/// Folder labels in a tag such as `Users/Admin`, outermost first.
+fn folder_labels(tag: &str) -> Vec<String> {
+    tag.split('/')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn find_folder_path(root: &TreeNode, folders: &[String]) -> Option<Vec<usize>> {
+    let mut path = Vec::new();
+    let mut current = root;
+    for label in folders {
+        let index = current
+            .children
+            .iter()
+            .position(|child| child.content.is_none() && &child.label == label)?;
+        current = &current.children[index];
+        path.push(index);
     }
-    (root.children.len(), false)
+    Some(path)
 }
 
 fn normalize_request_path(value: &str) -> String {
@@ -741,7 +1259,7 @@ fn normalize_request_path(value: &str) -> String {
         return String::new();
     }
 
-    if trimmed.starts_with('/') || trimmed.starts_with('?') {
+    if trimmed.starts_with('/') || trimmed.starts_with('?') || trimmed.starts_with("{{") {
         return trimmed.to_string();
     }
 
@@ -752,21 +1270,25 @@ fn strip_query(value: &str) -> String {
     value.split('?').next().unwrap_or("").to_string()
 }
 
-fn find_request_path_by_label(root: &TreeNode, label: &str) -> Option<Vec<usize>> {
-    for (index, child) in root.children.iter().enumerate() {
-        if child.content.is_some() {
-            if child.label == label {
-                return Some(vec![index]);
-            }
-            continue;
-        }
-        for (child_index, grand) in child.children.iter().enumerate() {
-            if grand.content.is_some() && grand.label == label {
-                return Some(vec![index, child_index]);
-            }
-        }
-    }
-    None
+/// The request saved as `method` and `label` directly inside the folder `folders`
+/// names. Variants of one path may live in other folders.
+fn find_request_in_folder(
+    root: &TreeNode,
+    folders: &[String],
+    method: MethodEnum,
+    label: &str,
+) -> Option<Vec<usize>> {
+    let mut path = find_folder_path(root, folders)?;
+    let folder = node_at_path(root, &path)?;
+    let index = folder.children.iter().position(|child| {
+        child.label == label
+            && child
+                .content
+                .as_ref()
+                .is_some_and(|content| content.method == method)
+    })?;
+    path.push(index);
+    Some(path)
 }
 
 fn event_target_value(event: &InputEvent) -> String {
@@ -777,6 +1299,80 @@ fn event_target_value(event: &InputEvent) -> String {
         .unwrap_or_default()
 }
 
+fn restore_workspace(
+    workspace: &Workspace,
+    tab_state: &UseReducerHandle<TabState>,
+    tree_state: &UseReducerHandle<TreeState>,
+) {
+    let (root, servers) = workspace.tree();
+    tree_state.dispatch(TreeAction::SetTree { root, servers });
+    tree_state.dispatch(TreeAction::SetEnvironments {
+        environments: workspace.environments(),
+    });
+    tree_state.dispatch(TreeAction::SetActiveEnvironment {
+        index: workspace.active_environment(),
+    });
+    if let Some(index) = workspace.selected_server() {
+        tree_state.dispatch(TreeAction::SetSelectedServer { index });
+    }
+    if let Some(path) = workspace.selected_path() {
+        tree_state.dispatch(TreeAction::SetSelected { path });
+    }
+    tree_state.dispatch(TreeAction::ClearUndo);
+    tab_state.dispatch(TabAction::Restore {
+        tabs: workspace.tabs(),
+        active_tab_id: workspace.active_tab(),
+    });
+}
+
+async fn save_workspace(
+    tab_state: UseReducerHandle<TabState>,
+    tree_state: UseReducerHandle<TreeState>,
+    autosave: Rc<RefCell<Autosave>>,
+    vault: Rc<RefCell<Option<Vault>>>,
+    vault_prompt: UseStateHandle<Option<VaultAction>>,
+) {
+    let restore_responses = tauri_api::get_app_settings()
+        .await
+        .map(|settings| settings.restore_responses)
+        .unwrap_or(false);
+    let mut workspace = Workspace::capture(&tab_state, &tree_state, restore_responses);
+    // The borrow ends here, before anything is awaited.
+    let sealed = workspace.seal_secrets(vault.borrow_mut().as_mut());
+    match sealed {
+        SealedSecrets::None => {}
+        SealedSecrets::Stored { changed } => {
+            autosave.borrow_mut().vault_dirty |= changed;
+        }
+        SealedSecrets::Blanked => {
+            let asked = std::mem::replace(&mut autosave.borrow_mut().vault_asked, true);
+            if !asked {
+                vault_prompt.set(Some(VaultAction::Keep));
+            }
+        }
+    }
+    if autosave.borrow().vault_dirty {
+        let sealed = vault.borrow().as_ref().map(Vault::seal);
+        let saved = match sealed {
+            Some(Ok(sealed)) => tauri_api::save_vault(&sealed).await.is_ok(),
+            _ => false,
+        };
+        // The workspace waits for the vault, so its references never point at secrets
+        // that were not written; both are retried with the next change.
+        if !saved {
+            return;
+        }
+        autosave.borrow_mut().vault_dirty = false;
+    }
+    if autosave.borrow().last_saved.as_ref() == Some(&workspace) {
+        return;
+    }
+    // A failed write is retried with the next change.
+    if tauri_api::save_workspace(&workspace).await.is_ok() {
+        autosave.borrow_mut().last_saved = Some(workspace);
+    }
+}
+
 fn show_alert(message: &str) {
     if let Some(window) = web_sys::window() {
         let _ = window.alert_with_message(message);
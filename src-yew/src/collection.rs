@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::state::{Environment, Response, ServerEntry, TreeNode};
+
+/// Value of the `rustman` key that marks a native collection file.
+const COLLECTION_MARKER: &str = "collection";
+
+/// Version written by this build. Older files are upgraded through `MIGRATIONS` on
+/// open; newer ones are refused rather than read partially.
+const COLLECTION_VERSION: u64 = 1;
+
+/// `MIGRATIONS[n]` upgrades a version `n + 1` document to version `n + 2` in place.
+const MIGRATIONS: [fn(&mut Value); 0] = [];
+
+/// Native collection file: the tree and servers exactly as held in `TreeState`, so
+/// every saved request, including duplicates of the same method and path, disabled
+/// rows and editor state, comes back as it was. Responses are left out: they hold
+/// resolved credentials and cookies.
+#[derive(Serialize, Deserialize)]
+struct Collection {
+    rustman: String,
+    version: u64,
+    root: TreeNode,
+    servers: Vec<ServerEntry>,
+    /// Secret values are vault references, so the file can be shared.
+    #[serde(default)]
+    environments: Vec<Environment>,
+}
+
+/// Tree, servers and environments read from a collection file.
+pub type Opened = (TreeNode, Vec<ServerEntry>, Vec<Environment>);
+
+/// Serializes the tree as YAML, or as JSON when `json` is set.
+pub fn build_collection(
+    root: &TreeNode,
+    servers: &[ServerEntry],
+    environments: &[Environment],
+    json: bool,
+) -> Result<String, String> {
+    let mut root = root.clone();
+    clear_responses(&mut root);
+    let collection = Collection {
+        rustman: COLLECTION_MARKER.to_string(),
+        version: COLLECTION_VERSION,
+        root,
+        servers: servers.to_vec(),
+        environments: environments.to_vec(),
+    };
+    // Going through `Value` keeps enums as plain maps in YAML instead of YAML tags, so
+    // both encodings read back through the same path.
+    let value = serde_json::to_value(&collection).map_err(|err| err.to_string())?;
+    if json {
+        serde_json::to_string_pretty(&value).map_err(|err| err.to_string())
+    } else {
+        serde_yaml::to_string(&value).map_err(|err| err.to_string())
+    }
+}
+
+/// Parses a native collection, or returns `None` when `text` is not one (e.g. an
+/// OpenAPI document).
+pub fn parse_collection(text: &str) -> Option<Result<Opened, String>> {
+    let mut value: Value = serde_yaml::from_str(text).ok()?;
+    if value.get("rustman").and_then(Value::as_str) != Some(COLLECTION_MARKER) {
+        return None;
+    }
+    Some(migrate(&mut value).and_then(|()| {
+        let collection: Collection =
+            serde_json::from_value(value).map_err(|err| format!("Coleção inválida: {err}"))?;
+        Ok((collection.root, collection.servers, collection.environments))
+    }))
+}
+
+fn clear_responses(node: &mut TreeNode) {
+    if let Some(content) = node.content.as_mut() {
+        content.response = Response::default();
+    }
+    node.children.iter_mut().for_each(clear_responses);
+}
+
+fn migrate(value: &mut Value) -> Result<(), String> {
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "Coleção sem versão.".to_string())?;
+    if version == 0 || version > COLLECTION_VERSION {
+        return Err(format!(
+            "Coleção na versão {version}; esta versão do app lê até a {COLLECTION_VERSION}."
+        ));
+    }
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        migration(value);
+    }
+    value["version"] = Value::from(COLLECTION_VERSION);
+    Ok(())
+}
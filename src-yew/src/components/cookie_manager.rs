@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::state::StoredCookie;
+use crate::tauri_api;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CookieManagerProps {
+    pub server_url: String,
+    pub on_close: Callback<()>,
+}
+
+#[function_component(CookieManager)]
+pub fn cookie_manager(props: &CookieManagerProps) -> Html {
+    let cookies = use_state(Vec::<StoredCookie>::new);
+    let reload = use_state(|| 0u32);
+    let error = use_state(|| None::<String>);
+    // `(original, form)`: original is `None` when adding a new cookie.
+    let editing = use_state(|| None::<(Option<StoredCookie>, CookieForm)>);
+    let server_url = props.server_url.clone();
+
+    {
+        let cookies = cookies.clone();
+        let error = error.clone();
+        let server_url = server_url.clone();
+        use_effect_with((server_url, *reload), move |(server_url, _)| {
+            let server_url = server_url.clone();
+            spawn_local(async move {
+                match tauri_api::list_cookies(&server_url).await {
+                    Ok(list) => cookies.set(list),
+                    Err(err) => error.set(Some(tauri_api::js_error_to_string(&err))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let refresh = {
+        let reload = reload.clone();
+        let error = error.clone();
+        Callback::from(move |result: Result<(), wasm_bindgen::JsValue>| match result {
+            Ok(()) => {
+                error.set(None);
+                reload.set(*reload + 1);
+            }
+            Err(err) => error.set(Some(tauri_api::js_error_to_string(&err))),
+        })
+    };
+
+    let on_add = {
+        let editing = editing.clone();
+        let server_url = server_url.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let domain = url::Url::parse(&server_url)
+                .ok()
+                .and_then(|url| url.host_str().map(|host| host.to_string()))
+                .unwrap_or_default();
+            editing.set(Some((
+                None,
+                CookieForm {
+                    domain,
+                    path: "/".to_string(),
+                    ..CookieForm::default()
+                },
+            )));
+        })
+    };
+
+    let on_clear = {
+        let server_url = server_url.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let confirmed = web_sys::window()
+                .and_then(|window| window.confirm_with_message("Remove all cookies for this server?").ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let server_url = server_url.clone();
+            let refresh = refresh.clone();
+            spawn_local(async move {
+                refresh.emit(tauri_api::clear_cookies(&server_url).await);
+            });
+        })
+    };
+
+    let on_export = {
+        let cookies = cookies.clone();
+        let error = error.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let contents = export_netscape(&cookies);
+            let error = error.clone();
+            spawn_local(async move {
+                let path = match tauri_api::dialog_save().await {
+                    Ok(Some(path)) => path,
+                    Ok(None) => return,
+                    Err(err) => {
+                        error.set(Some(tauri_api::js_error_to_string(&err)));
+                        return;
+                    }
+                };
+                if let Err(err) = tauri_api::fs_write_text(&path, &contents).await {
+                    error.set(Some(tauri_api::js_error_to_string(&err)));
+                }
+            });
+        })
+    };
+
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| on_close.emit(()))
+    };
+
+    let mut by_domain: BTreeMap<String, Vec<StoredCookie>> = BTreeMap::new();
+    for cookie in cookies.iter() {
+        by_domain
+            .entry(cookie.domain.clone())
+            .or_default()
+            .push(cookie.clone());
+    }
+
+    let editor = if let Some((original, form)) = (*editing).clone() {
+        let update = {
+            let editing = editing.clone();
+            let original = original.clone();
+            move |apply: fn(&mut CookieForm, String), value: String| {
+                let mut next = form_of(&editing);
+                apply(&mut next, value);
+                editing.set(Some((original.clone(), next)));
+            }
+        };
+        let on_field = |apply: fn(&mut CookieForm, String)| {
+            let update = update.clone();
+            Callback::from(move |event: InputEvent| update(apply, input_value(&event)))
+        };
+        let on_flag = |apply: fn(&mut CookieForm, String)| {
+            let update = update.clone();
+            Callback::from(move |event: Event| {
+                update(apply, checkbox_value(&event).to_string());
+            })
+        };
+        let on_save = {
+            let editing = editing.clone();
+            let server_url = server_url.clone();
+            let refresh = refresh.clone();
+            let error = error.clone();
+            let form = form.clone();
+            let original = original.clone();
+            Callback::from(move |_event: MouseEvent| {
+                let cookie = match form.to_cookie() {
+                    Ok(cookie) => cookie,
+                    Err(message) => {
+                        error.set(Some(message));
+                        return;
+                    }
+                };
+                editing.set(None);
+                let server_url = server_url.clone();
+                let refresh = refresh.clone();
+                let original = original.clone();
+                spawn_local(async move {
+                    let result =
+                        tauri_api::save_cookie(&server_url, &cookie, original.as_ref()).await;
+                    refresh.emit(result);
+                });
+            })
+        };
+        let on_cancel = {
+            let editing = editing.clone();
+            Callback::from(move |_event: MouseEvent| editing.set(None))
+        };
+        html! {
+            <div class="cookie-editor">
+                <div class="settings-grid">
+                    <label class="settings-label">{ "Name" }</label>
+                    <input type="text" value={form.name.clone()} oninput={on_field(|form, value| form.name = value)} />
+                    <label class="settings-label">{ "Value" }</label>
+                    <input type="text" value={form.value.clone()} oninput={on_field(|form, value| form.value = value)} />
+                    <label class="settings-label">{ "Domain" }</label>
+                    <input type="text" value={form.domain.clone()} oninput={on_field(|form, value| form.domain = value)} />
+                    <label class="settings-label">{ "Path" }</label>
+                    <input type="text" value={form.path.clone()} oninput={on_field(|form, value| form.path = value)} />
+                    <label class="settings-label">{ "Expires" }</label>
+                    <input
+                        type="text"
+                        placeholder="Session (or 2030-01-01T00:00:00Z)"
+                        value={form.expires.clone()}
+                        oninput={on_field(|form, value| form.expires = value)}
+                    />
+                    <label class="settings-label">{ "Flags" }</label>
+                    <div class="cookie-flags">
+                        <label>
+                            <input type="checkbox" checked={form.include_subdomains} onchange={on_flag(|form, value| form.include_subdomains = value == "true")} />
+                            { " Subdomains" }
+                        </label>
+                        <label>
+                            <input type="checkbox" checked={form.secure} onchange={on_flag(|form, value| form.secure = value == "true")} />
+                            { " Secure" }
+                        </label>
+                        <label>
+                            <input type="checkbox" checked={form.http_only} onchange={on_flag(|form, value| form.http_only = value == "true")} />
+                            { " HttpOnly" }
+                        </label>
+                    </div>
+                </div>
+                <div class="modal-actions">
+                    <button class="button secondary" onclick={on_cancel}>{ "Cancel" }</button>
+                    <button class="button" onclick={on_save}>{ "Save cookie" }</button>
+                </div>
+            </div>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class="modal-backdrop">
+            <div class="modal cookie-modal">
+                <h2 class="modal-title">{ "Cookies" }</h2>
+                <p class="modal-text muted">{ server_url.clone() }</p>
+                {
+                    if let Some(message) = (*error).clone() {
+                        html! { <p class="modal-text error">{ message }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if by_domain.is_empty() {
+                        html! { <p class="modal-text muted">{ "No cookies stored for this server." }</p> }
+                    } else {
+                        html! {
+                            <div class="cookie-list">
+                                { for by_domain.into_iter().map(|(domain, cookies)| html! {
+                                    <div class="cookie-domain">
+                                        <h3 class="table-title">{ domain }</h3>
+                                        <table>
+                                            <thead>
+                                                <tr>
+                                                    <th>{ "" }</th>
+                                                    <th>{ "NAME" }</th>
+                                                    <th>{ "VALUE" }</th>
+                                                    <th>{ "PATH" }</th>
+                                                    <th>{ "EXPIRES" }</th>
+                                                    <th>{ "" }</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                { for cookies.into_iter().map(|cookie| {
+                                                    let on_edit = {
+                                                        let editing = editing.clone();
+                                                        let cookie = cookie.clone();
+                                                        Callback::from(move |_event: MouseEvent| {
+                                                            editing.set(Some((
+                                                                Some(cookie.clone()),
+                                                                CookieForm::from_cookie(&cookie),
+                                                            )));
+                                                        })
+                                                    };
+                                                    let on_delete = {
+                                                        let server_url = server_url.clone();
+                                                        let refresh = refresh.clone();
+                                                        let cookie = cookie.clone();
+                                                        Callback::from(move |_event: MouseEvent| {
+                                                            let server_url = server_url.clone();
+                                                            let refresh = refresh.clone();
+                                                            let cookie = cookie.clone();
+                                                            spawn_local(async move {
+                                                                refresh.emit(tauri_api::delete_cookie(&server_url, &cookie).await);
+                                                            });
+                                                        })
+                                                    };
+                                                    html! {
+                                                        <tr>
+                                                            <td>{ if cookie.secure { "🔒" } else { "" } }</td>
+                                                            <td>{ cookie.name.clone() }</td>
+                                                            <td class="cookie-value" title={cookie.value.clone()}>{ cookie.value.clone() }</td>
+                                                            <td>{ cookie.path.clone() }</td>
+                                                            <td>{ format_expires(cookie.expires) }</td>
+                                                            <td class="cookie-actions">
+                                                                <button class="button ghost" onclick={on_edit}>{ "Edit" }</button>
+                                                                <button class="button ghost" onclick={on_delete}>{ "X" }</button>
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                }) }
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }) }
+                            </div>
+                        }
+                    }
+                }
+                { editor }
+                <div class="modal-actions">
+                    <button class="button secondary" onclick={on_add}>{ "Add cookie" }</button>
+                    <button class="button secondary" onclick={on_export}>{ "Export" }</button>
+                    <button class="button danger" onclick={on_clear}>{ "Clear all" }</button>
+                    <button class="button" onclick={on_close}>{ "Close" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Default)]
+struct CookieForm {
+    name: String,
+    value: String,
+    domain: String,
+    path: String,
+    expires: String,
+    include_subdomains: bool,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<String>,
+}
+
+impl CookieForm {
+    fn from_cookie(cookie: &StoredCookie) -> Self {
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            domain: cookie.domain.clone(),
+            path: cookie.path.clone(),
+            expires: cookie.expires.map(iso_from_seconds).unwrap_or_default(),
+            include_subdomains: !cookie.host_only,
+            secure: cookie.secure,
+            http_only: cookie.http_only,
+            same_site: cookie.same_site.clone(),
+        }
+    }
+
+    fn to_cookie(&self) -> Result<StoredCookie, String> {
+        let expires = match self.expires.trim() {
+            "" => None,
+            value => {
+                let millis = js_sys::Date::parse(value);
+                if millis.is_nan() {
+                    return Err(format!("Invalid expiry date: {value}"));
+                }
+                Some((millis / 1000.0) as i64)
+            }
+        };
+        Ok(StoredCookie {
+            name: self.name.trim().to_string(),
+            value: self.value.clone(),
+            domain: self.domain.trim().to_string(),
+            host_only: !self.include_subdomains,
+            path: if self.path.trim().is_empty() {
+                "/".to_string()
+            } else {
+                self.path.trim().to_string()
+            },
+            expires,
+            secure: self.secure,
+            http_only: self.http_only,
+            same_site: self.same_site.clone(),
+        })
+    }
+}
+
+fn form_of(editing: &UseStateHandle<Option<(Option<StoredCookie>, CookieForm)>>) -> CookieForm {
+    editing
+        .as_ref()
+        .map(|(_, form)| form.clone())
+        .unwrap_or_default()
+}
+
+/// Serializes cookies in the Netscape `cookies.txt` format understood by curl and wget.
+fn export_netscape(cookies: &[StoredCookie]) -> String {
+    let mut text = String::from("# Netscape HTTP Cookie File\n");
+    for cookie in cookies {
+        let domain = if cookie.host_only {
+            cookie.domain.clone()
+        } else {
+            format!(".{}", cookie.domain)
+        };
+        let prefix = if cookie.http_only { "#HttpOnly_" } else { "" };
+        text.push_str(&format!(
+            "{prefix}{domain}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            if cookie.host_only { "FALSE" } else { "TRUE" },
+            cookie.path,
+            if cookie.secure { "TRUE" } else { "FALSE" },
+            cookie.expires.unwrap_or(0),
+            cookie.name,
+            cookie.value,
+        ));
+    }
+    text
+}
+
+fn format_expires(expires: Option<i64>) -> String {
+    expires
+        .map(iso_from_seconds)
+        .unwrap_or_else(|| "Session".to_string())
+}
+
+fn iso_from_seconds(seconds: i64) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(seconds as f64 * 1000.0));
+    date.to_iso_string().as_string().unwrap_or_default()
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn checkbox_value(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.checked())
+        .unwrap_or(false)
+}
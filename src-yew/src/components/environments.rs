@@ -0,0 +1,273 @@
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+use crate::state::{EnvVariable, Environment, TreeAction, TreeState};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct EnvironmentsDialogProps {
+    pub on_close: Callback<()>,
+}
+
+#[function_component(EnvironmentsDialog)]
+pub fn environments_dialog(props: &EnvironmentsDialogProps) -> Html {
+    let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let initial = tree_state
+        .as_ref()
+        .map(|state| state.environments.clone())
+        .unwrap_or_default();
+    let initial_selected = tree_state
+        .as_ref()
+        .and_then(|state| state.active_environment)
+        .or(if initial.is_empty() { None } else { Some(0) });
+    let drafts = use_state(|| initial);
+    let selected = use_state(|| initial_selected);
+    // Index of the active environment among the drafts, kept in step with removals.
+    let active = use_state(|| tree_state.as_ref().and_then(|state| state.active_environment));
+
+    let Some(tree_state) = tree_state else {
+        return html! {};
+    };
+
+    let on_add = {
+        let drafts = drafts.clone();
+        let selected = selected.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let mut next = (*drafts).clone();
+            next.push(Environment {
+                name: format!("Environment {}", next.len() + 1),
+                variables: vec![EnvVariable::default()],
+            });
+            selected.set(Some(next.len() - 1));
+            drafts.set(next);
+        })
+    };
+
+    let on_select = |index: usize| {
+        let selected = selected.clone();
+        Callback::from(move |_event: MouseEvent| selected.set(Some(index)))
+    };
+
+    let current = (*selected).and_then(|index| drafts.get(index).map(|draft| (index, draft)));
+
+    let editor = if let Some((index, environment)) = current {
+        let update = |apply: fn(&mut Environment, String)| {
+            let drafts = drafts.clone();
+            Callback::from(move |event: InputEvent| {
+                let mut next = (*drafts).clone();
+                if let Some(environment) = next.get_mut(index) {
+                    apply(environment, input_value(&event));
+                }
+                drafts.set(next);
+            })
+        };
+        let update_variable = |row: usize, apply: fn(&mut EnvVariable, String)| {
+            let drafts = drafts.clone();
+            Callback::from(move |event: InputEvent| {
+                let mut next = (*drafts).clone();
+                if let Some(variable) = next
+                    .get_mut(index)
+                    .and_then(|environment| environment.variables.get_mut(row))
+                {
+                    apply(variable, input_value(&event));
+                }
+                drafts.set(next);
+            })
+        };
+        let toggle_variable = |row: usize, apply: fn(&mut EnvVariable, bool)| {
+            let drafts = drafts.clone();
+            Callback::from(move |event: Event| {
+                let mut next = (*drafts).clone();
+                if let Some(variable) = next
+                    .get_mut(index)
+                    .and_then(|environment| environment.variables.get_mut(row))
+                {
+                    apply(variable, checkbox_value(&event));
+                }
+                drafts.set(next);
+            })
+        };
+        let on_add_variable = {
+            let drafts = drafts.clone();
+            Callback::from(move |_event: MouseEvent| {
+                let mut next = (*drafts).clone();
+                if let Some(environment) = next.get_mut(index) {
+                    environment.variables.push(EnvVariable::default());
+                }
+                drafts.set(next);
+            })
+        };
+        let on_remove_environment = {
+            let drafts = drafts.clone();
+            let selected = selected.clone();
+            let active = active.clone();
+            Callback::from(move |_event: MouseEvent| {
+                active.set(match *active {
+                    Some(current) if current == index => None,
+                    Some(current) if current > index => Some(current - 1),
+                    other => other,
+                });
+                let mut next = (*drafts).clone();
+                if index < next.len() {
+                    next.remove(index);
+                }
+                selected.set(if next.is_empty() {
+                    None
+                } else {
+                    Some(index.min(next.len() - 1))
+                });
+                drafts.set(next);
+            })
+        };
+
+        html! {
+            <div class="environment-editor">
+                <label class="modal-label">{ "Name" }</label>
+                <div class="modal-file-row">
+                    <input
+                        class="modal-input"
+                        type="text"
+                        value={environment.name.clone()}
+                        oninput={update(|environment, value| environment.name = value)}
+                    />
+                    <button class="button ghost" onclick={on_remove_environment}>{ "Remove" }</button>
+                </div>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "" }</th>
+                            <th>{ "KEY" }</th>
+                            <th>{ "VALUE" }</th>
+                            <th>{ "SECRET" }</th>
+                            <th>{ "REMOVE" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for environment.variables.iter().enumerate().map(|(row, variable)| {
+                            let on_remove = {
+                                let drafts = drafts.clone();
+                                Callback::from(move |_event: MouseEvent| {
+                                    let mut next = (*drafts).clone();
+                                    if let Some(environment) = next.get_mut(index) {
+                                        if row < environment.variables.len() {
+                                            environment.variables.remove(row);
+                                        }
+                                    }
+                                    drafts.set(next);
+                                })
+                            };
+                            html! {
+                                <tr>
+                                    <td>
+                                        <input
+                                            type="checkbox"
+                                            checked={variable.enable}
+                                            onchange={toggle_variable(row, |variable, value| variable.enable = value)}
+                                        />
+                                    </td>
+                                    <td>
+                                        <input
+                                            type="text"
+                                            value={variable.key.clone()}
+                                            oninput={update_variable(row, |variable, value| variable.key = value)}
+                                        />
+                                    </td>
+                                    <td>
+                                        <input
+                                            type={if variable.secret { "password" } else { "text" }}
+                                            value={variable.value.clone()}
+                                            oninput={update_variable(row, |variable, value| variable.value = value)}
+                                        />
+                                    </td>
+                                    <td>
+                                        <input
+                                            type="checkbox"
+                                            checked={variable.secret}
+                                            onchange={toggle_variable(row, |variable, value| variable.secret = value)}
+                                        />
+                                    </td>
+                                    <td>
+                                        <button class="button ghost" onclick={on_remove}>{ "X" }</button>
+                                    </td>
+                                </tr>
+                            }
+                        }) }
+                    </tbody>
+                    <tfoot>
+                        <tr>
+                            <td class="table-add-cell" colspan="5">
+                                <button class="button ghost table-add" onclick={on_add_variable}>{ "+" }</button>
+                            </td>
+                        </tr>
+                    </tfoot>
+                </table>
+                <p class="modal-text muted">
+                    { "Use {{key}} in the URL, params, headers or body. Secret values are masked and saved to the encrypted vault, not the collection file." }
+                </p>
+            </div>
+        }
+    } else {
+        html! { <p class="modal-text muted">{ "No environments yet." }</p> }
+    };
+
+    let on_save = {
+        let tree_state = tree_state.clone();
+        let drafts = drafts.clone();
+        let active = active.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| {
+            tree_state.dispatch(TreeAction::SetEnvironments {
+                environments: (*drafts).clone(),
+            });
+            tree_state.dispatch(TreeAction::SetActiveEnvironment { index: *active });
+            on_close.emit(());
+        })
+    };
+
+    let on_cancel = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| on_close.emit(()))
+    };
+
+    html! {
+        <div class="modal-backdrop">
+            <div class="modal environments-modal">
+                <h2 class="modal-title">{ "Environments" }</h2>
+                <div class="environments-layout">
+                    <div class="environment-list">
+                        { for drafts.iter().enumerate().map(|(index, environment)| html! {
+                            <button
+                                type="button"
+                                class={classes!("tree-menu-item", (*selected == Some(index)).then_some("selected"))}
+                                onclick={on_select(index)}
+                            >
+                                { if environment.name.trim().is_empty() { "Untitled" } else { environment.name.as_str() } }
+                            </button>
+                        }) }
+                        <button type="button" class="button ghost" onclick={on_add}>{ "Add environment" }</button>
+                    </div>
+                    { editor }
+                </div>
+                <div class="modal-actions">
+                    <button class="button secondary" onclick={on_cancel}>{ "Cancel" }</button>
+                    <button class="button" onclick={on_save}>{ "Save" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn checkbox_value(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.checked())
+        .unwrap_or(false)
+}
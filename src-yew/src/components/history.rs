@@ -0,0 +1,321 @@
+use js_sys::Date;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::request::url::send_tab_request;
+use crate::history::HistoryDetail;
+use crate::state::{
+    HistoryAction, HistoryState, HistorySummary, TabAction, TabState, TreeState,
+};
+use crate::tauri_api;
+
+#[derive(Clone, Copy, PartialEq)]
+enum HistoryFilter {
+    All,
+    Success,
+    Errors,
+}
+
+impl HistoryFilter {
+    fn from_value(value: &str) -> Self {
+        match value {
+            "success" => Self::Success,
+            "errors" => Self::Errors,
+            _ => Self::All,
+        }
+    }
+
+    fn matches(self, entry: &HistorySummary) -> bool {
+        match self {
+            Self::All => true,
+            Self::Success => entry.ok,
+            Self::Errors => !entry.ok,
+        }
+    }
+}
+
+#[function_component(HistoryPanel)]
+pub fn history_panel() -> Html {
+    let history_state = use_context::<UseReducerHandle<HistoryState>>();
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let query = use_state(String::new);
+    let filter = use_state(|| HistoryFilter::All);
+    let confirm_clear = use_state(|| false);
+
+    let (Some(history_state), Some(tab_state), Some(tree_state)) =
+        (history_state, tab_state, tree_state)
+    else {
+        return html! {};
+    };
+
+    let on_query = {
+        let query = query.clone();
+        Callback::from(move |event: InputEvent| query.set(input_value(&event)))
+    };
+    let on_filter = {
+        let filter = filter.clone();
+        Callback::from(move |event: Event| filter.set(HistoryFilter::from_value(&select_value(&event))))
+    };
+    let on_clear = {
+        let confirm_clear = confirm_clear.clone();
+        Callback::from(move |_| confirm_clear.set(true))
+    };
+    let on_cancel_clear = {
+        let confirm_clear = confirm_clear.clone();
+        Callback::from(move |_| confirm_clear.set(false))
+    };
+    let on_confirm_clear = {
+        let confirm_clear = confirm_clear.clone();
+        let history_state = history_state.clone();
+        Callback::from(move |_| {
+            let history_state = history_state.clone();
+            spawn_local(async move {
+                match tauri_api::clear_history().await {
+                    Ok(()) => history_state.dispatch(HistoryAction::Clear),
+                    Err(err) => show_alert(&tauri_api::js_error_to_string(&err)),
+                }
+            });
+            confirm_clear.set(false);
+        })
+    };
+
+    let needle = query.trim().to_lowercase();
+    let entries = history_state
+        .entries
+        .iter()
+        .filter(|entry| filter.matches(entry) && matches_query(entry, &needle))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    html! {
+        <div class="history">
+            <div class="history-filters">
+                <input
+                    class="history-search"
+                    placeholder="Search"
+                    value={(*query).clone()}
+                    oninput={on_query}
+                />
+                <select class="history-filter" onchange={on_filter}>
+                    <option value="all" selected={*filter == HistoryFilter::All}>{ "All" }</option>
+                    <option value="success" selected={*filter == HistoryFilter::Success}>{ "Success" }</option>
+                    <option value="errors" selected={*filter == HistoryFilter::Errors}>{ "Errors" }</option>
+                </select>
+                <button
+                    type="button"
+                    class="button ghost"
+                    onclick={on_clear}
+                    disabled={history_state.entries.is_empty()}
+                >
+                    { "Clear" }
+                </button>
+            </div>
+            {
+                if entries.is_empty() {
+                    let message = if history_state.entries.is_empty() {
+                        "Requests you send show up here."
+                    } else {
+                        "No matching requests."
+                    };
+                    html! { <div class="tree-empty">{ message }</div> }
+                } else {
+                    html! {
+                        <div class="history-list">
+                            { for entries.into_iter().map(|entry| {
+                                html! {
+                                    <HistoryRow
+                                        key={entry.id.clone()}
+                                        entry={entry.clone()}
+                                        tab_state={tab_state.clone()}
+                                        tree_state={tree_state.clone()}
+                                        history_state={history_state.clone()}
+                                    />
+                                }
+                            }) }
+                        </div>
+                    }
+                }
+            }
+            {
+                if *confirm_clear {
+                    html! {
+                        <div class="modal-backdrop">
+                            <div class="modal">
+                                <h2 class="modal-title">{ "Clear history" }</h2>
+                                <p class="modal-text">{ "Remove every recorded request?" }</p>
+                                <div class="modal-actions">
+                                    <button class="button secondary" onclick={on_cancel_clear}>{ "Cancel" }</button>
+                                    <button class="button danger" onclick={on_confirm_clear}>{ "Clear" }</button>
+                                </div>
+                            </div>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct HistoryRowProps {
+    entry: HistorySummary,
+    tab_state: UseReducerHandle<TabState>,
+    tree_state: UseReducerHandle<TreeState>,
+    history_state: UseReducerHandle<HistoryState>,
+}
+
+#[function_component(HistoryRow)]
+fn history_row(props: &HistoryRowProps) -> Html {
+    let entry = &props.entry;
+
+    let on_open = {
+        let tab_state = props.tab_state.clone();
+        let entry = entry.clone();
+        Callback::from(move |_| {
+            let tab_state = tab_state.clone();
+            let entry = entry.clone();
+            spawn_local(async move {
+                let Some(mut detail) = load_detail(&entry.id).await else {
+                    return;
+                };
+                detail.content.response = detail.response;
+                tab_state.dispatch(TabAction::OpenTab {
+                    label: entry.tab_label,
+                    content: Box::new(detail.content),
+                });
+            });
+        })
+    };
+
+    let on_resend = {
+        let tab_state = props.tab_state.clone();
+        let tree_state = props.tree_state.clone();
+        let history_state = props.history_state.clone();
+        let entry = entry.clone();
+        Callback::from(move |_| {
+            let tab_state = tab_state.clone();
+            let tree_state = tree_state.clone();
+            let history_state = history_state.clone();
+            let entry = entry.clone();
+            spawn_local(async move {
+                let Some(detail) = load_detail(&entry.id).await else {
+                    return;
+                };
+                let index = tab_state.tabs.len();
+                let server_index = entry.server_url.as_ref().and_then(|url| {
+                    tree_state.servers.iter().position(|server| &server.url == url)
+                });
+                let content = detail.content;
+                tab_state.dispatch(TabAction::OpenTab {
+                    label: entry.tab_label.clone(),
+                    content: Box::new(content.clone()),
+                });
+                send_tab_request(
+                    &tab_state,
+                    &tree_state,
+                    Some(&history_state),
+                    index,
+                    entry.tab_label,
+                    content,
+                    server_index,
+                );
+            });
+        })
+    };
+
+    let method_class = format!("method-{}", entry.method.key());
+    let status = if entry.status == 0 {
+        "ERR".to_string()
+    } else {
+        entry.status.to_string()
+    };
+    let duration = entry
+        .duration_ms
+        .map(|duration| format!("{duration} ms"))
+        .unwrap_or_default();
+
+    html! {
+        <div class="history-row" title={entry.url.clone()}>
+            <div class="history-row-main">
+                <span class={classes!("tree-tab-icon", method_class)} aria-hidden="true">
+                    { entry.method.as_str().chars().next().unwrap_or(' ') }
+                </span>
+                <span class="history-url">{ entry.url.clone() }</span>
+            </div>
+            <div class="history-row-meta">
+                <span class={classes!("history-status", if entry.ok { "ok" } else { "error" })}>
+                    { status }
+                </span>
+                <span class="muted">{ format_timestamp(entry.timestamp) }</span>
+                <span class="muted">{ duration }</span>
+                <span class="history-actions">
+                    <button type="button" class="tree-menu-item" onclick={on_open}>{ "Open" }</button>
+                    <button type="button" class="tree-menu-item" onclick={on_resend}>{ "Resend" }</button>
+                </span>
+            </div>
+        </div>
+    }
+}
+
+async fn load_detail(id: &str) -> Option<HistoryDetail> {
+    let result = match tauri_api::get_history_entry(id).await {
+        Ok(Some(value)) => HistoryDetail::from_value(value),
+        Ok(None) => Err("Entrada não está mais no histórico.".to_string()),
+        Err(err) => Err(tauri_api::js_error_to_string(&err)),
+    };
+    result.map_err(|message| show_alert(&message)).ok()
+}
+
+fn matches_query(entry: &HistorySummary, needle: &str) -> bool {
+    needle.is_empty()
+        || [
+            entry.url.as_str(),
+            entry.method.as_str(),
+            entry.tab_label.as_str(),
+            entry.server_url.as_deref().unwrap_or_default(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(needle))
+        || entry.status.to_string() == needle
+}
+
+/// Local date and time as `YYYY-MM-DD HH:MM:SS`.
+fn format_timestamp(millis: f64) -> String {
+    let date = Date::new(&JsValue::from_f64(millis));
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes(),
+        date.get_seconds()
+    )
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn select_value(event: &Event) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+        .map(|select| select.value())
+        .unwrap_or_default()
+}
+
+fn show_alert(message: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
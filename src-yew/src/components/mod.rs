@@ -1,7 +1,14 @@
+pub mod cookie_manager;
+pub mod environments;
+pub mod history;
 pub mod json_highlight;
+pub mod proxy_settings;
 pub mod request;
 pub mod response;
 pub mod section;
+pub mod server_settings;
 pub mod side;
 pub mod tools;
 pub mod tree;
+pub mod vault;
+pub mod websocket;
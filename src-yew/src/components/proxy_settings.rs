@@ -0,0 +1,232 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::state::{AppSettings, ProxyConfig};
+use crate::tauri_api;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ProxyFieldsProps {
+    pub config: ProxyConfig,
+    pub on_change: Callback<ProxyConfig>,
+}
+
+/// URL, credentials and bypass list of a proxy, shared by the global and server dialogs.
+#[function_component(ProxyFields)]
+pub fn proxy_fields(props: &ProxyFieldsProps) -> Html {
+    let field = |apply: fn(&mut ProxyConfig, String)| {
+        let config = props.config.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = config.clone();
+            apply(&mut next, input_value(&event));
+            on_change.emit(next);
+        })
+    };
+
+    html! {
+        <>
+            <label class="modal-label">{ "Proxy URL" }</label>
+            <input
+                class="modal-input"
+                type="text"
+                placeholder="http://proxy:8080 or socks5://127.0.0.1:1080"
+                value={props.config.url.clone()}
+                oninput={field(|config, value| config.url = value)}
+            />
+            <div class="modal-row">
+                <div class="modal-column">
+                    <label class="modal-label">{ "Username" }</label>
+                    <input
+                        class="modal-input"
+                        type="text"
+                        value={props.config.username.clone()}
+                        oninput={field(|config, value| config.username = value)}
+                    />
+                </div>
+                <div class="modal-column">
+                    <label class="modal-label">{ "Password" }</label>
+                    <input
+                        class="modal-input"
+                        type="password"
+                        value={props.config.password.clone()}
+                        oninput={field(|config, value| config.password = value)}
+                    />
+                </div>
+            </div>
+            <label class="modal-label">{ "Bypass proxy for" }</label>
+            <input
+                class="modal-input"
+                type="text"
+                placeholder="localhost, .internal.example.com, 10.0.0.0/8"
+                value={props.config.no_proxy.clone()}
+                oninput={field(|config, value| config.no_proxy = value)}
+            />
+        </>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ProxySettingsDialogProps {
+    pub on_close: Callback<()>,
+}
+
+/// App-wide settings: the global proxy, used by every server that does not override
+/// it, and the in-memory response size limit.
+#[function_component(ProxySettingsDialog)]
+pub fn proxy_settings_dialog(props: &ProxySettingsDialogProps) -> Html {
+    let settings = use_state(|| None::<AppSettings>);
+    let error = use_state(|| None::<String>);
+
+    {
+        let settings = settings.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match tauri_api::get_app_settings().await {
+                    Ok(loaded) => settings.set(Some(loaded)),
+                    Err(err) => {
+                        settings.set(Some(AppSettings::default()));
+                        error.set(Some(tauri_api::js_error_to_string(&err)));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_cancel = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| on_close.emit(()))
+    };
+
+    let Some(current) = (*settings).clone() else {
+        return html! {};
+    };
+
+    let toggle = |apply: fn(&mut AppSettings, bool)| {
+        let settings = settings.clone();
+        let current = current.clone();
+        Callback::from(move |event: Event| {
+            let mut next = current.clone();
+            apply(&mut next, checkbox_value(&event));
+            settings.set(Some(next));
+        })
+    };
+
+    let on_proxy_change = {
+        let settings = settings.clone();
+        let current = current.clone();
+        Callback::from(move |proxy: ProxyConfig| {
+            let mut next = current.clone();
+            next.proxy.proxy = proxy;
+            settings.set(Some(next));
+        })
+    };
+
+    let on_limit_change = {
+        let settings = settings.clone();
+        let current = current.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = current.clone();
+            next.response_limit_mb = input_value(&event)
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|value| *value > 0);
+            settings.set(Some(next));
+        })
+    };
+
+    let on_save = {
+        let current = current.clone();
+        let error = error.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let current = current.clone();
+            let error = error.clone();
+            let on_close = on_close.clone();
+            spawn_local(async move {
+                match tauri_api::save_app_settings(&current).await {
+                    Ok(()) => on_close.emit(()),
+                    Err(err) => error.set(Some(tauri_api::js_error_to_string(&err))),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="modal-backdrop">
+            <div class="modal auth-modal settings-modal">
+                <h2 class="modal-title">{ "Settings" }</h2>
+                <h3 class="modal-subtitle">{ "Proxy" }</h3>
+                <p class="modal-text muted">
+                    { "Applies to every server unless its own settings choose a different proxy." }
+                </p>
+                <label class="modal-label-inline">
+                    <input
+                        type="checkbox"
+                        checked={current.proxy.enabled}
+                        onchange={toggle(|settings, value| settings.proxy.enabled = value)}
+                    />
+                    <span>{ " Use a proxy" }</span>
+                </label>
+                if current.proxy.enabled {
+                    <ProxyFields config={current.proxy.proxy.clone()} on_change={on_proxy_change} />
+                }
+                <label class="modal-label-inline">
+                    <input
+                        type="checkbox"
+                        checked={current.proxy.use_env}
+                        onchange={toggle(|settings, value| settings.proxy.use_env = value)}
+                    />
+                    <span>{ " Otherwise use HTTP_PROXY / HTTPS_PROXY / NO_PROXY from the environment" }</span>
+                </label>
+                <h3 class="modal-subtitle">{ "Responses" }</h3>
+                <label class="modal-label">{ "Keep bodies in memory up to (MB)" }</label>
+                <input
+                    class="modal-input"
+                    type="number"
+                    min="1"
+                    placeholder="10"
+                    value={current.response_limit_mb.map(|value| value.to_string()).unwrap_or_default()}
+                    oninput={on_limit_change}
+                />
+                <p class="modal-text muted">
+                    { "Larger responses are written to a temp file and shown as a preview." }
+                </p>
+                <label class="modal-label-inline">
+                    <input
+                        type="checkbox"
+                        checked={current.restore_responses}
+                        onchange={toggle(|settings, value| settings.restore_responses = value)}
+                    />
+                    <span>{ " Restore the last responses with the open tabs" }</span>
+                </label>
+                if let Some(message) = (*error).clone() {
+                    <p class="modal-text error">{ message }</p>
+                }
+                <div class="modal-actions">
+                    <button class="button secondary" onclick={on_cancel}>{ "Cancel" }</button>
+                    <button class="button" onclick={on_save}>{ "Save" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn checkbox_value(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.checked())
+        .unwrap_or(false)
+}
@@ -1,14 +1,24 @@
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::components::json_highlight::{highlight_json, parse_json_value};
-use crate::state::{TabAction, TabState};
+use crate::components::request::form_table::FormTable;
+use crate::components::request::graphql::GraphQlBody;
+use crate::components::response::binary::format_size;
+use crate::state::{BodyMode, FileInfo, FormField, TabAction, TabState};
+use crate::tauri_api;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct RequestBodyProps {
     pub tab_index: usize,
     pub body: String,
     pub formatted: bool,
+    pub mode: BodyMode,
+    pub form_fields: Vec<FormField>,
+    pub file: String,
+    pub graphql_query: String,
+    pub graphql_variables: String,
 }
 
 #[function_component(RequestBody)]
@@ -20,6 +30,22 @@ pub fn request_body(props: &RequestBodyProps) -> Html {
     let index = props.tab_index;
     let body = props.body.clone();
     let formatted = props.formatted;
+    let mode = props.mode;
+
+    let on_mode_change = {
+        let tab_state = tab_state.clone();
+        Callback::from(move |event: Event| {
+            let value = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+                .map(|select| select.value())
+                .unwrap_or_default();
+            tab_state.dispatch(TabAction::UpdateBodyMode {
+                index,
+                mode: BodyMode::from_key(&value),
+            });
+        })
+    };
 
     let on_change = {
         let tab_state = tab_state.clone();
@@ -61,14 +87,41 @@ pub fn request_body(props: &RequestBodyProps) -> Html {
             <div class="table-wrap body-wrap">
                 <div class="request-title">
                     <h1>{ "Body" }</h1>
-                    <button class="button secondary" onclick={on_format}>
-                        { if formatted { "Edit" } else { "Format" } }
-                    </button>
+                    <div class="body-actions">
+                        <select class="body-mode" onchange={on_mode_change}>
+                            { for BodyMode::all().iter().map(|option| html! {
+                                <option value={option.key()} selected={*option == mode}>{ option.label() }</option>
+                            }) }
+                        </select>
+                        if mode == BodyMode::Raw {
+                            <button class="button secondary" onclick={on_format}>
+                                { if formatted { "Edit" } else { "Format" } }
+                            </button>
+                        }
+                    </div>
                 </div>
                 <hr class="section-divider" />
                 <div class="body-editor-wrap">
                     {
-                        if formatted {
+                        if mode == BodyMode::Binary {
+                            html! { <FileBody tab_index={index} path={props.file.clone()} /> }
+                        } else if mode == BodyMode::GraphQl {
+                            html! {
+                                <GraphQlBody
+                                    tab_index={index}
+                                    query={props.graphql_query.clone()}
+                                    variables={props.graphql_variables.clone()}
+                                />
+                            }
+                        } else if mode != BodyMode::Raw {
+                            html! {
+                                <FormTable
+                                    tab_index={index}
+                                    fields={props.form_fields.clone()}
+                                    allow_files={mode == BodyMode::Multipart}
+                                />
+                            }
+                        } else if formatted {
                             if let Some(highlight) = highlight_json(&body) {
                                 html! { <pre class="editor body-editor response-code"><code>{ highlight }</code></pre> }
                             } else {
@@ -83,6 +136,89 @@ pub fn request_body(props: &RequestBodyProps) -> Html {
     }
 }
 
+#[derive(Properties, Clone, PartialEq)]
+struct FileBodyProps {
+    tab_index: usize,
+    path: String,
+}
+
+/// Path of the file streamed as the body, with its size and detected MIME type.
+#[function_component(FileBody)]
+fn file_body(props: &FileBodyProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let info = use_state(|| None::<Result<FileInfo, String>>);
+
+    {
+        let info = info.clone();
+        use_effect_with(props.path.clone(), move |path| {
+            let path = path.trim().to_string();
+            if path.is_empty() {
+                info.set(None);
+            } else {
+                spawn_local(async move {
+                    let result = tauri_api::file_info(&path)
+                        .await
+                        .map_err(|err| tauri_api::js_error_to_string(&err));
+                    info.set(Some(result));
+                });
+            }
+            || ()
+        });
+    }
+
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+
+    let on_input = {
+        let tab_state = tab_state.clone();
+        Callback::from(move |event: InputEvent| {
+            let path = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                .map(|input| input.value())
+                .unwrap_or_default();
+            tab_state.dispatch(TabAction::UpdateBodyFile { index, path });
+        })
+    };
+
+    let on_browse = {
+        let tab_state = tab_state.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let tab_state = tab_state.clone();
+            spawn_local(async move {
+                if let Ok(Some(path)) = tauri_api::dialog_open().await {
+                    tab_state.dispatch(TabAction::UpdateBodyFile { index, path });
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="file-body">
+            <div class="form-file-cell">
+                <input
+                    type="text"
+                    placeholder="No file selected"
+                    value={props.path.clone()}
+                    oninput={on_input}
+                />
+                <button class="button secondary" onclick={on_browse}>{ "Browse" }</button>
+            </div>
+            {
+                match (*info).clone() {
+                    Some(Ok(info)) => html! {
+                        <p class="muted">{ format!("{} · {} · {}", info.name, format_size(info.size), info.mime) }</p>
+                    },
+                    Some(Err(message)) => html! { <p class="modal-text error">{ message }</p> },
+                    None => html! {},
+                }
+            }
+        </div>
+    }
+}
+
 fn event_target_value(event: &InputEvent) -> String {
     event
         .target()
@@ -4,6 +4,7 @@ use crate::components::request::body::RequestBody;
 use crate::components::request::header_table::HeaderTable;
 use crate::components::request::param_table::ParamTable;
 use crate::components::request::path_table::PathTable;
+use crate::components::request::settings::RequestSettingsPanel;
 use crate::state::TabContent;
 
 #[derive(Properties, Clone, PartialEq)]
@@ -49,6 +50,12 @@ pub fn request_content(props: &RequestContentProps) -> Html {
                 >
                     { "Body" }
                 </button>
+                <button
+                    class={classes!("subtab", if *active == "settings" { "active" } else { "" })}
+                    onclick={on_select("settings", active.clone())}
+                >
+                    { "Settings" }
+                </button>
             </div>
 
             {
@@ -66,8 +73,16 @@ pub fn request_content(props: &RequestContentProps) -> Html {
                             tab_index={tab_index}
                             body={content.body.clone()}
                             formatted={content.body_formatted}
+                            mode={content.body_mode}
+                            form_fields={content.form_fields.clone()}
+                            file={content.body_file.clone()}
+                            graphql_query={content.graphql_query.clone()}
+                            graphql_variables={content.graphql_variables.clone()}
                         />
                     },
+                    "settings" => html! {
+                        <RequestSettingsPanel tab_index={tab_index} settings={content.settings.clone()} />
+                    },
                     _ => html! { <ParamTable tab_index={tab_index} url={content.url.clone()} params={content.params.clone()} /> },
                 }
             }
@@ -0,0 +1,235 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::environment::Variables;
+use crate::state::{FormField, TabAction, TabState, TreeState};
+use crate::tauri_api;
+
+/// Row index plus the change to apply to that row.
+type FieldEdit = (usize, Box<dyn FnOnce(&mut FormField)>);
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct FormTableProps {
+    pub tab_index: usize,
+    pub fields: Vec<FormField>,
+    /// Multipart bodies can carry file parts; urlencoded ones only text.
+    pub allow_files: bool,
+}
+
+#[function_component(FormTable)]
+pub fn form_table(props: &FormTableProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let variables = use_context::<UseReducerHandle<TreeState>>()
+        .map(|tree_state| Variables::active(&tree_state))
+        .unwrap_or_default();
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let fields = props.fields.clone();
+    let allow_files = props.allow_files;
+
+    let update_fields = {
+        let tab_state = tab_state.clone();
+        move |next_fields: Vec<FormField>| {
+            tab_state.dispatch(TabAction::SetFormFields {
+                index,
+                fields: next_fields,
+            });
+        }
+    };
+
+    let on_edit = {
+        let update_fields = update_fields.clone();
+        let fields = fields.clone();
+        Callback::from(move |(row_index, apply): FieldEdit| {
+            let mut next_fields = fields.clone();
+            if let Some(field) = next_fields.get_mut(row_index) {
+                apply(field);
+            }
+            update_fields(next_fields);
+        })
+    };
+
+    let on_remove = {
+        let update_fields = update_fields.clone();
+        let fields = fields.clone();
+        Callback::from(move |row_index: usize| {
+            if fields.len() <= 1 {
+                return;
+            }
+            let mut next_fields = fields.clone();
+            if row_index < next_fields.len() {
+                next_fields.remove(row_index);
+            }
+            update_fields(next_fields);
+        })
+    };
+
+    let on_add = {
+        let update_fields = update_fields.clone();
+        let fields = fields.clone();
+        Callback::from(move |_| {
+            let mut next_fields = fields.clone();
+            next_fields.push(FormField::empty());
+            update_fields(next_fields);
+        })
+    };
+
+    let columns = if allow_files { "5" } else { "4" };
+
+    html! {
+        <div class="table-wrap">
+            <table>
+                <thead>
+                    <tr>
+                        <th>{ "" }</th>
+                        <th>{ "KEY" }</th>
+                        if allow_files {
+                            <th>{ "TYPE" }</th>
+                        }
+                        <th>{ "VALUE" }</th>
+                        <th>{ "REMOVE" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for fields.iter().enumerate().map(|(row_index, field)| {
+                        let is_file = allow_files && field.file;
+                        let on_toggle = {
+                            let on_edit = on_edit.clone();
+                            Callback::from(move |event: Event| {
+                                let checked = event_target_checked(&event);
+                                on_edit.emit((row_index, Box::new(move |field: &mut FormField| field.enable = checked)));
+                            })
+                        };
+                        let on_key_change = {
+                            let on_edit = on_edit.clone();
+                            Callback::from(move |event: InputEvent| {
+                                let value = event_target_value(&event);
+                                on_edit.emit((row_index, Box::new(move |field: &mut FormField| field.key = value)));
+                            })
+                        };
+                        let on_value_change = {
+                            let on_edit = on_edit.clone();
+                            Callback::from(move |event: InputEvent| {
+                                let value = event_target_value(&event);
+                                on_edit.emit((row_index, Box::new(move |field: &mut FormField| field.value = value)));
+                            })
+                        };
+                        let on_kind_change = {
+                            let on_edit = on_edit.clone();
+                            Callback::from(move |event: Event| {
+                                let file = event_target_select(&event) == "file";
+                                on_edit.emit((row_index, Box::new(move |field: &mut FormField| {
+                                    if field.file != file {
+                                        field.file = file;
+                                        field.value.clear();
+                                    }
+                                })));
+                            })
+                        };
+                        let on_browse = {
+                            let on_edit = on_edit.clone();
+                            Callback::from(move |_event: MouseEvent| {
+                                let on_edit = on_edit.clone();
+                                spawn_local(async move {
+                                    if let Ok(Some(path)) = tauri_api::dialog_open().await {
+                                        on_edit.emit((row_index, Box::new(move |field: &mut FormField| field.value = path)));
+                                    }
+                                });
+                            })
+                        };
+                        let on_remove_click = {
+                            let on_remove = on_remove.clone();
+                            Callback::from(move |_| on_remove.emit(row_index))
+                        };
+                        html! {
+                            <tr>
+                                <td>
+                                    <input type="checkbox" checked={field.enable} onchange={on_toggle} />
+                                </td>
+                                <td>
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&field.key).then_some("unresolved"))}
+                                        value={field.key.clone()}
+                                        oninput={on_key_change}
+                                    />
+                                </td>
+                                if allow_files {
+                                    <td>
+                                        <select onchange={on_kind_change}>
+                                            <option value="text" selected={!field.file}>{ "Text" }</option>
+                                            <option value="file" selected={field.file}>{ "File" }</option>
+                                        </select>
+                                    </td>
+                                }
+                                <td>
+                                    if is_file {
+                                        <div class="form-file-cell">
+                                            <input
+                                                type="text"
+                                                placeholder="No file selected"
+                                                value={field.value.clone()}
+                                                oninput={on_value_change}
+                                            />
+                                            <button class="button secondary" onclick={on_browse}>{ "Browse" }</button>
+                                        </div>
+                                    } else {
+                                        <input
+                                            type="text"
+                                            class={classes!(variables.has_unresolved(&field.value).then_some("unresolved"))}
+                                            value={field.value.clone()}
+                                            oninput={on_value_change}
+                                        />
+                                    }
+                                </td>
+                                <td>
+                                    {
+                                        if fields.len() <= 1 {
+                                            html! {}
+                                        } else {
+                                            html! { <button class="button ghost" onclick={on_remove_click}>{ "X" }</button> }
+                                        }
+                                    }
+                                </td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+                <tfoot>
+                    <tr>
+                        <td class="table-add-cell" colspan={columns}>
+                            <button class="button ghost table-add" onclick={on_add}>{ "+" }</button>
+                        </td>
+                    </tr>
+                </tfoot>
+            </table>
+        </div>
+    }
+}
+
+fn event_target_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn event_target_checked(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.checked())
+        .unwrap_or(false)
+}
+
+fn event_target_select(event: &Event) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+        .map(|select| select.value())
+        .unwrap_or_default()
+}
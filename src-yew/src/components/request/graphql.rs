@@ -0,0 +1,319 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::request::url::introspect_schema;
+use crate::environment::Variables;
+use crate::graphql::{self, Completion};
+use crate::state::{TabAction, TabState, TreeAction, TreeState};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct GraphQlBodyProps {
+    pub tab_index: usize,
+    pub query: String,
+    pub variables: String,
+}
+
+/// Query and variables editors with completion and validation against the selected
+/// server's introspected schema.
+#[function_component(GraphQlBody)]
+pub fn graphql_body(props: &GraphQlBodyProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let query_ref = use_node_ref();
+    // Byte offset of the caret in the query, and whether suggestions are showing.
+    let cursor = use_state(|| 0usize);
+    let suggesting = use_state(|| false);
+    let highlighted = use_state(|| 0usize);
+    let pending_caret = use_mut_ref(|| None::<u32>);
+    let introspecting = use_state(|| false);
+    let introspect_error = use_state(|| None::<String>);
+
+    {
+        let query_ref = query_ref.clone();
+        let pending_caret = pending_caret.clone();
+        use_effect(move || {
+            if let Some(caret) = pending_caret.borrow_mut().take() {
+                if let Some(textarea) = query_ref.cast::<web_sys::HtmlTextAreaElement>() {
+                    let _ = textarea.set_selection_range(caret, caret);
+                    let _ = textarea.focus();
+                }
+            }
+            || ()
+        });
+    }
+
+    let (Some(tab_state), Some(tree_state)) = (tab_state, tree_state) else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let query = props.query.clone();
+    let variables = props.variables.clone();
+    let server_index = tree_state.selected_server;
+    let schema = server_index
+        .and_then(|index| tree_state.servers.get(index))
+        .and_then(|server| server.graphql_schema.clone());
+
+    let completion: Option<Completion> = (*suggesting)
+        .then_some(schema.as_deref())
+        .flatten()
+        .and_then(|schema| graphql::complete(schema, &query, *cursor));
+    let errors = if query.trim().is_empty() {
+        Vec::new()
+    } else {
+        graphql::validate(&query, schema.as_deref())
+    };
+
+    let update = {
+        let tab_state = tab_state.clone();
+        move |query: String, variables: String| {
+            tab_state.dispatch(TabAction::UpdateGraphQl {
+                index,
+                query,
+                variables,
+            });
+        }
+    };
+
+    let track_cursor = {
+        let cursor = cursor.clone();
+        let highlighted = highlighted.clone();
+        move |textarea: &web_sys::HtmlTextAreaElement| {
+            let value = textarea.value();
+            let caret = textarea.selection_start().ok().flatten().unwrap_or(0);
+            cursor.set(byte_offset(&value, caret));
+            highlighted.set(0);
+        }
+    };
+
+    let on_query_input = {
+        let update = update.clone();
+        let variables = variables.clone();
+        let track_cursor = track_cursor.clone();
+        let suggesting = suggesting.clone();
+        Callback::from(move |event: InputEvent| {
+            let Some(textarea) = textarea_target(&event) else {
+                return;
+            };
+            track_cursor(&textarea);
+            suggesting.set(true);
+            update(textarea.value(), variables.clone());
+        })
+    };
+
+    let on_query_click = {
+        let suggesting = suggesting.clone();
+        Callback::from(move |event: MouseEvent| {
+            if let Some(textarea) = textarea_target(&event) {
+                track_cursor(&textarea);
+            }
+            suggesting.set(false);
+        })
+    };
+
+    let accept = {
+        let update = update.clone();
+        let query = query.clone();
+        let variables = variables.clone();
+        let end = (*cursor).min(query.len());
+        let suggesting = suggesting.clone();
+        let pending_caret = pending_caret.clone();
+        move |start: usize, label: &str| {
+            let mut next = String::with_capacity(query.len() + label.len());
+            next.push_str(&query[..start]);
+            next.push_str(label);
+            next.push_str(&query[end..]);
+            let caret = start + label.len();
+            *pending_caret.borrow_mut() = Some(next[..caret].encode_utf16().count() as u32);
+            suggesting.set(false);
+            update(next, variables.clone());
+        }
+    };
+
+    let on_query_keydown = {
+        let completion = completion.clone();
+        let highlighted = highlighted.clone();
+        let suggesting = suggesting.clone();
+        let accept = accept.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if event.key() == " " && event.ctrl_key() {
+                event.prevent_default();
+                suggesting.set(true);
+                return;
+            }
+            let Some(completion) = completion.as_ref() else {
+                return;
+            };
+            let count = completion.items.len();
+            match event.key().as_str() {
+                "ArrowDown" => {
+                    event.prevent_default();
+                    highlighted.set((*highlighted + 1) % count);
+                }
+                "ArrowUp" => {
+                    event.prevent_default();
+                    highlighted.set((*highlighted + count - 1) % count);
+                }
+                "Enter" | "Tab" => {
+                    event.prevent_default();
+                    if let Some(item) = completion.items.get(*highlighted) {
+                        accept(completion.start, &item.label);
+                    }
+                }
+                "Escape" => suggesting.set(false),
+                _ => {}
+            }
+        })
+    };
+
+    let on_query_blur = {
+        let suggesting = suggesting.clone();
+        Callback::from(move |_event: FocusEvent| suggesting.set(false))
+    };
+
+    let on_variables_input = {
+        let query = query.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(textarea) = textarea_target(&event) {
+                update(query.clone(), textarea.value());
+            }
+        })
+    };
+
+    let on_introspect = {
+        let tab_state = tab_state.clone();
+        let tree_state = tree_state.clone();
+        let introspecting = introspecting.clone();
+        let introspect_error = introspect_error.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let Some(server_index) = server_index else {
+                introspect_error.set(Some("Selecione um server.".to_string()));
+                return;
+            };
+            let Some(tab) = tab_state.tabs.get(index).cloned() else {
+                return;
+            };
+            let variables = Variables::active(&tree_state);
+            let server = tree_state.servers.get(server_index);
+            if let Err(error) = variables.check_vault(&tab.content, server) {
+                introspect_error.set(Some(error));
+                return;
+            }
+            let server = server.map(|server| variables.resolve_server(server));
+            let content = variables.resolve(&tab.content);
+            let tree_state = tree_state.clone();
+            let introspecting = introspecting.clone();
+            let introspect_error = introspect_error.clone();
+            introspecting.set(true);
+            spawn_local(async move {
+                match introspect_schema(&content, server.as_ref()).await {
+                    Ok(schema) => {
+                        introspect_error.set(None);
+                        tree_state.dispatch(TreeAction::SetServerSchema {
+                            index: server_index,
+                            schema: Some(Rc::new(schema)),
+                        });
+                    }
+                    Err(error) => introspect_error.set(Some(error)),
+                }
+                introspecting.set(false);
+            });
+        })
+    };
+
+    let schema_status = match (&*introspect_error, schema.as_deref()) {
+        (Some(error), _) => error.clone(),
+        (None, Some(schema)) => format!("Schema · {} types", schema.user_type_count()),
+        (None, None) => "No schema · introspect to enable completion".to_string(),
+    };
+
+    html! {
+        <div class="graphql-body">
+            <div class="graphql-editors">
+                <div class="graphql-editor">
+                    <span class="graphql-label">{ "Query" }</span>
+                    <textarea
+                        ref={query_ref}
+                        class="editor body-editor"
+                        spellcheck="false"
+                        value={query.clone()}
+                        oninput={on_query_input}
+                        onkeydown={on_query_keydown}
+                        onclick={on_query_click}
+                        onblur={on_query_blur}
+                    />
+                    if let Some(completion) = completion {
+                        <ul class="graphql-suggestions">
+                            { for completion.items.iter().enumerate().map(|(position, item)| {
+                                let accept = accept.clone();
+                                let start = completion.start;
+                                let label = item.label.clone();
+                                let onmousedown = Callback::from(move |event: MouseEvent| {
+                                    // Keep focus in the editor so the blur does not hide the list first.
+                                    event.prevent_default();
+                                    accept(start, &label);
+                                });
+                                html! {
+                                    <li
+                                        class={classes!((position == *highlighted).then_some("active"))}
+                                        {onmousedown}
+                                    >
+                                        <span>{ item.label.clone() }</span>
+                                        <span class="graphql-detail">{ item.detail.clone() }</span>
+                                    </li>
+                                }
+                            }) }
+                        </ul>
+                    }
+                </div>
+                <div class="graphql-editor graphql-variables">
+                    <span class="graphql-label">{ "Variables" }</span>
+                    <textarea
+                        class="editor body-editor"
+                        spellcheck="false"
+                        placeholder="{ }"
+                        value={variables}
+                        oninput={on_variables_input}
+                    />
+                </div>
+            </div>
+            <div class="graphql-status">
+                <span class={classes!("graphql-schema", introspect_error.is_some().then_some("error"))}>
+                    { schema_status }
+                </span>
+                <button
+                    class="button secondary"
+                    onclick={on_introspect}
+                    disabled={*introspecting}
+                >
+                    { if *introspecting { "Introspecting..." } else { "Introspect schema" } }
+                </button>
+            </div>
+            if !errors.is_empty() {
+                <ul class="graphql-errors">
+                    { for errors.iter().map(|error| html! { <li>{ error.clone() }</li> }) }
+                </ul>
+            }
+        </div>
+    }
+}
+
+fn textarea_target(event: &Event) -> Option<web_sys::HtmlTextAreaElement> {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+}
+
+/// Converts a textarea caret position (UTF-16 code units) into a byte offset.
+fn byte_offset(text: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    for (index, ch) in text.char_indices() {
+        if units >= utf16 as usize {
+            return index;
+        }
+        units += ch.len_utf16();
+    }
+    text.len()
+}
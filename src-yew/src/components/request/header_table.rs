@@ -1,7 +1,8 @@
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
 
-use crate::state::{Header, TabAction, TabState};
+use crate::environment::Variables;
+use crate::state::{Header, TabAction, TabState, TreeState};
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct HeaderTableProps {
@@ -12,6 +13,9 @@ pub struct HeaderTableProps {
 #[function_component(HeaderTable)]
 pub fn header_table(props: &HeaderTableProps) -> Html {
     let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let variables = use_context::<UseReducerHandle<TreeState>>()
+        .map(|tree_state| Variables::active(&tree_state))
+        .unwrap_or_default();
     let Some(tab_state) = tab_state else {
         return html! {};
     };
@@ -146,10 +150,20 @@ pub fn header_table(props: &HeaderTableProps) -> Html {
                                     <input type="checkbox" checked={header.enable} onchange={on_toggle} />
                                 </td>
                                 <td>
-                                    <input type="text" value={header.key.clone()} oninput={on_key_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&header.key).then_some("unresolved"))}
+                                        value={header.key.clone()}
+                                        oninput={on_key_change}
+                                    />
                                 </td>
                                 <td>
-                                    <input type="text" value={header.value.clone()} oninput={on_value_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&header.value).then_some("unresolved"))}
+                                        value={header.value.clone()}
+                                        oninput={on_value_change}
+                                    />
                                 </td>
                                 <td>
                                     {
@@ -1,7 +1,10 @@
 pub mod body;
 pub mod content;
+pub mod form_table;
+pub mod graphql;
 pub mod header_table;
 pub mod param_table;
 pub mod path_table;
+pub mod settings;
 pub mod title;
 pub mod url;
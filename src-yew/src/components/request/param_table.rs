@@ -1,7 +1,8 @@
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
 
-use crate::state::{Param, TabAction, TabState};
+use crate::environment::Variables;
+use crate::state::{Param, TabAction, TabState, TreeState};
 use crate::utils::url_from_params;
 
 #[derive(Properties, Clone, PartialEq)]
@@ -14,6 +15,9 @@ pub struct ParamTableProps {
 #[function_component(ParamTable)]
 pub fn param_table(props: &ParamTableProps) -> Html {
     let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let variables = use_context::<UseReducerHandle<TreeState>>()
+        .map(|tree_state| Variables::active(&tree_state))
+        .unwrap_or_default();
     let Some(tab_state) = tab_state else {
         return html! {};
     };
@@ -143,10 +147,20 @@ pub fn param_table(props: &ParamTableProps) -> Html {
                                     <input type="checkbox" checked={param.enable} onchange={on_toggle} />
                                 </td>
                                 <td>
-                                    <input type="text" value={param.key.clone()} oninput={on_key_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&param.key).then_some("unresolved"))}
+                                        value={param.key.clone()}
+                                        oninput={on_key_change}
+                                    />
                                 </td>
                                 <td>
-                                    <input type="text" value={param.value.clone()} oninput={on_value_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&param.value).then_some("unresolved"))}
+                                        value={param.value.clone()}
+                                        oninput={on_value_change}
+                                    />
                                 </td>
                                 <td>
                                     {
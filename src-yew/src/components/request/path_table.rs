@@ -1,7 +1,8 @@
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
 
-use crate::state::{Param, TabAction, TabState};
+use crate::environment::Variables;
+use crate::state::{Param, TabAction, TabState, TreeState};
 use crate::utils::{path_params_from_url, url_from_path_params};
 
 #[derive(Properties, Clone, PartialEq)]
@@ -14,6 +15,9 @@ pub struct PathTableProps {
 #[function_component(PathTable)]
 pub fn path_table(props: &PathTableProps) -> Html {
     let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let variables = use_context::<UseReducerHandle<TreeState>>()
+        .map(|tree_state| Variables::active(&tree_state))
+        .unwrap_or_default();
     let Some(tab_state) = tab_state else {
         return html! {};
     };
@@ -160,10 +164,20 @@ pub fn path_table(props: &PathTableProps) -> Html {
                                     <input type="checkbox" checked={param.enable} onchange={on_toggle} />
                                 </td>
                                 <td>
-                                    <input type="text" value={param.key.clone()} oninput={on_key_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&param.key).then_some("unresolved"))}
+                                        value={param.key.clone()}
+                                        oninput={on_key_change}
+                                    />
                                 </td>
                                 <td>
-                                    <input type="text" value={param.value.clone()} oninput={on_value_change} />
+                                    <input
+                                        type="text"
+                                        class={classes!(variables.has_unresolved(&param.value).then_some("unresolved"))}
+                                        value={param.value.clone()}
+                                        oninput={on_value_change}
+                                    />
                                 </td>
                                 <td>
                                     {
@@ -0,0 +1,208 @@
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+use crate::state::{
+    ContentEncoding, HttpVersion, RedirectPolicy, RequestSettings, TabAction, TabState,
+};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RequestSettingsPanelProps {
+    pub tab_index: usize,
+    pub settings: RequestSettings,
+}
+
+#[function_component(RequestSettingsPanel)]
+pub fn request_settings_panel(props: &RequestSettingsPanelProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let settings = props.settings.clone();
+
+    let update_settings = {
+        let tab_state = tab_state.clone();
+        move |settings: RequestSettings| {
+            tab_state.dispatch(TabAction::UpdateSettings { index, settings });
+        }
+    };
+
+    let on_timeout_change = {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = settings.clone();
+            next.timeout_ms = parse_millis(&input_value(&event));
+            update_settings(next);
+        })
+    };
+
+    let on_redirect_mode_change = {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: Event| {
+            let mut next = settings.clone();
+            next.redirect = match select_value(&event).as_str() {
+                "none" => RedirectPolicy::NoFollow,
+                "limit" => RedirectPolicy::Limit(match settings.redirect {
+                    RedirectPolicy::Limit(limit) => limit,
+                    _ => DEFAULT_REDIRECT_LIMIT,
+                }),
+                _ => RedirectPolicy::Follow,
+            };
+            update_settings(next);
+        })
+    };
+
+    let on_redirect_limit_change = {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = settings.clone();
+            let limit = input_value(&event).trim().parse::<u32>().unwrap_or(0);
+            next.redirect = RedirectPolicy::Limit(limit);
+            update_settings(next);
+        })
+    };
+
+    let on_http_version_change = {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: Event| {
+            let mut next = settings.clone();
+            next.http_version = HttpVersion::from_key(&select_value(&event));
+            update_settings(next);
+        })
+    };
+
+    let on_decode_toggle = |encoding: ContentEncoding| {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: Event| {
+            let mut next = settings.clone();
+            next.decode_disabled.retain(|disabled| *disabled != encoding);
+            if !checkbox_value(&event) {
+                next.decode_disabled.push(encoding);
+            }
+            update_settings(next);
+        })
+    };
+
+    let on_compress_change = {
+        let update_settings = update_settings.clone();
+        let settings = settings.clone();
+        Callback::from(move |event: Event| {
+            let mut next = settings.clone();
+            next.compress_body = ContentEncoding::from_token(&select_value(&event));
+            update_settings(next);
+        })
+    };
+
+    let redirect_mode = match settings.redirect {
+        RedirectPolicy::Follow => "follow",
+        RedirectPolicy::NoFollow => "none",
+        RedirectPolicy::Limit(_) => "limit",
+    };
+
+    html! {
+        <div class="table-wrap">
+            <h2 class="table-title">{ "Request Settings" }</h2>
+            <div class="settings-grid">
+                <label class="settings-label" for="request-timeout">{ "Timeout (ms)" }</label>
+                <input
+                    id="request-timeout"
+                    type="number"
+                    min="0"
+                    placeholder="Server default"
+                    value={settings.timeout_ms.map(|value| value.to_string()).unwrap_or_default()}
+                    oninput={on_timeout_change}
+                />
+                <label class="settings-label" for="request-redirects">{ "Redirects" }</label>
+                <select id="request-redirects" onchange={on_redirect_mode_change}>
+                    <option value="follow" selected={redirect_mode == "follow"}>{ "Follow" }</option>
+                    <option value="none" selected={redirect_mode == "none"}>{ "Don't follow" }</option>
+                    <option value="limit" selected={redirect_mode == "limit"}>{ "Follow up to…" }</option>
+                </select>
+                {
+                    if let RedirectPolicy::Limit(limit) = settings.redirect {
+                        html! {
+                            <>
+                                <label class="settings-label" for="request-redirect-limit">{ "Max redirects" }</label>
+                                <input
+                                    id="request-redirect-limit"
+                                    type="number"
+                                    min="0"
+                                    value={limit.to_string()}
+                                    oninput={on_redirect_limit_change}
+                                />
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <label class="settings-label" for="request-http-version">{ "HTTP version" }</label>
+                <select id="request-http-version" onchange={on_http_version_change}>
+                    <option value="" selected={settings.http_version.is_none()}>{ "Server default" }</option>
+                    { for HttpVersion::all().iter().map(|version| html! {
+                        <option value={version.key()} selected={settings.http_version == Some(*version)}>
+                            { version.label() }
+                        </option>
+                    }) }
+                </select>
+                <span class="settings-label">{ "Decode responses" }</span>
+                <div class="settings-checks">
+                    { for ContentEncoding::all().into_iter().map(|encoding| html! {
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={!settings.decode_disabled.contains(&encoding)}
+                                onchange={on_decode_toggle(encoding)}
+                            />
+                            { format!(" {}", encoding.label()) }
+                        </label>
+                    }) }
+                </div>
+                <label class="settings-label" for="request-compress">{ "Compress body" }</label>
+                <select id="request-compress" onchange={on_compress_change}>
+                    <option value="" selected={settings.compress_body.is_none()}>{ "No" }</option>
+                    { for ContentEncoding::all().iter().map(|encoding| html! {
+                        <option value={encoding.token()} selected={settings.compress_body == Some(*encoding)}>
+                            { encoding.label() }
+                        </option>
+                    }) }
+                </select>
+            </div>
+        </div>
+    }
+}
+
+const DEFAULT_REDIRECT_LIMIT: u32 = 10;
+
+pub(crate) fn parse_millis(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok().filter(|value| *value > 0)
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn checkbox_value(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.checked())
+        .unwrap_or(false)
+}
+
+fn select_value(event: &Event) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+        .map(|select| select.value())
+        .unwrap_or_default()
+}
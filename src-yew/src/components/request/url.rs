@@ -1,8 +1,6 @@
-use std::collections::HashMap;
-
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use js_sys::{Date, Object, Reflect};
+use js_sys::{Array, Date, Math, Object, Reflect};
 use serde::Serialize;
 use serde_json::Value;
 use wasm_bindgen::JsValue;
@@ -14,12 +12,18 @@ use yew::prelude::*;
 use url::Url;
 
 use crate::components::json_highlight::parse_json_value;
+use crate::environment::Variables;
+use crate::graphql::{self, GraphQlSchema};
+use crate::history::HistoryRecord;
 use crate::state::{
-    ApiKeyLocation, Header, MethodEnum, Param, RequestDebugInfo, Response, ServerAuth,
-    ServerEntry, TabAction, TabContent, TabState, TreeAction, TreeState,
+    ApiKeyLocation, BodyMode, ContentEncoding, FormField, Header, HeaderList, HistoryAction,
+    HistoryState, HttpVersion, MethodEnum, Param, RedirectPolicy, RequestDebugInfo, Response,
+    RetryPolicy, ServerAuth, ServerEntry, ServerProxy, TabAction, TabContent, TabState,
+    TlsSettings, TreeAction, TreeState,
 };
 use crate::tauri_api;
-use crate::utils::{params_from_url, path_params_from_url};
+use crate::utils::{copy_variable, params_from_url, path_params_from_url};
+use crate::vault::secret_values;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct RequestUrlProps {
@@ -31,20 +35,24 @@ pub struct RequestUrlProps {
 pub fn request_url(props: &RequestUrlProps) -> Html {
     let tab_state = use_context::<UseReducerHandle<TabState>>();
     let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let history_state = use_context::<UseReducerHandle<HistoryState>>();
     let Some(tab_state) = tab_state else {
         return html! {};
     };
     let Some(tree_state) = tree_state else {
         return html! {};
     };
-    let is_sending = use_state(|| false);
     let index = props.tab_index;
     let content = props.content.clone();
-    let selected_server = tree_state
-        .selected_server
-        .and_then(|index| tree_state.servers.get(index))
-        .cloned();
+    let pending_request = tab_state
+        .tabs
+        .get(index)
+        .and_then(|tab| tab.pending_request.clone());
+    let is_sending = pending_request.is_some();
     let selected_server_index = tree_state.selected_server;
+    let variables = Variables::active(&tree_state);
+    let unresolved = variables.unresolved_in(&content);
+    let url_unresolved = variables.has_unresolved(&content.url);
 
     let on_method_change = {
         let tab_state = tab_state.clone();
@@ -100,68 +108,41 @@ pub fn request_url(props: &RequestUrlProps) -> Html {
     let on_submit = {
         let tab_state = tab_state.clone();
         let tree_state = tree_state.clone();
-        let selected_server = selected_server.clone();
-        let selected_server_index = selected_server_index;
-        let is_sending = is_sending.clone();
+        let history_state = history_state.clone();
         Callback::from(move |event: SubmitEvent| {
             event.prevent_default();
-            if *is_sending {
+            if is_sending {
                 return;
             }
-            is_sending.set(true);
-            let tab_state = tab_state.clone();
-            let tree_state = tree_state.clone();
-            let selected_server = selected_server.clone();
-            let selected_server_index = selected_server_index;
-            let is_sending = is_sending.clone();
-            spawn_local(async move {
-                let Some(tab) = tab_state.tabs.get(index).cloned() else {
-                    is_sending.set(false);
-                    return;
-                };
-                let started_at = Date::now();
-                let response =
-                    match perform_request(&tab.content, selected_server.as_ref()).await {
-                        Ok(mut response) => {
-                            let request_info =
-                                build_request_debug(&tab.content, selected_server.as_ref()).ok();
-                            response.request = request_info;
-                            response.duration_ms = Some(duration_ms(started_at));
-                            response
-                        }
-                        Err(error) => {
-                            let request_info =
-                                build_request_debug(&tab.content, selected_server.as_ref()).ok();
-                            Response {
-                                data: error,
-                                ok: false,
-                                status: 0,
-                                duration_ms: Some(duration_ms(started_at)),
-                                request: request_info,
-                                ..Response::default()
-                            }
-                        }
-                    };
-                let response = format_response_data(response);
-                if let Some(next_auth) = extract_bearer_auth_update(
-                    selected_server.as_ref(),
-                    &response.data,
-                ) {
-                    if let Some(server_index) = selected_server_index {
-                        tree_state.dispatch(TreeAction::UpdateServerAuth {
-                            index: server_index,
-                            auth: next_auth,
-                        });
-                    }
-                }
+            let Some(tab) = tab_state.tabs.get(index).cloned() else {
+                return;
+            };
+            send_tab_request(
+                &tab_state,
+                &tree_state,
+                history_state.as_ref(),
+                index,
+                tab.label,
+                tab.content,
+                selected_server_index,
+            );
+        })
+    };
 
-                tab_state.dispatch(TabAction::SetResponse { index, response });
-                is_sending.set(false);
+    let on_cancel = {
+        let pending_request = pending_request.clone();
+        Callback::from(move |_| {
+            let Some(request_id) = pending_request.clone() else {
+                return;
+            };
+            spawn_local(async move {
+                let _ = cancel_request(&request_id).await;
             });
         })
     };
 
     html! {
+        <>
         <form class="form-row" onsubmit={on_submit}>
             <div class="request-url">
                 <div class="select-wrap">
@@ -178,47 +159,197 @@ pub fn request_url(props: &RequestUrlProps) -> Html {
                     <span class="select-chevron"></span>
                 </div>
                 <input
-                    class="url-input"
+                    class={classes!("url-input", url_unresolved.then_some("unresolved"))}
                     placeholder="/path"
                     value={content.url}
                     oninput={on_url_change}
                 />
             </div>
-            <button
-                type="submit"
-                class="button"
-                disabled={*is_sending}
-                aria-busy={if *is_sending { Some(String::from("true")) } else { None }}
-            >
-                { if *is_sending { "Sending..." } else { "Send" } }
-            </button>
+            {
+                if is_sending {
+                    html! {
+                        <button type="button" class="button danger" aria-busy="true" onclick={on_cancel}>
+                            { "Cancel" }
+                        </button>
+                    }
+                } else {
+                    html! { <button type="submit" class="button">{ "Send" }</button> }
+                }
+            }
         </form>
+        if !unresolved.is_empty() {
+            <div class="unresolved-notice">
+                { "Unresolved variables: " }
+                { unresolved.iter().map(|name| format!("{{{{{name}}}}}")).collect::<Vec<_>>().join(", ") }
+            </div>
+        }
+        </>
     }
 }
 
+/// Sends `content` for the tab at `index` through the server at `server_index`, then
+/// stores the response on the tab and records the send in the history.
+pub(crate) fn send_tab_request(
+    tab_state: &UseReducerHandle<TabState>,
+    tree_state: &UseReducerHandle<TreeState>,
+    history_state: Option<&UseReducerHandle<HistoryState>>,
+    index: usize,
+    label: String,
+    content: TabContent,
+    server_index: Option<usize>,
+) {
+    let server = server_index
+        .and_then(|server_index| tree_state.servers.get(server_index))
+        .cloned();
+    // Placeholders are filled in at send time, so the tab and the history keep them.
+    let variables = Variables::active(tree_state);
+    let sent = variables.resolve(&content);
+    let sent_server = server.as_ref().map(|server| variables.resolve_server(server));
+    let unlocked = variables.check_vault(&content, server.as_ref());
+    let environment = tree_state
+        .active_environment
+        .and_then(|index| tree_state.environments.get(index));
+    let secrets = secret_values(sent_server.as_ref(), environment);
+    let request_id = new_request_id();
+    tab_state.dispatch(TabAction::SetPendingRequest {
+        index,
+        request_id: Some(request_id.clone()),
+    });
+    let tab_state = tab_state.clone();
+    let tree_state = tree_state.clone();
+    let history_state = history_state.cloned();
+    spawn_local(async move {
+        let started_at = Date::now();
+        let result = match unlocked {
+            Ok(()) => perform_request(&sent, sent_server.as_ref(), &request_id).await,
+            Err(error) => Err(error),
+        };
+        let response = match result {
+            Ok(mut response) => {
+                let request_info = build_request_debug(&sent, sent_server.as_ref()).ok();
+                response.request = request_info;
+                response.duration_ms = Some(duration_ms(started_at));
+                response
+            }
+            Err(error) => {
+                let request_info = build_request_debug(&sent, sent_server.as_ref()).ok();
+                Response {
+                    data: error,
+                    ok: false,
+                    status: 0,
+                    duration_ms: Some(duration_ms(started_at)),
+                    request: request_info,
+                    ..Response::default()
+                }
+            }
+        };
+        let response = format_response_data(response);
+        if let Some(next_auth) = extract_bearer_auth_update(server.as_ref(), &response.data) {
+            if let Some(server_index) = server_index {
+                tree_state.dispatch(TreeAction::UpdateServerAuth {
+                    index: server_index,
+                    auth: next_auth,
+                });
+            }
+        }
+
+        let record = HistoryRecord::new(
+            new_request_id(),
+            &label,
+            &content,
+            server.as_ref(),
+            &response,
+            &secrets,
+        );
+        tab_state.dispatch(TabAction::SetResponse {
+            index,
+            response: Box::new(response),
+        });
+        tab_state.dispatch(TabAction::SetPendingRequest {
+            index,
+            request_id: None,
+        });
+        // A failed write only costs the history entry, not the response.
+        if tauri_api::append_history(&record).await.is_ok() {
+            if let Some(history_state) = history_state {
+                history_state.dispatch(HistoryAction::Push(record.summary().clone()));
+            }
+        }
+    });
+}
+
 async fn perform_request(
     content: &TabContent,
     server: Option<&ServerEntry>,
+    request_id: &str,
 ) -> Result<Response, String> {
+    if content.body_mode == BodyMode::GraphQl {
+        let schema = server.and_then(|server| server.graphql_schema.as_deref());
+        let errors = graphql::validate(&content.graphql_query, schema);
+        if !errors.is_empty() {
+            return Err(format!("Query GraphQL inválida:\n{}", errors.join("\n")));
+        }
+    }
+
+    let sends_body = should_send_body(content.method);
     let url = build_request_url(content, server)?;
-    let mut headers = build_headers(&content.headers, content.method, &content.body);
+    let url = if content.body_mode == BodyMode::GraphQl && !sends_body {
+        graphql::query_pairs(&content.graphql_query, &content.graphql_variables)?
+            .iter()
+            .try_fold(url, |url, (name, value)| append_query_param(&url, name, value))?
+    } else {
+        url
+    };
+    let mut headers = build_headers(&content.headers, content.method, raw_body(content));
     let url = if let Some(server) = server {
         apply_auth(&url, &mut headers, &server.auth)?
     } else {
         url
     };
+    let body = match content.body_mode {
+        _ if !sends_body => None,
+        BodyMode::Raw => Some(content.body.clone()),
+        BodyMode::GraphQl => Some(graphql::request_body(
+            &content.graphql_query,
+            &content.graphql_variables,
+        )?),
+        BodyMode::UrlEncoded | BodyMode::Multipart | BodyMode::Binary => None,
+    };
     let request = TauriRequest {
         method: content.method.as_str().to_string(),
         url,
         headers,
-        body: if should_send_body(content.method) {
-            Some(content.body.clone())
-        } else {
-            None
-        },
+        body,
+        body_mode: if sends_body { encoded_body(content) } else { None },
+        request_id: request_id.to_string(),
+        timeout_ms: content
+            .settings
+            .timeout_ms
+            .or_else(|| server.and_then(|server| server.settings.timeout_ms)),
+        server_url: server.map(|server| server.url.clone()),
+        unix_socket: server.and_then(|server| server.settings.unix_socket.clone()),
+        retry: server
+            .map(|server| server.settings.retry.clone())
+            .filter(RetryPolicy::is_enabled),
+        redirect: content.settings.redirect,
+        http_version: content
+            .settings
+            .http_version
+            .or_else(|| server.map(|server| server.settings.http_version))
+            .unwrap_or_default(),
+        decode_disabled: content.settings.decode_disabled.clone(),
+        compress_body: content.settings.compress_body,
+        tls: server.map(|server| server.settings.tls.clone()),
+        proxy: server
+            .map(|server| server.settings.proxy.clone())
+            .unwrap_or_default(),
     };
 
-    let payload = build_request_payload(&request)
+    send_tauri_request(&request).await
+}
+
+async fn send_tauri_request(request: &TauriRequest) -> Result<Response, String> {
+    let payload = build_request_payload(request)
         .map_err(|err| format_request_error(tauri_api::js_error_to_string(&err)))?;
     let value = tauri_api::invoke("send_request", payload)
         .await
@@ -228,30 +359,214 @@ async fn perform_request(
     Ok(response)
 }
 
+/// Runs the introspection query against the tab's URL with the server's auth and
+/// connection settings.
+pub(crate) async fn introspect_schema(
+    content: &TabContent,
+    server: Option<&ServerEntry>,
+) -> Result<GraphQlSchema, String> {
+    let body = graphql::request_body(graphql::INTROSPECTION_QUERY, "")?;
+    let url = build_request_url(content, server)?;
+    let mut headers = build_headers(&content.headers, MethodEnum::Post, &body);
+    set_header(&mut headers, "Content-Type", "application/json".to_string());
+    let url = if let Some(server) = server {
+        apply_auth(&url, &mut headers, &server.auth)?
+    } else {
+        url
+    };
+    let request = TauriRequest {
+        method: MethodEnum::Post.as_str().to_string(),
+        url,
+        headers,
+        body: Some(body),
+        body_mode: None,
+        request_id: new_request_id(),
+        timeout_ms: content
+            .settings
+            .timeout_ms
+            .or_else(|| server.and_then(|server| server.settings.timeout_ms)),
+        server_url: server.map(|server| server.url.clone()),
+        unix_socket: server.and_then(|server| server.settings.unix_socket.clone()),
+        retry: server
+            .map(|server| server.settings.retry.clone())
+            .filter(RetryPolicy::is_enabled),
+        redirect: content.settings.redirect,
+        http_version: content
+            .settings
+            .http_version
+            .or_else(|| server.map(|server| server.settings.http_version))
+            .unwrap_or_default(),
+        decode_disabled: content.settings.decode_disabled.clone(),
+        compress_body: None,
+        tls: server.map(|server| server.settings.tls.clone()),
+        proxy: server
+            .map(|server| server.settings.proxy.clone())
+            .unwrap_or_default(),
+    };
+    let response = send_tauri_request(&request).await?;
+    if !response.ok {
+        return Err(format!("Introspection falhou: HTTP {}", response.status));
+    }
+    GraphQlSchema::from_introspection(&response.data)
+}
+
+pub(crate) async fn cancel_request(request_id: &str) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("requestId"),
+        &JsValue::from_str(request_id),
+    )?;
+    let _ = tauri_api::invoke("cancel_request", payload.into()).await?;
+    Ok(())
+}
+
+pub(crate) fn new_request_id() -> String {
+    let nonce = (Math::random() * 1_000_000_000.0) as u64;
+    format!("{}-{nonce}", Date::now() as u64)
+}
+
 pub(crate) fn build_request_debug(
     content: &TabContent,
     server: Option<&ServerEntry>,
 ) -> Result<RequestDebugInfo, String> {
     let url = build_request_url(content, server)?;
-    let mut headers = build_headers(&content.headers, content.method, &content.body);
+    let mut headers = build_headers(&content.headers, content.method, raw_body(content));
     let url = if let Some(server) = server {
         apply_auth(&url, &mut headers, &server.auth)?
     } else {
         url
     };
+    if let Some(media_type) = content.body_mode.media_type() {
+        set_header(&mut headers, "Content-Type", media_type.to_string());
+    }
+    // Mirrors the coding headers the backend adds.
+    if let Some(encoding) = content.settings.compress_body {
+        set_header(&mut headers, "Content-Encoding", encoding.token().to_string());
+    }
+    let accept_encoding = ContentEncoding::all()
+        .into_iter()
+        .filter(|encoding| !content.settings.decode_disabled.contains(encoding))
+        .map(ContentEncoding::token)
+        .collect::<Vec<_>>();
+    if !accept_encoding.is_empty()
+        && !headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case("accept-encoding"))
+    {
+        headers.push(("Accept-Encoding".to_string(), accept_encoding.join(", ")));
+    }
+
+    let body = match content.body_mode {
+        BodyMode::Raw => content.body.clone(),
+        BodyMode::UrlEncoded | BodyMode::Multipart => enabled_form_fields(content)
+            .map(|field| {
+                if field.file {
+                    format!("{}: @{}", field.key.trim(), field.value)
+                } else {
+                    format!("{}: {}", field.key.trim(), field.value)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        BodyMode::Binary => format!("@{}", content.body_file.trim()),
+        BodyMode::GraphQl => graphql::request_body(&content.graphql_query, &content.graphql_variables)
+            .ok()
+            .and_then(|body| parse_json_value(&body))
+            .and_then(|body| serde_json::to_string_pretty(&body).ok())
+            .unwrap_or_else(|| content.graphql_query.clone()),
+    };
 
     Ok(RequestDebugInfo {
         method: content.method.as_str().to_string(),
         url,
         headers,
-        body: if content.body.trim().is_empty() {
-            None
-        } else {
-            Some(content.body.clone())
-        },
+        body: if body.trim().is_empty() { None } else { Some(body) },
     })
 }
 
+/// Handshake URL and headers of a WebSocket tab. Paths resolve against the selected
+/// server with its scheme switched to ws/wss, and the server auth applies as for HTTP.
+pub(crate) fn build_socket_target(
+    content: &TabContent,
+    server: Option<&ServerEntry>,
+) -> Result<(String, HeaderList), String> {
+    let mut headers = HeaderList::new();
+    for header in &content.headers {
+        if header.enable && !header.key.trim().is_empty() {
+            headers.push((header.key.trim().to_string(), header.value.clone()));
+        }
+    }
+
+    let url = match Url::parse(content.url.trim()) {
+        Ok(url) if matches!(url.scheme(), "ws" | "wss") => url,
+        _ if server.is_some_and(|server| {
+            server.url.starts_with("unix:") || server.settings.unix_socket.is_some()
+        }) =>
+        {
+            return Err("WebSocket não suporta server em unix socket.".to_string());
+        }
+        _ => {
+            let mut url = Url::parse(&build_request_url(content, server)?)
+                .map_err(|_| "URL inválida.".to_string())?;
+            let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+            url.set_scheme(scheme)
+                .map_err(|_| "URL inválida.".to_string())?;
+            url
+        }
+    };
+    let url = match server {
+        Some(server) => apply_auth(url.as_str(), &mut headers, &server.auth)?,
+        None => url.to_string(),
+    };
+    Ok((url, headers))
+}
+
+fn raw_body(content: &TabContent) -> &str {
+    match content.body_mode {
+        BodyMode::Raw => &content.body,
+        BodyMode::GraphQl => &content.graphql_query,
+        BodyMode::UrlEncoded | BodyMode::Multipart | BodyMode::Binary => "",
+    }
+}
+
+fn enabled_form_fields(content: &TabContent) -> impl Iterator<Item = &FormField> {
+    let allow_files = content.body_mode == BodyMode::Multipart;
+    content
+        .form_fields
+        .iter()
+        .filter(|field| field.enable && !field.key.trim().is_empty())
+        .filter(move |field| allow_files || !field.file)
+}
+
+/// Form or file body payload for `send_request`; the backend encodes it and sets
+/// Content-Type.
+fn encoded_body(content: &TabContent) -> Option<Value> {
+    match content.body_mode {
+        BodyMode::Raw | BodyMode::GraphQl => return None,
+        BodyMode::Binary => {
+            return Some(serde_json::json!({
+                "mode": content.body_mode.key(),
+                "path": content.body_file.trim(),
+            }));
+        }
+        BodyMode::UrlEncoded | BodyMode::Multipart => {}
+    }
+    let fields = enabled_form_fields(content)
+        .map(|field| {
+            serde_json::json!({
+                "name": field.key.trim(),
+                "value": field.value,
+                "file": field.file,
+            })
+        })
+        .collect::<Vec<_>>();
+    Some(serde_json::json!({
+        "mode": content.body_mode.key(),
+        "fields": fields,
+    }))
+}
+
 pub(crate) fn authorization_header_value(auth: &ServerAuth) -> Option<String> {
     match auth {
         ServerAuth::HttpBasic { username, password } => {
@@ -292,8 +607,9 @@ pub(crate) fn authorization_header_value(auth: &ServerAuth) -> Option<String> {
     }
 }
 
-fn build_headers(headers: &[Header], method: MethodEnum, body: &str) -> HashMap<String, String> {
-    let mut map = HashMap::new();
+/// Enabled header rows in table order. Repeated names are kept as separate headers.
+fn build_headers(headers: &[Header], method: MethodEnum, body: &str) -> HeaderList {
+    let mut list = HeaderList::new();
     let mut has_accept = false;
     let mut has_content_type = false;
 
@@ -311,17 +627,17 @@ fn build_headers(headers: &[Header], method: MethodEnum, body: &str) -> HashMap<
         if key.eq_ignore_ascii_case("content-type") {
             has_content_type = true;
         }
-        map.insert(key.to_string(), header.value.clone());
+        list.push((key.to_string(), header.value.clone()));
     }
 
     if !has_accept {
-        map.insert("Accept".to_string(), "*/*".to_string());
+        list.push(("Accept".to_string(), "*/*".to_string()));
     }
     if !has_content_type && should_send_body(method) && !body.trim().is_empty() {
-        map.insert("Content-Type".to_string(), "application/json".to_string());
+        list.push(("Content-Type".to_string(), "application/json".to_string()));
     }
 
-    map
+    list
 }
 
 fn should_send_body(method: MethodEnum) -> bool {
@@ -348,14 +664,27 @@ fn select_value(event: &Event) -> String {
 struct TauriRequest {
     method: String,
     url: String,
-    headers: HashMap<String, String>,
+    headers: HeaderList,
     body: Option<String>,
+    body_mode: Option<Value>,
+    request_id: String,
+    timeout_ms: Option<u64>,
+    server_url: Option<String>,
+    unix_socket: Option<String>,
+    retry: Option<RetryPolicy>,
+    #[serde(skip)]
+    redirect: RedirectPolicy,
+    http_version: HttpVersion,
+    decode_disabled: Vec<ContentEncoding>,
+    compress_body: Option<ContentEncoding>,
+    tls: Option<TlsSettings>,
+    proxy: ServerProxy,
 }
 
 fn build_request_payload(request: &TauriRequest) -> Result<JsValue, JsValue> {
     let payload = Object::new();
     let request_obj = Object::new();
-    let headers_obj = Object::new();
+    let headers_array = Array::new();
 
     Reflect::set(
         &request_obj,
@@ -369,16 +698,15 @@ fn build_request_payload(request: &TauriRequest) -> Result<JsValue, JsValue> {
     )?;
 
     for (key, value) in &request.headers {
-        Reflect::set(
-            &headers_obj,
+        headers_array.push(&Array::of2(
             &JsValue::from_str(key),
             &JsValue::from_str(value),
-        )?;
+        ));
     }
     Reflect::set(
         &request_obj,
         &JsValue::from_str("headers"),
-        &headers_obj,
+        &headers_array,
     )?;
 
     let body_value = request
@@ -391,6 +719,103 @@ fn build_request_payload(request: &TauriRequest) -> Result<JsValue, JsValue> {
         &JsValue::from_str("body"),
         &body_value,
     )?;
+    Reflect::set(
+        &request_obj,
+        &JsValue::from_str("requestId"),
+        &JsValue::from_str(&request.request_id),
+    )?;
+    if let Some(timeout_ms) = request.timeout_ms {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("timeoutMs"),
+            &JsValue::from_f64(timeout_ms as f64),
+        )?;
+    }
+    if let Some(server_url) = request.server_url.as_ref() {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("serverUrl"),
+            &JsValue::from_str(server_url),
+        )?;
+    }
+    if let Some(unix_socket) = request.unix_socket.as_ref() {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("unixSocket"),
+            &JsValue::from_str(unix_socket),
+        )?;
+    }
+    if let Some(retry) = request.retry.as_ref() {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("retry"),
+            &serde_wasm_bindgen::to_value(retry)?,
+        )?;
+    }
+    if let Some(tls) = request.tls.as_ref().filter(|tls| !tls.is_default()) {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("tls"),
+            &serde_wasm_bindgen::to_value(tls)?,
+        )?;
+    }
+    if let Some(body_mode) = request.body_mode.as_ref() {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("bodyMode"),
+            &body_mode.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?,
+        )?;
+    }
+    if request.http_version != HttpVersion::Auto {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("httpVersion"),
+            &JsValue::from_str(request.http_version.key()),
+        )?;
+    }
+    if !request.decode_disabled.is_empty() {
+        let disabled = request
+            .decode_disabled
+            .iter()
+            .map(|encoding| JsValue::from_str(encoding.token()))
+            .collect::<Array>();
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("decodeDisabled"),
+            &disabled,
+        )?;
+    }
+    if let Some(encoding) = request.compress_body {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("compressBody"),
+            &JsValue::from_str(encoding.token()),
+        )?;
+    }
+    if request.proxy != ServerProxy::Inherit {
+        Reflect::set(
+            &request_obj,
+            &JsValue::from_str("proxy"),
+            &serde_wasm_bindgen::to_value(&request.proxy)?,
+        )?;
+    }
+    match request.redirect {
+        RedirectPolicy::Follow => {}
+        RedirectPolicy::NoFollow => {
+            Reflect::set(
+                &request_obj,
+                &JsValue::from_str("followRedirects"),
+                &JsValue::FALSE,
+            )?;
+        }
+        RedirectPolicy::Limit(limit) => {
+            Reflect::set(
+                &request_obj,
+                &JsValue::from_str("maxRedirects"),
+                &JsValue::from_f64(limit as f64),
+            )?;
+        }
+    }
 
     Reflect::set(
         &payload,
@@ -442,7 +867,15 @@ fn format_response_data(mut response: Response) -> Response {
 }
 
 fn build_request_url(content: &TabContent, server: Option<&ServerEntry>) -> Result<String, String> {
-    let path = normalize_request_path(&content.url);
+    // A URL that resolved to an absolute one, e.g. from `{{baseUrl}}/users`, is sent
+    // as is rather than appended to the server.
+    let url = content.url.trim();
+    let absolute = is_http_url(url);
+    let path = if absolute {
+        url.to_string()
+    } else {
+        normalize_request_path(url)
+    };
     let path_with_values = apply_path_params(&path, &content.path_params);
     if path.is_empty() {
         return Err("Path vazio.".to_string());
@@ -450,10 +883,8 @@ fn build_request_url(content: &TabContent, server: Option<&ServerEntry>) -> Resu
 
     let path_with_query = apply_params(&path_with_values, &content.params);
 
-    if let Ok(url) = Url::parse(&path_with_query) {
-        if matches!(url.scheme(), "http" | "https") {
-            return Ok(path_with_query);
-        }
+    if absolute {
+        return Ok(path_with_query);
     }
 
     let Some(server) = server else {
@@ -468,11 +899,19 @@ fn build_request_url(content: &TabContent, server: Option<&ServerEntry>) -> Resu
     Ok(format!("{base}{path}"))
 }
 
+fn is_http_url(value: &str) -> bool {
+    Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
 fn normalize_request_path(value: &str) -> String {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return String::new();
     }
+    // A leading variable may hold the scheme and host, so it is not made a path.
+    if trimmed.starts_with("{{") {
+        return trimmed.to_string();
+    }
 
     if let Ok(url) = Url::parse(trimmed) {
         if matches!(url.scheme(), "http" | "https") {
@@ -541,6 +980,9 @@ fn apply_path_params(base: &str, params: &[Param]) -> String {
     let mut result = String::new();
     let mut chars = base.chars().peekable();
     while let Some(ch) = chars.next() {
+        if ch == '{' && copy_variable(&mut chars, &mut result) {
+            continue;
+        }
         if ch == '{' {
             let mut key = String::new();
             while let Some(next) = chars.next() {
@@ -566,7 +1008,7 @@ fn apply_path_params(base: &str, params: &[Param]) -> String {
 
 fn apply_auth(
     url: &str,
-    headers: &mut HashMap<String, String>,
+    headers: &mut HeaderList,
     auth: &ServerAuth,
 ) -> Result<String, String> {
     match auth {
@@ -707,31 +1149,71 @@ fn append_query_param(url: &str, name: &str, value: &str) -> Result<String, Stri
     Ok(parsed.to_string())
 }
 
-fn set_header(headers: &mut HashMap<String, String>, key: &str, value: String) {
-    if let Some(existing) = headers
-        .keys()
-        .find(|existing| existing.eq_ignore_ascii_case(key))
-        .cloned()
-    {
-        headers.insert(existing, value);
-    } else {
-        headers.insert(key.to_string(), value);
+/// Replaces every header named `key` with a single one, kept at the position of the first.
+fn set_header(headers: &mut HeaderList, key: &str, value: String) {
+    let mut found = false;
+    headers.retain_mut(|(existing, current)| {
+        if !existing.eq_ignore_ascii_case(key) {
+            return true;
+        }
+        if found {
+            return false;
+        }
+        found = true;
+        *current = value.clone();
+        true
+    });
+    if !found {
+        headers.push((key.to_string(), value));
     }
 }
 
-fn append_cookie(headers: &mut HashMap<String, String>, name: &str, value: &str) {
+fn append_cookie(headers: &mut HeaderList, name: &str, value: &str) {
     let pair = format!("{name}={value}");
-    if let Some(existing_key) = headers
-        .keys()
-        .find(|existing| existing.eq_ignore_ascii_case("cookie"))
-        .cloned()
+    match headers
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case("cookie"))
     {
-        let next = match headers.get(&existing_key) {
-            Some(current) if !current.trim().is_empty() => format!("{current}; {pair}"),
-            _ => pair,
+        Some((_, current)) if !current.trim().is_empty() => {
+            *current = format!("{current}; {pair}");
+        }
+        Some((_, current)) => *current = pair,
+        None => headers.push(("Cookie".to_string(), pair)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_params_leave_environment_variables_alone() {
+        let params = [Param {
+            enable: true,
+            key: "y".to_string(),
+            value: "7".to_string(),
+        }];
+        assert_eq!(apply_path_params("/a/{{x}}/{y}", &params), "/a/{{x}}/7");
+    }
+
+    #[test]
+    fn leading_variable_is_not_made_a_path() {
+        assert_eq!(
+            normalize_request_path("{{baseUrl}}/users"),
+            "{{baseUrl}}/users"
+        );
+    }
+
+    #[test]
+    fn resolved_absolute_url_is_not_appended_to_the_server() {
+        let content = TabContent {
+            url: "https://api.example.com/users".to_string(),
+            ..TabContent::default()
         };
-        headers.insert(existing_key, next);
-    } else {
-        headers.insert("Cookie".to_string(), pair);
+        let server = ServerEntry::new("https://srv.example.com".to_string());
+        assert_eq!(
+            build_request_url(&content, Some(&server)).unwrap(),
+            "https://api.example.com/users"
+        );
     }
 }
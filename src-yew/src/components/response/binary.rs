@@ -0,0 +1,170 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::state::{BodyKind, Response};
+use crate::tauri_api;
+
+const HEX_PREVIEW_LIMIT: usize = 64 * 1024;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct BinaryBodyProps {
+    pub response: Response,
+}
+
+#[function_component(BinaryBody)]
+pub fn binary_body(props: &BinaryBodyProps) -> Html {
+    let response = &props.response;
+    let encoded = response.body_base64.clone().unwrap_or_default();
+    let mime = response
+        .content_type
+        .as_deref()
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| default_mime(response.body_kind).to_string());
+    let size = response
+        .size
+        .unwrap_or_else(|| STANDARD.decode(&encoded).map(|bytes| bytes.len() as u64).unwrap_or(0));
+
+    let on_save = {
+        let response = response.clone();
+        Callback::from(move |_| {
+            let response = response.clone();
+            spawn_local(async move {
+                save_response(&response).await;
+            });
+        })
+    };
+
+    let body = match response.body_kind {
+        BodyKind::Image | BodyKind::Pdf if response.body_file.is_some() => html! {
+            <p class="muted">{ "Too large to preview. Save the response to open it." }</p>
+        },
+        BodyKind::Image => html! {
+            <div class="binary-media">
+                <img src={format!("data:{mime};base64,{encoded}")} alt="Response image" />
+            </div>
+        },
+        BodyKind::Pdf => html! {
+            <iframe class="binary-document" src={format!("data:{mime};base64,{encoded}")} title="Response PDF"></iframe>
+        },
+        _ => {
+            let bytes = STANDARD.decode(&encoded).unwrap_or_default();
+            let shown = bytes.len().min(HEX_PREVIEW_LIMIT);
+            let truncated = (shown as u64) < size;
+            let dump = hex_dump(&bytes[..shown]);
+            html! {
+                <pre class="editor response-editor response-code">
+                    <code>{ dump }</code>
+                    {
+                        if truncated {
+                            html! { <span class="muted">{ format!("\n… showing first {} of {}", format_size(shown as u64), format_size(size)) }</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </pre>
+            }
+        }
+    };
+
+    html! {
+        <div class="binary-body">
+            <div class="binary-toolbar">
+                <span class="muted">{ format!("{mime} · {}", format_size(size)) }</span>
+                <button class="button secondary" onclick={on_save}>{ "Save to file" }</button>
+            </div>
+            { body }
+        </div>
+    }
+}
+
+/// Asks where to save the response body and writes all of it there, copying the
+/// backend's temp file when the body was too large to keep in memory.
+pub(crate) async fn save_response(response: &Response) {
+    let path = match tauri_api::dialog_save().await {
+        Ok(Some(path)) => path,
+        Ok(None) => return,
+        Err(err) => {
+            show_alert(&format!(
+                "Falha ao abrir diálogo de salvar: {}",
+                tauri_api::js_error_to_string(&err)
+            ));
+            return;
+        }
+    };
+    let result = match (response.body_file.as_deref(), response.body_base64.as_deref()) {
+        (Some(source), _) => tauri_api::save_response_file(source, &path).await,
+        (None, Some(encoded)) => match STANDARD.decode(encoded) {
+            Ok(bytes) => tauri_api::fs_write_binary(&path, &bytes).await,
+            Err(_) => {
+                show_alert("Invalid response body.");
+                return;
+            }
+        },
+        (None, None) => tauri_api::fs_write_binary(&path, response.data.as_bytes()).await,
+    };
+    if let Err(err) = result {
+        show_alert(&format!(
+            "Falha ao salvar o arquivo: {}",
+            tauri_api::js_error_to_string(&err)
+        ));
+    }
+}
+
+fn default_mime(kind: BodyKind) -> &'static str {
+    match kind {
+        BodyKind::Image => "image/png",
+        BodyKind::Pdf => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 4);
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        text.push_str(&format!("{:08x}  ", line * 16));
+        for index in 0..16 {
+            match chunk.get(index) {
+                Some(byte) => text.push_str(&format!("{byte:02x} ")),
+                None => text.push_str("   "),
+            }
+            if index == 7 {
+                text.push(' ');
+            }
+        }
+        text.push_str(" |");
+        for byte in chunk {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                text.push(*byte as char);
+            } else {
+                text.push('.');
+            }
+        }
+        text.push_str("|\n");
+    }
+    text
+}
+
+fn show_alert(message: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
@@ -12,9 +12,13 @@ use yew::prelude::*;
 
 use crate::components::json_highlight::{highlight_json, parse_json_value};
 use crate::components::request::url::{authorization_header_value, build_request_debug};
+use crate::environment::Variables;
+use crate::components::response::binary::{format_size, save_response, BinaryBody};
+use crate::components::response::event_stream::EventStreamView;
 use crate::state::TabAction;
 use crate::state::{
-    RequestDebugInfo, Response, ServerEntry, TabContent, TabState, TreeState,
+    BodyKind, EventStream, HeaderList, RequestDebugInfo, Response, ResponseTiming, ServerEntry,
+    TabContent, TabState, TreeState,
 };
 use crate::tauri_api;
 
@@ -23,6 +27,8 @@ pub struct ResponseContentProps {
     pub tab_index: usize,
     pub response: Response,
     pub request: TabContent,
+    #[prop_or_default]
+    pub event_stream: Option<EventStream>,
 }
 
 #[function_component(ResponseContent)]
@@ -65,7 +71,10 @@ pub fn response_content(props: &ResponseContentProps) -> Html {
                 }
             }
             response.formatted = true;
-            tab_state.dispatch(TabAction::SetResponse { index, response });
+            tab_state.dispatch(TabAction::SetResponse {
+                index,
+                response: Box::new(response),
+            });
         })
     };
 
@@ -193,13 +202,17 @@ pub fn response_content(props: &ResponseContentProps) -> Html {
         });
     }
 
+    let variables = Variables::active(&tree_state);
     let selected_server = tree_state
         .selected_server
         .and_then(|index| tree_state.servers.get(index))
-        .cloned();
+        .map(|server| variables.resolve_server(server));
 
-    let (debug_request, debug_response, debug_jwt) =
-        format_debug_sections(&request, &response, selected_server.as_ref());
+    let (debug_request, debug_response, debug_jwt) = format_debug_sections(
+        &variables.resolve(&request),
+        &response,
+        selected_server.as_ref(),
+    );
 
     let on_preview = {
         let data_for_preview = data.clone();
@@ -220,6 +233,16 @@ pub fn response_content(props: &ResponseContentProps) -> Html {
         })
     };
 
+    let on_save = {
+        let response = response.clone();
+        Callback::from(move |_| {
+            let response = response.clone();
+            spawn_local(async move {
+                save_response(&response).await;
+            });
+        })
+    };
+
     let on_tools = Callback::from(move |_| {
         spawn_local(async move {
             let payload = Object::new();
@@ -232,7 +255,13 @@ pub fn response_content(props: &ResponseContentProps) -> Html {
             <div class="request-title">
                 <h1>{ "Response" }</h1>
                 {
-                    if let Some(meta) = build_response_meta(status_label.as_ref(), duration_ms) {
+                    if let Some(meta) = build_response_meta(
+                        status_label.as_ref(),
+                        response.version.as_deref(),
+                        size_label(&response).as_ref(),
+                        duration_ms,
+                        response.timing.as_ref(),
+                    ) {
                         html! { <span class="response-meta">{ meta }</span> }
                     } else {
                         html! {}
@@ -242,12 +271,26 @@ pub fn response_content(props: &ResponseContentProps) -> Html {
                     <button class="button secondary" onclick={on_preview}>{ "Preview" }</button>
                     <button class="button secondary" onclick={on_debug}>{ "Debug" }</button>
                     <button class="button secondary" onclick={on_tools}>{ "Tools" }</button>
+                    <button class="button secondary" onclick={on_save}>{ "Save" }</button>
                     <button class="button secondary" onclick={on_format}>{ "Format" }</button>
                 </div>
             </div>
+            if response.body_file.is_some() && response.body_kind == BodyKind::Text {
+                <p class="response-truncated muted">
+                    { format!(
+                        "Showing the first {} of {}. Save the response to get the full body.",
+                        format_size(response.data.len() as u64),
+                        format_size(response.size.unwrap_or_default()),
+                    ) }
+                </p>
+            }
             <div class="response-body">
                 {
-                    if formatted {
+                    if let Some(stream) = props.event_stream.clone() {
+                        html! { <EventStreamView tab_index={index} stream={stream} /> }
+                    } else if response.body_kind != BodyKind::Text {
+                        html! { <BinaryBody response={response.clone()} /> }
+                    } else if formatted {
                         if let Some(highlight) = highlight_json(&response.data) {
                             html! { <pre class="editor response-editor response-code"><code>{ highlight }</code></pre> }
                         } else {
@@ -329,7 +372,7 @@ fn format_debug_sections(
             request_text.push_str(&format!("Method: {}\n", info.method));
             request_text.push_str(&format!("URL: {}\n", info.url));
             request_text.push_str("Headers:\n");
-            request_text.push_str(&format_headers_map(&info.headers));
+            request_text.push_str(&format_header_list(&info.headers));
             request_text.push_str("\nBody:\n");
             if let Some(body) = info.body.as_ref() {
                 if body.trim().is_empty() {
@@ -359,7 +402,62 @@ fn format_debug_sections(
     if let Some(duration) = response.duration_ms {
         response_text.push_str(&format!("Duration: {} ms\n", duration));
     }
+    if let Some(timing) = response.timing.as_ref() {
+        response_text.push_str("Timing:\n");
+        for (label, value) in timing_phases(timing) {
+            response_text.push_str(&format!("  {label}: {value:.1} ms\n"));
+        }
+        if timing.reused_connection {
+            response_text.push_str("  (reused connection)\n");
+        }
+        response_text.push_str(&format!("  Total: {:.1} ms\n", timing.total_ms));
+    }
     response_text.push_str(&format!("URL: {}\n", response.url));
+    if !response.attempts.is_empty() {
+        response_text.push_str(&format!(
+            "\nAttempts ({}):\n",
+            response.attempts.len() + 1
+        ));
+        for (attempt_index, attempt) in response.attempts.iter().enumerate() {
+            let outcome = match (attempt.status, attempt.error.as_ref()) {
+                (Some(status), _) => status.to_string(),
+                (None, Some(error)) => error.clone(),
+                (None, None) => "failed".to_string(),
+            };
+            response_text.push_str(&format!(
+                "  {}. {} {} ({:.1} ms), retried after {} ms\n",
+                attempt_index + 1,
+                outcome,
+                attempt.url,
+                attempt.duration_ms,
+                attempt.delay_ms
+            ));
+        }
+        response_text.push_str(&format!(
+            "  {}. {} {}\n",
+            response.attempts.len() + 1,
+            response.status,
+            response.url
+        ));
+    }
+    if !response.redirects.is_empty() {
+        response_text.push_str(&format!("\nRedirects ({}):\n", response.redirects.len()));
+        for (hop_index, hop) in response.redirects.iter().enumerate() {
+            response_text.push_str(&format!(
+                "  {}. {} {}\n     Location: {}\n",
+                hop_index + 1,
+                hop.status,
+                hop.url,
+                hop.location
+            ));
+            let mut entries: Vec<_> = hop.headers.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            for (key, values) in entries {
+                response_text.push_str(&format!("     {}: {}\n", key, values.join(", ")));
+            }
+        }
+        response_text.push('\n');
+    }
     response_text.push_str("Headers:\n");
     response_text.push_str(&format_headers_map(&response.headers));
     if !response.raw_headers.is_empty() {
@@ -369,7 +467,13 @@ fn format_debug_sections(
         }
     }
     response_text.push_str("\nBody:\n");
-    if response.data.trim().is_empty() {
+    if response.body_kind != BodyKind::Text {
+        response_text.push_str(&format!(
+            "(binary: {}, {})\n",
+            response.content_type.as_deref().unwrap_or("unknown type"),
+            format_size(response.size.unwrap_or_default())
+        ));
+    } else if response.data.trim().is_empty() {
         response_text.push_str("(empty)\n");
     } else {
         response_text.push_str(&response.data);
@@ -381,25 +485,22 @@ fn format_debug_sections(
     (request_text, response_text, jwt_text)
 }
 
-fn find_header_key(headers: &HashMap<String, String>, key: &str) -> Option<String> {
+fn find_header<'a>(headers: &'a HeaderList, key: &str) -> Option<&'a str> {
     headers
-        .keys()
-        .find(|existing| existing.eq_ignore_ascii_case(key))
-        .cloned()
+        .iter()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.as_str())
 }
 
 fn ensure_authorization(info: &mut RequestDebugInfo, value: String) {
-    if let Some(existing_key) = find_header_key(&info.headers, "authorization") {
-        let should_replace = info
-            .headers
-            .get(&existing_key)
-            .map(|current| current.trim().is_empty())
-            .unwrap_or(true);
-        if should_replace {
-            info.headers.insert(existing_key, value);
-        }
-    } else {
-        info.headers.insert("Authorization".to_string(), value);
+    match info
+        .headers
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case("authorization"))
+    {
+        Some((_, current)) if current.trim().is_empty() => *current = value,
+        Some(_) => {}
+        None => info.headers.push(("Authorization".to_string(), value)),
     }
 }
 
@@ -411,7 +512,7 @@ struct DebugDragState {
     start_height: f64,
 }
 
-fn build_jwt_debug(headers: &HashMap<String, String>) -> String {
+fn build_jwt_debug(headers: &HeaderList) -> String {
     let Some(token) = extract_bearer_token(headers) else {
         return "No bearer token found in Authorization header.".to_string();
     };
@@ -435,9 +536,8 @@ fn build_jwt_debug(headers: &HashMap<String, String>) -> String {
     }
 }
 
-fn extract_bearer_token(headers: &HashMap<String, String>) -> Option<String> {
-    let key = find_header_key(headers, "authorization")?;
-    let value = headers.get(&key)?.trim();
+fn extract_bearer_token(headers: &HeaderList) -> Option<String> {
+    let value = find_header(headers, "authorization")?.trim();
     let mut parts = value.split_whitespace();
     let scheme = parts.next()?;
     if !scheme.eq_ignore_ascii_case("bearer") {
@@ -496,6 +596,18 @@ fn format_headers_map(headers: &HashMap<String, String>) -> String {
     text
 }
 
+/// Request headers in the order they are sent, repeated names included.
+fn format_header_list(headers: &HeaderList) -> String {
+    if headers.is_empty() {
+        return "  (none)\n".to_string();
+    }
+    let mut text = String::new();
+    for (key, value) in headers {
+        text.push_str(&format!("  {}: {}\n", key, value));
+    }
+    text
+}
+
 fn status_label(status: u16) -> Option<String> {
     if status == 0 {
         return None;
@@ -508,17 +620,93 @@ fn status_label(status: u16) -> Option<String> {
     Some(label)
 }
 
-fn build_response_meta(status: Option<&String>, duration_ms: Option<u64>) -> Option<String> {
+/// Body size, with the size on the wire when the backend decoded it.
+fn size_label(response: &Response) -> Option<String> {
+    let size = response.size?;
+    Some(match (response.content_encoding.as_deref(), response.wire_size) {
+        (Some(encoding), Some(wire_size)) => format!(
+            "{} ({encoding} {})",
+            format_size(size),
+            format_size(wire_size)
+        ),
+        _ => format_size(size),
+    })
+}
+
+fn build_response_meta(
+    status: Option<&String>,
+    version: Option<&str>,
+    size: Option<&String>,
+    duration_ms: Option<u64>,
+    timing: Option<&ResponseTiming>,
+) -> Option<Html> {
     let mut parts = Vec::new();
     if let Some(status) = status {
         parts.push(status.clone());
     }
-    if let Some(duration) = duration_ms {
-        parts.push(format!("{duration} ms"));
+    if let Some(version) = version {
+        parts.push(version.to_string());
+    }
+    if let Some(size) = size {
+        parts.push(size.clone());
+    }
+    match (timing, duration_ms) {
+        (Some(timing), _) => parts.push(format!("{:.0} ms", timing.total_ms)),
+        (None, Some(duration)) => parts.push(format!("{duration} ms")),
+        (None, None) => {}
     }
     if parts.is_empty() {
-        None
-    } else {
-        Some(parts.join(" · "))
+        return None;
+    }
+    let text = parts.join(" · ");
+    let Some(timing) = timing else {
+        return Some(html! { { text } });
+    };
+
+    let phases = timing_phases(timing);
+    let total: f64 = phases.iter().map(|(_, value)| value).sum();
+    let mut tooltip = phases
+        .iter()
+        .map(|(label, value)| format!("{label}: {value:.1} ms"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if timing.reused_connection {
+        tooltip.push_str("\n(reused connection)");
+    }
+    Some(html! {
+        <>
+            { text }
+            <span class="timing-waterfall" title={tooltip}>
+                { for phases.iter().filter(|(_, value)| *value > 0.0).map(|(label, value)| {
+                    let width = if total > 0.0 { value / total * 100.0 } else { 0.0 };
+                    html! {
+                        <span
+                            class={classes!("timing-segment", format!("timing-{}", label.to_lowercase()))}
+                            style={format!("width: {width:.2}%;")}
+                        ></span>
+                    }
+                }) }
+            </span>
+        </>
+    })
+}
+
+/// Ordered waterfall phases; connection phases are omitted when a pooled connection was reused.
+fn timing_phases(timing: &ResponseTiming) -> Vec<(&'static str, f64)> {
+    let mut phases = Vec::new();
+    if timing.redirect_ms > 0.0 {
+        phases.push(("Redirects", timing.redirect_ms));
+    }
+    if let Some(dns) = timing.dns_ms {
+        phases.push(("DNS", dns));
+    }
+    if let Some(connect) = timing.connect_ms {
+        phases.push(("Connect", connect));
+    }
+    if let Some(tls) = timing.tls_ms {
+        phases.push(("TLS", tls));
     }
+    phases.push(("TTFB", timing.ttfb_ms));
+    phases.push(("Download", timing.download_ms));
+    phases
 }
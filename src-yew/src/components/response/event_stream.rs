@@ -0,0 +1,106 @@
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::request::url::cancel_request;
+use crate::state::{EventStream, TabAction, TabState};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct EventStreamViewProps {
+    pub tab_index: usize,
+    pub stream: EventStream,
+}
+
+/// Live list of server-sent events, newest last.
+#[function_component(EventStreamView)]
+pub fn event_stream_view(props: &EventStreamViewProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let list_ref = use_node_ref();
+
+    {
+        let list_ref = list_ref.clone();
+        use_effect_with(props.stream.events.len(), move |_| {
+            if let Some(list) = list_ref.cast::<web_sys::Element>() {
+                list.set_scroll_top(list.scroll_height());
+            }
+            || ()
+        });
+    }
+
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let stream = &props.stream;
+
+    let on_stop = {
+        let request_id = stream.request_id.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let request_id = request_id.clone();
+            spawn_local(async move {
+                let _ = cancel_request(&request_id).await;
+            });
+        })
+    };
+
+    let on_clear = Callback::from(move |_event: MouseEvent| {
+        tab_state.dispatch(TabAction::ClearStreamEvents(index));
+    });
+
+    let status = match (&stream.error, stream.open) {
+        (Some(error), _) => error.clone(),
+        (None, true) => format!("Streaming · {} events", stream.events.len()),
+        (None, false) => format!("Closed · {} events", stream.events.len()),
+    };
+
+    html! {
+        <div class="event-stream">
+            <div class="event-stream-toolbar">
+                <span class={classes!("event-stream-status", stream.open.then_some("live"), stream.error.is_some().then_some("error"))}>
+                    { status }
+                </span>
+                <div class="request-actions">
+                    if stream.open {
+                        <button class="button danger" onclick={on_stop}>{ "Stop" }</button>
+                    }
+                    <button class="button secondary" onclick={on_clear}>{ "Clear" }</button>
+                </div>
+            </div>
+            <div class="event-stream-list" ref={list_ref}>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "TIME" }</th>
+                            <th>{ "ID" }</th>
+                            <th>{ "EVENT" }</th>
+                            <th>{ "DATA" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for stream.events.iter().map(|event| html! {
+                            <tr>
+                                <td class="event-stream-time">{ format_time(event.received_at) }</td>
+                                <td>{ event.id.clone().unwrap_or_default() }</td>
+                                <td>{ event.event.clone() }</td>
+                                <td><pre class="event-stream-data">{ event.data.clone() }</pre></td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+/// Local wall-clock time as `HH:MM:SS.mmm`.
+pub(crate) fn format_time(millis: f64) -> String {
+    let date = Date::new(&JsValue::from_f64(millis));
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        date.get_hours(),
+        date.get_minutes(),
+        date.get_seconds(),
+        date.get_milliseconds()
+    )
+}
@@ -1 +1,3 @@
+pub mod binary;
 pub mod content;
+pub mod event_stream;
@@ -1,12 +1,17 @@
 use gloo::events::EventListener;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::components::request::content::RequestContent;
 use crate::components::request::title::RequestTitle;
 use crate::components::request::url::RequestUrl;
 use crate::components::response::content::ResponseContent;
-use crate::state::{TabAction, TabState, TreeState};
+use crate::components::websocket::composer::SocketComposer;
+use crate::components::websocket::connection::SocketConnection;
+use crate::components::websocket::message_log::SocketLog;
+use crate::state::{TabAction, TabState, TreeState, WsStatus};
+use crate::tauri_api;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct SectionProps {
@@ -42,6 +47,13 @@ pub fn section(props: &SectionProps) -> Html {
         })
     };
 
+    let on_add_socket = {
+        let tab_state = tab_state.clone();
+        Callback::from(move |_| {
+            tab_state.dispatch(TabAction::AddWebSocketTab);
+        })
+    };
+
     let on_add_server = props.on_add_server.clone();
     let on_add_server_click = Callback::from(move |_event: MouseEvent| {
         on_add_server.emit(());
@@ -55,9 +67,19 @@ pub fn section(props: &SectionProps) -> Html {
         });
         let tab_state_for_close = tab_state.clone();
         let pending_close = pending_close.clone();
-        let is_dirty = tab.dirty;
+        // WebSocket tabs are not saved, so there is nothing to discard.
+        let is_dirty = tab.dirty && tab.socket().is_none();
+        let open_socket = tab
+            .socket()
+            .filter(|session| session.status == WsStatus::Open)
+            .and_then(|session| session.connection_id.clone());
         let on_close = Callback::from(move |event: MouseEvent| {
             event.stop_propagation();
+            if let Some(connection_id) = open_socket.clone() {
+                spawn_local(async move {
+                    let _ = tauri_api::ws_close(&connection_id, Some(1001), "").await;
+                });
+            }
             if is_dirty {
                 pending_close.set(Some(index));
             } else {
@@ -71,12 +93,15 @@ pub fn section(props: &SectionProps) -> Html {
                 title={tab.label.clone()}
             >
                 {
-                    if tab.dirty {
+                    if is_dirty {
                         html! { <span class="tab-icon" aria-hidden="true"></span> }
                     } else {
                         html! {}
                     }
                 }
+                if tab.socket().is_some() {
+                    <span class="tab-kind">{ "WS" }</span>
+                }
                 <span>{ tab.label.clone() }</span>
                 <span class="tab-close" onclick={on_close.clone()}>{ "x" }</span>
             </button>
@@ -154,24 +179,55 @@ pub fn section(props: &SectionProps) -> Html {
             <div class="tab-list">
                 { for triggers }
                 <button class="tab-add" onclick={on_add} disabled={servers_empty}>{ "+" }</button>
+                <button class="tab-add" onclick={on_add_socket} disabled={servers_empty} title="New WebSocket tab">
+                    { "WS" }
+                </button>
             </div>
             {
                 if let Some(tab) = active_tab {
                     let request_style = format!("height: {}px;", *request_height);
-                    html! {
-                        <div class="tab-panel" ref={panel_ref}>
-                            <div class="request-pane" style={request_style}>
-                                <RequestTitle title={tab.label.clone()} on_save={props.on_save.clone()} />
-                                <RequestUrl key={active.to_string()} tab_index={active} content={tab.content.clone()} />
-                                <RequestContent tab_index={active} content={tab.content.clone()} />
+                    if let Some(session) = tab.socket().cloned() {
+                        html! {
+                            <div class="tab-panel" ref={panel_ref}>
+                                <div class="request-pane" style={request_style}>
+                                    <div class="request-title">
+                                        <h1>{ tab.label.clone() }</h1>
+                                    </div>
+                                    <SocketConnection
+                                        key={active.to_string()}
+                                        tab_index={active}
+                                        content={tab.content.clone()}
+                                        session={session.clone()}
+                                    />
+                                    <SocketComposer
+                                        tab_index={active}
+                                        content={tab.content.clone()}
+                                        session={session.clone()}
+                                    />
+                                </div>
+                                <div class="resize-handle" onmousedown={on_resize_start}></div>
+                                <SocketLog tab_index={active} session={session} />
                             </div>
-                            <div class="resize-handle" onmousedown={on_resize_start}></div>
-                            <ResponseContent
-                                tab_index={active}
-                                response={tab.content.response.clone()}
-                                request={tab.content.clone()}
-                            />
-                        </div>
+                        }
+                    } else {
+                        html! {
+                            <div class="tab-panel" ref={panel_ref}>
+                                // Edits here are recorded by the tab reducer, so Edit > Undo
+                                // goes to the app history instead of the field.
+                                <div class="request-pane" style={request_style} data-undo="app">
+                                    <RequestTitle title={tab.label.clone()} on_save={props.on_save.clone()} />
+                                    <RequestUrl key={active.to_string()} tab_index={active} content={tab.content.clone()} />
+                                    <RequestContent tab_index={active} content={tab.content.clone()} />
+                                </div>
+                                <div class="resize-handle" onmousedown={on_resize_start}></div>
+                                <ResponseContent
+                                    tab_index={active}
+                                    response={tab.content.response.clone()}
+                                    request={tab.content.clone()}
+                                    event_stream={tab.event_stream.clone()}
+                                />
+                            </div>
+                        }
                     }
                 } else {
                     html! {
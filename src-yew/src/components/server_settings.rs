@@ -0,0 +1,446 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::proxy_settings::ProxyFields;
+use crate::components::request::settings::parse_millis;
+use crate::state::{
+    HttpVersion, ProxyConfig, RetryPolicy, ServerProxy, ServerSettings, TlsSettings, TreeAction,
+    TreeState,
+};
+use crate::tauri_api;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ServerSettingsDialogProps {
+    pub index: usize,
+    pub on_close: Callback<()>,
+}
+
+#[function_component(ServerSettingsDialog)]
+pub fn server_settings_dialog(props: &ServerSettingsDialogProps) -> Html {
+    let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let index = props.index;
+    let initial = tree_state
+        .as_ref()
+        .and_then(|state| state.servers.get(index))
+        .map(|server| server.settings.clone())
+        .unwrap_or_default();
+    let form = use_state(|| SettingsForm::from_settings(&initial));
+
+    let Some(tree_state) = tree_state else {
+        return html! {};
+    };
+    let label = tree_state
+        .servers
+        .get(index)
+        .map(|server| server.url.clone())
+        .unwrap_or_default();
+
+    let on_timeout = {
+        let form = form.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = (*form).clone();
+            next.timeout_ms = input_value(&event);
+            form.set(next);
+        })
+    };
+
+    let on_http_version = {
+        let form = form.clone();
+        Callback::from(move |event: Event| {
+            let mut next = (*form).clone();
+            next.http_version = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+                .and_then(|select| HttpVersion::from_key(&select.value()))
+                .unwrap_or_default();
+            form.set(next);
+        })
+    };
+
+    let on_unix_socket = {
+        let form = form.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = (*form).clone();
+            next.unix_socket = input_value(&event);
+            form.set(next);
+        })
+    };
+
+    let retry_field = |apply: fn(&mut RetryForm, String)| {
+        let form = form.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = (*form).clone();
+            apply(&mut next.retry, input_value(&event));
+            form.set(next);
+        })
+    };
+
+    let on_retry_network = {
+        let form = form.clone();
+        Callback::from(move |event: Event| {
+            let mut next = (*form).clone();
+            next.retry.network_errors = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                .map(|input| input.checked())
+                .unwrap_or(false);
+            form.set(next);
+        })
+    };
+
+    let tls_field = |apply: fn(&mut TlsSettings, String)| {
+        let form = form.clone();
+        Callback::from(move |event: InputEvent| {
+            let mut next = (*form).clone();
+            apply(&mut next.tls, input_value(&event));
+            form.set(next);
+        })
+    };
+
+    let tls_browse = |apply: fn(&mut TlsSettings, String)| {
+        let form = form.clone();
+        Callback::from(move |_event: MouseEvent| {
+            let form = form.clone();
+            spawn_local(async move {
+                if let Ok(Some(path)) = tauri_api::dialog_open().await {
+                    let mut next = (*form).clone();
+                    apply(&mut next.tls, path);
+                    form.set(next);
+                }
+            });
+        })
+    };
+
+    let on_allow_invalid = {
+        let form = form.clone();
+        Callback::from(move |event: Event| {
+            let mut next = (*form).clone();
+            next.tls.allow_invalid_certs = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                .map(|input| input.checked())
+                .unwrap_or(false);
+            form.set(next);
+        })
+    };
+
+    let on_proxy_mode = {
+        let form = form.clone();
+        Callback::from(move |event: Event| {
+            let mut next = (*form).clone();
+            next.proxy_mode = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+                .map(|select| ProxyMode::from_value(&select.value()))
+                .unwrap_or_default();
+            form.set(next);
+        })
+    };
+
+    let on_proxy_change = {
+        let form = form.clone();
+        Callback::from(move |proxy: ProxyConfig| {
+            let mut next = (*form).clone();
+            next.proxy = proxy;
+            form.set(next);
+        })
+    };
+
+    let path_field = |label: &'static str, value: &str, apply: fn(&mut TlsSettings, String)| {
+        html! {
+            <>
+                <label class="modal-label">{ label }</label>
+                <div class="modal-file-row">
+                    <input
+                        class="modal-input"
+                        type="text"
+                        placeholder="Not set"
+                        value={value.to_string()}
+                        oninput={tls_field(apply)}
+                    />
+                    <button class="button secondary" onclick={tls_browse(apply)}>{ "Browse" }</button>
+                </div>
+            </>
+        }
+    };
+
+    let on_save = {
+        let tree_state = tree_state.clone();
+        let form = form.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| {
+            tree_state.dispatch(TreeAction::UpdateServerSettings {
+                index,
+                settings: form.to_settings(),
+            });
+            on_close.emit(());
+        })
+    };
+
+    let on_cancel = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_event: MouseEvent| on_close.emit(()))
+    };
+
+    html! {
+        <div class="modal-backdrop">
+            <div class="modal auth-modal settings-modal">
+                <h2 class="modal-title">{ "Server settings" }</h2>
+                <p class="modal-text muted">{ label }</p>
+                <label class="modal-label">{ "Default timeout (ms)" }</label>
+                <input
+                    class="modal-input"
+                    type="number"
+                    min="0"
+                    placeholder="No timeout"
+                    value={form.timeout_ms.clone()}
+                    oninput={on_timeout}
+                />
+                <label class="modal-label">{ "HTTP version" }</label>
+                <select class="modal-input" onchange={on_http_version}>
+                    { for HttpVersion::all().iter().map(|version| html! {
+                        <option value={version.key()} selected={*version == form.http_version}>
+                            { version.label() }
+                        </option>
+                    }) }
+                </select>
+                <label class="modal-label">{ "Unix socket" }</label>
+                <input
+                    class="modal-input"
+                    type="text"
+                    placeholder="Not set (connect over TCP)"
+                    value={form.unix_socket.clone()}
+                    oninput={on_unix_socket}
+                />
+                <h3 class="modal-subtitle">{ "Retry" }</h3>
+                <label class="modal-label">{ "Max attempts" }</label>
+                <input
+                    class="modal-input"
+                    type="number"
+                    min="1"
+                    placeholder="1 (no retries)"
+                    value={form.retry.max_attempts.clone()}
+                    oninput={retry_field(|retry, value| retry.max_attempts = value)}
+                />
+                <label class="modal-label">{ "Retry on status codes" }</label>
+                <input
+                    class="modal-input"
+                    type="text"
+                    placeholder="502, 503, 504"
+                    value={form.retry.statuses.clone()}
+                    oninput={retry_field(|retry, value| retry.statuses = value)}
+                />
+                <label class="modal-label-inline">
+                    <input type="checkbox" checked={form.retry.network_errors} onchange={on_retry_network} />
+                    <span>{ " Retry on connection errors" }</span>
+                </label>
+                <label class="modal-label">{ "Initial backoff (ms)" }</label>
+                <input
+                    class="modal-input"
+                    type="number"
+                    min="0"
+                    value={form.retry.base_delay_ms.clone()}
+                    oninput={retry_field(|retry, value| retry.base_delay_ms = value)}
+                />
+                <label class="modal-label">{ "Max backoff (ms)" }</label>
+                <input
+                    class="modal-input"
+                    type="number"
+                    min="0"
+                    value={form.retry.max_delay_ms.clone()}
+                    oninput={retry_field(|retry, value| retry.max_delay_ms = value)}
+                />
+                <p class="modal-text muted">
+                    { "Backoff doubles after each attempt, with jitter. A Retry-After header from the server takes precedence, up to 5 minutes." }
+                </p>
+                <h3 class="modal-subtitle">{ "TLS" }</h3>
+                { path_field("CA bundle (PEM)", &form.tls.ca_cert_path, |tls, value| tls.ca_cert_path = value) }
+                { path_field("Client certificate (PEM)", &form.tls.client_cert_path, |tls, value| tls.client_cert_path = value) }
+                { path_field("Client key (PEM)", &form.tls.client_key_path, |tls, value| tls.client_key_path = value) }
+                { path_field("Client identity (PKCS#12)", &form.tls.pkcs12_path, |tls, value| tls.pkcs12_path = value) }
+                <label class="modal-label">{ "PKCS#12 password" }</label>
+                <input
+                    class="modal-input"
+                    type="password"
+                    value={form.tls.pkcs12_password.clone()}
+                    oninput={tls_field(|tls, value| tls.pkcs12_password = value)}
+                />
+                <label class="modal-label-inline">
+                    <input type="checkbox" checked={form.tls.allow_invalid_certs} onchange={on_allow_invalid} />
+                    <span>{ " Allow invalid certificates (insecure)" }</span>
+                </label>
+                <h3 class="modal-subtitle">{ "Proxy" }</h3>
+                <select class="modal-input" onchange={on_proxy_mode}>
+                    { for ProxyMode::ALL.iter().map(|mode| html! {
+                        <option value={mode.value()} selected={*mode == form.proxy_mode}>
+                            { mode.label() }
+                        </option>
+                    }) }
+                </select>
+                if form.proxy_mode == ProxyMode::Custom {
+                    <ProxyFields config={form.proxy.clone()} on_change={on_proxy_change} />
+                }
+                <div class="modal-actions">
+                    <button class="button secondary" onclick={on_cancel}>{ "Cancel" }</button>
+                    <button class="button" onclick={on_save}>{ "Save" }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Clone, PartialEq, Default)]
+struct SettingsForm {
+    timeout_ms: String,
+    http_version: HttpVersion,
+    unix_socket: String,
+    retry: RetryForm,
+    tls: TlsSettings,
+    proxy_mode: ProxyMode,
+    proxy: ProxyConfig,
+}
+
+impl SettingsForm {
+    fn from_settings(settings: &ServerSettings) -> Self {
+        Self {
+            timeout_ms: settings
+                .timeout_ms
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            http_version: settings.http_version,
+            unix_socket: settings.unix_socket.clone().unwrap_or_default(),
+            retry: RetryForm::from_policy(&settings.retry),
+            tls: settings.tls.clone(),
+            proxy_mode: ProxyMode::of(&settings.proxy),
+            proxy: match &settings.proxy {
+                ServerProxy::Custom(config) => config.clone(),
+                _ => ProxyConfig::default(),
+            },
+        }
+    }
+
+    fn to_settings(&self) -> ServerSettings {
+        ServerSettings {
+            timeout_ms: parse_millis(&self.timeout_ms),
+            http_version: self.http_version,
+            unix_socket: Some(self.unix_socket.trim().to_string())
+                .filter(|path| !path.is_empty()),
+            retry: self.retry.to_policy(),
+            tls: self.tls.clone(),
+            proxy: match self.proxy_mode {
+                ProxyMode::Inherit => ServerProxy::Inherit,
+                ProxyMode::Direct => ServerProxy::Direct,
+                ProxyMode::Custom => ServerProxy::Custom(self.proxy.clone()),
+            },
+        }
+    }
+}
+
+/// Retry fields as typed; blank or invalid numbers fall back to the defaults.
+#[derive(Clone, PartialEq, Default)]
+struct RetryForm {
+    max_attempts: String,
+    statuses: String,
+    network_errors: bool,
+    base_delay_ms: String,
+    max_delay_ms: String,
+}
+
+impl RetryForm {
+    fn from_policy(policy: &RetryPolicy) -> Self {
+        Self {
+            max_attempts: policy.max_attempts.to_string(),
+            statuses: policy
+                .statuses
+                .iter()
+                .map(|status| status.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            network_errors: policy.network_errors,
+            base_delay_ms: policy.base_delay_ms.to_string(),
+            max_delay_ms: policy.max_delay_ms.to_string(),
+        }
+    }
+
+    fn to_policy(&self) -> RetryPolicy {
+        let defaults = RetryPolicy::default();
+        RetryPolicy {
+            max_attempts: self
+                .max_attempts
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts > 0)
+                .unwrap_or(defaults.max_attempts),
+            statuses: self
+                .statuses
+                .split(',')
+                .filter_map(|status| status.trim().parse::<u16>().ok())
+                .filter(|status| (100..600).contains(status))
+                .collect(),
+            network_errors: self.network_errors,
+            base_delay_ms: self
+                .base_delay_ms
+                .trim()
+                .parse()
+                .unwrap_or(defaults.base_delay_ms),
+            max_delay_ms: self
+                .max_delay_ms
+                .trim()
+                .parse()
+                .unwrap_or(defaults.max_delay_ms),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+enum ProxyMode {
+    #[default]
+    Inherit,
+    Direct,
+    Custom,
+}
+
+impl ProxyMode {
+    const ALL: [ProxyMode; 3] = [ProxyMode::Inherit, ProxyMode::Direct, ProxyMode::Custom];
+
+    fn of(proxy: &ServerProxy) -> Self {
+        match proxy {
+            ServerProxy::Inherit => ProxyMode::Inherit,
+            ServerProxy::Direct => ProxyMode::Direct,
+            ServerProxy::Custom(_) => ProxyMode::Custom,
+        }
+    }
+
+    fn value(self) -> &'static str {
+        match self {
+            ProxyMode::Inherit => "inherit",
+            ProxyMode::Direct => "direct",
+            ProxyMode::Custom => "custom",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ProxyMode::Inherit => "Use global proxy settings",
+            ProxyMode::Direct => "No proxy (direct connection)",
+            ProxyMode::Custom => "Custom proxy",
+        }
+    }
+
+    fn from_value(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.value() == value)
+            .unwrap_or_default()
+    }
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
@@ -3,6 +3,10 @@ use yew::prelude::*;
 
 use wasm_bindgen::JsCast;
 
+use crate::components::cookie_manager::CookieManager;
+use crate::components::environments::EnvironmentsDialog;
+use crate::components::history::HistoryPanel;
+use crate::components::server_settings::ServerSettingsDialog;
 use crate::state::{ApiKeyLocation, OAuth2Flow, OAuthScope, ServerAuth, TreeAction, TreeState};
 
 #[derive(Properties, Clone, PartialEq)]
@@ -11,6 +15,12 @@ pub struct SideProps {
     pub on_add_tag: Callback<()>,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum SideView {
+    Collection,
+    History,
+}
+
 #[function_component(Side)]
 pub fn side(props: &SideProps) -> Html {
     let tree_state = use_context::<UseReducerHandle<TreeState>>();
@@ -54,12 +64,41 @@ pub fn side(props: &SideProps) -> Html {
         })
     };
 
+    let environments_open = use_state(|| false);
+    let on_environment_change = {
+        let tree_state = tree_state.clone();
+        Callback::from(move |event: Event| {
+            let index = select_value(&event).parse::<usize>().ok();
+            tree_state.dispatch(TreeAction::SetActiveEnvironment { index });
+        })
+    };
+    let on_edit_environments = {
+        let environments_open = environments_open.clone();
+        Callback::from(move |_| environments_open.set(true))
+    };
+    let on_environments_close = {
+        let environments_open = environments_open.clone();
+        Callback::from(move |_| environments_open.set(false))
+    };
+
+    let view = use_state(|| SideView::Collection);
+    let on_show_collection = {
+        let view = view.clone();
+        Callback::from(move |_| view.set(SideView::Collection))
+    };
+    let on_show_history = {
+        let view = view.clone();
+        Callback::from(move |_| view.set(SideView::History))
+    };
+
     let menu_open = use_state(|| false);
     let menu_ref = use_node_ref();
     let pending_remove_server = use_state(|| None::<usize>);
     let auth_dialog_open = use_state(|| false);
     let auth_form = use_state(AuthForm::default);
     let auth_server_index = use_state(|| None::<usize>);
+    let settings_server_index = use_state(|| None::<usize>);
+    let cookie_server_url = use_state(|| None::<String>);
     let on_menu_toggle = {
         let menu_open = menu_open.clone();
         Callback::from(move |event: MouseEvent| {
@@ -153,6 +192,48 @@ pub fn side(props: &SideProps) -> Html {
         })
     };
 
+    let on_edit_settings = {
+        let menu_open = menu_open.clone();
+        let settings_server_index = settings_server_index.clone();
+        let servers = servers.clone();
+        Callback::from(move |event: MouseEvent| {
+            event.stop_propagation();
+            menu_open.set(false);
+            let Some(index) = selected_server else {
+                return;
+            };
+            if servers.get(index).is_some() {
+                settings_server_index.set(Some(index));
+            }
+        })
+    };
+
+    let on_edit_cookies = {
+        let menu_open = menu_open.clone();
+        let cookie_server_url = cookie_server_url.clone();
+        let servers = servers.clone();
+        Callback::from(move |event: MouseEvent| {
+            event.stop_propagation();
+            menu_open.set(false);
+            let Some(index) = selected_server else {
+                return;
+            };
+            if let Some(server) = servers.get(index) {
+                cookie_server_url.set(Some(server.url.clone()));
+            }
+        })
+    };
+
+    let on_cookies_close = {
+        let cookie_server_url = cookie_server_url.clone();
+        Callback::from(move |_| cookie_server_url.set(None))
+    };
+
+    let on_settings_close = {
+        let settings_server_index = settings_server_index.clone();
+        Callback::from(move |_| settings_server_index.set(None))
+    };
+
     let on_auth_save = {
         let tree_state = tree_state.clone();
         let auth_form = auth_form.clone();
@@ -234,6 +315,12 @@ pub fn side(props: &SideProps) -> Html {
                                     <button type="button" class="tree-menu-item" onclick={on_edit_auth.clone()}>
                                         { "Auth" }
                                     </button>
+                                    <button type="button" class="tree-menu-item" onclick={on_edit_settings.clone()}>
+                                        { "Settings" }
+                                    </button>
+                                    <button type="button" class="tree-menu-item" onclick={on_edit_cookies.clone()}>
+                                        { "Cookies" }
+                                    </button>
                                     <button type="button" class="tree-menu-item danger" onclick={on_remove_server.clone()}>
                                         { "Remove server" }
                                     </button>
@@ -245,33 +332,80 @@ pub fn side(props: &SideProps) -> Html {
                     }
                 </div>
             </div>
+            <div class="env-bar">
+                <div class="server-select-wrap">
+                    <select class="server-select" onchange={on_environment_change}>
+                        <option value="" selected={tree_state.active_environment.is_none()}>
+                            { "No environment" }
+                        </option>
+                        { for tree_state.environments.iter().enumerate().map(|(index, environment)| html! {
+                            <option
+                                value={index.to_string()}
+                                selected={tree_state.active_environment == Some(index)}
+                            >
+                                { environment.name.clone() }
+                            </option>
+                        }) }
+                    </select>
+                    <span class="select-chevron"></span>
+                </div>
+                <button type="button" class="tree-row-menu" title="Environments" onclick={on_edit_environments}>
+                    { "✎" }
+                </button>
+            </div>
+            <div class="side-views">
+                <button
+                    type="button"
+                    class={classes!("side-view", (*view == SideView::Collection).then_some("active"))}
+                    onclick={on_show_collection}
+                >
+                    { "Collection" }
+                </button>
+                <button
+                    type="button"
+                    class={classes!("side-view", (*view == SideView::History).then_some("active"))}
+                    onclick={on_show_history}
+                >
+                    { "History" }
+                </button>
+            </div>
             {
-                if let Some(pending_move) = tree_state.pending_move.as_ref() {
+                if *view == SideView::History {
+                    html! { <HistoryPanel /> }
+                } else {
                     html! {
-                        <div class="tree-banner">
-                            <div class="tree-banner-text">
-                                <span class="tree-banner-title">{ "Move:" }</span>
-                                <span class="tree-banner-label">{ pending_move.label.clone() }</span>
-                                <span class="muted">{ "Select destination tag." }</span>
-                            </div>
-                            <button class="tree-banner-cancel" type="button" onclick={on_cancel_move.clone()}>
-                                { "Cancel" }
-                            </button>
+                        <>
+                        {
+                            if let Some(pending_move) = tree_state.pending_move.as_ref() {
+                                html! {
+                                    <div class="tree-banner">
+                                        <div class="tree-banner-text">
+                                            <span class="tree-banner-title">{ "Move:" }</span>
+                                            <span class="tree-banner-label">{ pending_move.label.clone() }</span>
+                                            <span class="muted">{ "Select destination tag." }</span>
+                                        </div>
+                                        <button class="tree-banner-cancel" type="button" onclick={on_cancel_move.clone()}>
+                                            { "Cancel" }
+                                        </button>
+                                    </div>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                        <div class="tree">
+                            {
+                                if tree_state.root.children.is_empty() {
+                                    html! { <div class="tree-empty">{ "Add a tag to get started." }</div> }
+                                } else {
+                                    html! { <crate::components::tree::directory::TreeDirectory node={tree_state.root.clone()} path={vec![]} /> }
+                                }
+                            }
                         </div>
+                        </>
                     }
-                } else {
-                    html! {}
                 }
             }
-            <div class="tree">
-                {
-                    if tree_state.root.children.is_empty() {
-                        html! { <div class="tree-empty">{ "Add a tag to get started." }</div> }
-                    } else {
-                        html! { <crate::components::tree::directory::TreeDirectory node={tree_state.root.clone()} path={vec![]} /> }
-                    }
-                }
-            </div>
         </nav>
         {
             if let Some(index) = (*pending_remove_server).clone() {
@@ -315,6 +449,23 @@ pub fn side(props: &SideProps) -> Html {
                 html! {}
             }
         }
+        if *environments_open {
+            <EnvironmentsDialog on_close={on_environments_close} />
+        }
+        {
+            if let Some(index) = *settings_server_index {
+                html! { <ServerSettingsDialog index={index} on_close={on_settings_close.clone()} /> }
+            } else {
+                html! {}
+            }
+        }
+        {
+            if let Some(server_url) = (*cookie_server_url).clone() {
+                html! { <CookieManager server_url={server_url} on_close={on_cookies_close.clone()} /> }
+            } else {
+                html! {}
+            }
+        }
         {
             if *auth_dialog_open {
                 let auth_form_value = (*auth_form).clone();
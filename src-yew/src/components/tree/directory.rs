@@ -1,11 +1,23 @@
 use std::rc::Rc;
 
 use gloo::events::EventListener;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 use yew::prelude::*;
 
 use crate::state::{TabAction, TabContent, TabState, TreeAction, TreeNode, TreeState};
 
+/// Drag data type carrying the dragged node's path, so drops from outside the tree
+/// are ignored.
+const DRAG_TYPE: &str = "application/x-rustman-path";
+
+/// Where a drop lands relative to the row under the pointer.
+#[derive(Clone, Copy, PartialEq)]
+enum DropPosition {
+    Before,
+    Into,
+    After,
+}
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct TreeDirectoryProps {
     pub node: TreeNode,
@@ -13,16 +25,11 @@ pub struct TreeDirectoryProps {
     pub path: Vec<usize>,
 }
 
-fn render_children(node: &TreeNode, path: &Vec<usize>) -> Html {
-    let mut entries: Vec<(usize, TreeNode)> =
-        node.children.iter().cloned().enumerate().collect();
-    entries.sort_by_key(|(index, child)| {
-        let is_file = child.content.is_some();
-        (is_file, child.label.to_lowercase(), *index)
-    });
+/// Children in their stored order, which drag and drop arranges and saving keeps.
+fn render_children(node: &TreeNode, path: &[usize]) -> Html {
     html! {
-        { for entries.into_iter().map(|(index, child)| {
-            let mut child_path = path.clone();
+        { for node.children.iter().enumerate().map(|(index, child)| {
+            let mut child_path = path.to_vec();
             child_path.push(index);
             html! {
                 <TreeDirectory
@@ -57,6 +64,7 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
     let draft = use_state(|| props.node.label.clone());
     let menu_open = use_state(|| false);
     let menu_ref = use_node_ref();
+    let drop_hint = use_state(|| None::<DropPosition>);
 
     {
         let label = props.node.label.clone();
@@ -68,9 +76,97 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
     }
 
     if props.path.is_empty() {
-        return html! { <>{ render_children(&props.node, &props.path) }</> };
+        // Drops between or below the rows land at the end of the root.
+        let on_root_drag_over = Callback::from(|event: DragEvent| {
+            if is_tree_drag(&event) {
+                event.prevent_default();
+            }
+        });
+        let on_root_drop = {
+            let tree_state = tree_state.clone();
+            Callback::from(move |event: DragEvent| {
+                let Some(from) = dragged_path(&event) else {
+                    return;
+                };
+                event.prevent_default();
+                tree_state.dispatch(TreeAction::MoveNode {
+                    from,
+                    to: Vec::new(),
+                    index: None,
+                });
+            })
+        };
+        return html! {
+            <div class="tree-root" ondragover={on_root_drag_over} ondrop={on_root_drop}>
+                { render_children(&props.node, &props.path) }
+            </div>
+        };
     }
 
+    let on_drag_start = {
+        let path = props.path.clone();
+        let menu_open = menu_open.clone();
+        Callback::from(move |event: DragEvent| {
+            event.stop_propagation();
+            menu_open.set(false);
+            if let Some(data) = event.data_transfer() {
+                data.set_effect_allowed("move");
+                let _ = data.set_data(DRAG_TYPE, &encode_path(&path));
+            }
+        })
+    };
+
+    let on_drag_over = {
+        let drop_hint = drop_hint.clone();
+        Callback::from(move |event: DragEvent| {
+            if !is_tree_drag(&event) {
+                return;
+            }
+            event.prevent_default();
+            event.stop_propagation();
+            let position = drop_position(&event, is_folder);
+            if *drop_hint != position {
+                drop_hint.set(position);
+            }
+        })
+    };
+
+    let on_drag_leave = {
+        let drop_hint = drop_hint.clone();
+        Callback::from(move |_event: DragEvent| drop_hint.set(None))
+    };
+
+    let on_drop = {
+        let tree_state = tree_state.clone();
+        let path = props.path.clone();
+        let drop_hint = drop_hint.clone();
+        Callback::from(move |event: DragEvent| {
+            drop_hint.set(None);
+            let Some(from) = dragged_path(&event) else {
+                return;
+            };
+            event.prevent_default();
+            event.stop_propagation();
+            if from == path {
+                return;
+            }
+            let Some((to, index)) = drop_position(&event, is_folder)
+                .and_then(|position| drop_target(&path, position))
+            else {
+                return;
+            };
+            tree_state.dispatch(TreeAction::MoveNode { from, to, index });
+        })
+    };
+
+    let drop_class = match *drop_hint {
+        Some(DropPosition::Before) => "drop-before",
+        Some(DropPosition::Into) => "drop-into",
+        Some(DropPosition::After) => "drop-after",
+        None => "",
+    };
+    let draggable = if *is_editing { "false" } else { "true" };
+
     let commit_rename = {
         let tree_state = tree_state.clone();
         let path = props.path.clone();
@@ -194,6 +290,26 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
         })
     };
 
+    let on_menu_new_folder = {
+        let menu_open = menu_open.clone();
+        let tree_state = tree_state.clone();
+        let path = props.path.clone();
+        let label = new_folder_label(&props.node);
+        Callback::from(move |event: MouseEvent| {
+            event.stop_propagation();
+            menu_open.set(false);
+            tree_state.dispatch(TreeAction::AddChild {
+                path: path.clone(),
+                node: TreeNode {
+                    label: label.clone(),
+                    content: None,
+                    expanded: true,
+                    children: Vec::new(),
+                },
+            });
+        })
+    };
+
     let on_menu_delete = {
         let menu_open = menu_open.clone();
         let tree_state = tree_state.clone();
@@ -230,6 +346,7 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
                     tree_state.dispatch(TreeAction::MoveNode {
                         from: pending_move.path.clone(),
                         to: path.clone(),
+                        index: None,
                     });
                     return;
                 }
@@ -243,7 +360,14 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
 
         return html! {
             <div>
-                <div class={classes!("tree-row-wrap", if is_selected { "selected" } else { "" })}>
+                <div
+                    class={classes!("tree-row-wrap", if is_selected { "selected" } else { "" }, drop_class)}
+                    draggable={draggable}
+                    ondragstart={on_drag_start}
+                    ondragover={on_drag_over}
+                    ondragleave={on_drag_leave}
+                    ondrop={on_drop}
+                >
                     {
                         if *is_editing {
                             html! {
@@ -285,6 +409,9 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
                                             if *menu_open {
                                                 html! {
                                                     <div class="tree-menu">
+                                                        <button type="button" class="tree-menu-item" onclick={on_menu_new_folder.clone()}>
+                                                            { "New folder" }
+                                                        </button>
                                                         <button type="button" class="tree-menu-item" onclick={on_menu_edit.clone()}>
                                                             { "Edit" }
                                                         </button>
@@ -340,13 +467,20 @@ pub fn tree_directory(props: &TreeDirectoryProps) -> Html {
             let new_content = TabContent::from_node(content);
             tab_state.dispatch(TabAction::OpenTab {
                 label: label.clone(),
-                content: new_content,
+                content: Box::new(new_content),
             });
         })
     };
 
     html! {
-        <div class={classes!("tree-row-wrap", if is_selected { "selected" } else { "" })}>
+        <div
+            class={classes!("tree-row-wrap", if is_selected { "selected" } else { "" }, drop_class)}
+            draggable={draggable}
+            ondragstart={on_drag_start}
+            ondragover={on_drag_over}
+            ondragleave={on_drag_leave}
+            ondrop={on_drop}
+        >
             {
                 if *is_editing {
                     html! {
@@ -428,3 +562,62 @@ fn event_target_value(event: &InputEvent) -> String {
         .map(|input| input.value())
         .unwrap_or_default()
 }
+
+/// The first of "New folder", "New folder 2", ... not already used in `node`.
+fn new_folder_label(node: &TreeNode) -> String {
+    let taken = |label: &str| node.children.iter().any(|child| child.label == label);
+    let mut label = "New folder".to_string();
+    let mut number = 2;
+    while taken(&label) {
+        label = format!("New folder {number}");
+        number += 1;
+    }
+    label
+}
+
+fn encode_path(path: &[usize]) -> String {
+    path.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn dragged_path(event: &DragEvent) -> Option<Vec<usize>> {
+    let data = event.data_transfer()?.get_data(DRAG_TYPE).ok()?;
+    data.split('/').map(|part| part.parse().ok()).collect()
+}
+
+/// Only the types of the drag data can be read before the drop.
+fn is_tree_drag(event: &DragEvent) -> bool {
+    event
+        .data_transfer()
+        .map(|data| data.types().includes(&JsValue::from_str(DRAG_TYPE), 0))
+        .unwrap_or(false)
+}
+
+/// Folders take drops in their middle half; the edges of any row insert beside it.
+fn drop_position(event: &DragEvent, is_folder: bool) -> Option<DropPosition> {
+    let row = event
+        .current_target()?
+        .dyn_into::<web_sys::Element>()
+        .ok()?
+        .get_bounding_client_rect();
+    let offset = (f64::from(event.client_y()) - row.top()) / row.height().max(1.0);
+    Some(if is_folder && (0.25..0.75).contains(&offset) {
+        DropPosition::Into
+    } else if offset < 0.5 {
+        DropPosition::Before
+    } else {
+        DropPosition::After
+    })
+}
+
+/// The folder and index a drop at `position` of the row at `path` moves to.
+fn drop_target(path: &[usize], position: DropPosition) -> Option<(Vec<usize>, Option<usize>)> {
+    let (&index, parent) = path.split_last()?;
+    Some(match position {
+        DropPosition::Before => (parent.to_vec(), Some(index)),
+        DropPosition::After => (parent.to_vec(), Some(index + 1)),
+        DropPosition::Into => (path.to_vec(), None),
+    })
+}
@@ -0,0 +1,145 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::tauri_api;
+use crate::vault::Vault;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct VaultDialogProps {
+    /// Why the vault is needed, e.g. opening a collection that references it.
+    pub reason: AttrValue,
+    pub on_unlock: Callback<Vault>,
+    pub on_cancel: Callback<()>,
+}
+
+/// Unlocks the secret vault, or creates it when there is none yet.
+#[function_component(VaultDialog)]
+pub fn vault_dialog(props: &VaultDialogProps) -> Html {
+    // `None` until the vault file is read; `Some(None)` when there is no vault yet.
+    let sealed = use_state(|| None::<Option<serde_json::Value>>);
+    let passphrase = use_state(String::new);
+    let confirm = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let sealed = sealed.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match tauri_api::load_vault().await {
+                    Ok(vault) => sealed.set(Some(vault)),
+                    Err(err) => error.set(Some(tauri_api::js_error_to_string(&err))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let creating = matches!(*sealed, Some(None));
+
+    let on_passphrase = {
+        let passphrase = passphrase.clone();
+        Callback::from(move |event: InputEvent| passphrase.set(input_value(&event)))
+    };
+    let on_confirm_input = {
+        let confirm = confirm.clone();
+        Callback::from(move |event: InputEvent| confirm.set(input_value(&event)))
+    };
+
+    let on_submit = {
+        let sealed = sealed.clone();
+        let passphrase = passphrase.clone();
+        let confirm = confirm.clone();
+        let error = error.clone();
+        let on_unlock = props.on_unlock.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let Some(existing) = (*sealed).clone() else {
+                return;
+            };
+            match existing {
+                Some(existing) => match Vault::unlock(existing, &passphrase) {
+                    Ok(vault) => on_unlock.emit(vault),
+                    Err(message) => error.set(Some(message)),
+                },
+                None => {
+                    if passphrase.is_empty() {
+                        error.set(Some("Informe uma senha mestra.".to_string()));
+                        return;
+                    }
+                    if *passphrase != *confirm {
+                        error.set(Some("As senhas não conferem.".to_string()));
+                        return;
+                    }
+                    let vault = Vault::create(&passphrase);
+                    let error = error.clone();
+                    let on_unlock = on_unlock.clone();
+                    spawn_local(async move {
+                        let saved = match vault.seal() {
+                            Ok(sealed) => tauri_api::save_vault(&sealed)
+                                .await
+                                .map_err(|err| tauri_api::js_error_to_string(&err)),
+                            Err(message) => Err(message),
+                        };
+                        match saved {
+                            Ok(()) => on_unlock.emit(vault),
+                            Err(message) => error.set(Some(message)),
+                        }
+                    });
+                }
+            }
+        })
+    };
+
+    let on_cancel = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |_event: MouseEvent| on_cancel.emit(()))
+    };
+
+    html! {
+        <div class="modal-backdrop">
+            <form class="modal" onsubmit={on_submit}>
+                <h2 class="modal-title">{ if creating { "Create secret vault" } else { "Unlock secret vault" } }</h2>
+                <p class="modal-text muted">{ props.reason.clone() }</p>
+                if let Some(message) = (*error).clone() {
+                    <p class="modal-text error">{ message }</p>
+                }
+                <label class="modal-label">{ "Master passphrase" }</label>
+                <input
+                    class="modal-input"
+                    type="password"
+                    value={(*passphrase).clone()}
+                    oninput={on_passphrase}
+                    autofocus=true
+                />
+                if creating {
+                    <label class="modal-label">{ "Confirm passphrase" }</label>
+                    <input
+                        class="modal-input"
+                        type="password"
+                        value={(*confirm).clone()}
+                        oninput={on_confirm_input}
+                    />
+                    <p class="modal-text muted">
+                        { "Secrets are encrypted with this passphrase. It cannot be recovered if lost." }
+                    </p>
+                }
+                <div class="modal-actions">
+                    <button type="button" class="button secondary" onclick={on_cancel}>{ "Cancel" }</button>
+                    <button type="submit" class="button" disabled={sealed.is_none()}>
+                        { if creating { "Create" } else { "Unlock" } }
+                    </button>
+                </div>
+            </form>
+        </div>
+    }
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
@@ -0,0 +1,219 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::request::header_table::HeaderTable;
+use crate::state::{TabAction, TabContent, TabState, WsComposer, WsPayload, WsSession, WsStatus};
+use crate::tauri_api;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SocketComposerProps {
+    pub tab_index: usize,
+    pub content: TabContent,
+    pub session: WsSession,
+}
+
+/// Message composer, handshake headers and close frame settings of a WebSocket tab.
+#[function_component(SocketComposer)]
+pub fn socket_composer(props: &SocketComposerProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let active = use_state(|| "message".to_string());
+    let send_error = use_state(|| None::<String>);
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let on_select = |value: &'static str, active: UseStateHandle<String>| {
+        Callback::from(move |_| active.set(value.to_string()))
+    };
+
+    let index = props.tab_index;
+    let composer = props.session.composer.clone();
+    let is_open = props.session.status == WsStatus::Open;
+
+    let update = {
+        let tab_state = tab_state.clone();
+        let composer = composer.clone();
+        move |apply: Box<dyn FnOnce(&mut WsComposer)>| {
+            let mut composer = composer.clone();
+            apply(&mut composer);
+            tab_state.dispatch(TabAction::UpdateWsComposer { index, composer });
+        }
+    };
+
+    let on_payload_change = {
+        let update = update.clone();
+        let send_error = send_error.clone();
+        Callback::from(move |event: Event| {
+            let payload = WsPayload::from_key(&select_value(&event));
+            send_error.set(None);
+            update(Box::new(move |composer| composer.payload = payload));
+        })
+    };
+
+    let on_text_change = {
+        let update = update.clone();
+        Callback::from(move |event: InputEvent| {
+            let text = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+                .map(|input| input.value())
+                .unwrap_or_default();
+            update(Box::new(move |composer| composer.text = text));
+        })
+    };
+
+    let on_close_code_change = {
+        let update = update.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = input_value(&event);
+            update(Box::new(move |composer| composer.close_code = value));
+        })
+    };
+
+    let on_close_reason_change = {
+        let update = update.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = input_value(&event);
+            update(Box::new(move |composer| composer.close_reason = value));
+        })
+    };
+
+    let send = {
+        let connection_id = props.session.connection_id.clone();
+        let send_error = send_error.clone();
+        move |kind: &'static str, data: String| {
+            let Some(connection_id) = connection_id.clone() else {
+                return;
+            };
+            let send_error = send_error.clone();
+            spawn_local(async move {
+                match tauri_api::ws_send(&connection_id, kind, &data).await {
+                    Ok(()) => send_error.set(None),
+                    Err(err) => send_error.set(Some(tauri_api::js_error_to_string(&err))),
+                }
+            });
+        }
+    };
+
+    let on_send = {
+        let send = send.clone();
+        let composer = composer.clone();
+        let send_error = send_error.clone();
+        Callback::from(move |_event: MouseEvent| match composer.payload {
+            WsPayload::Text => send("text", composer.text.clone()),
+            WsPayload::Binary => send("binary", composer.text.clone()),
+            WsPayload::Json => match serde_json::from_str::<serde_json::Value>(&composer.text) {
+                Ok(_) => send("text", composer.text.clone()),
+                Err(err) => send_error.set(Some(format!("JSON inválido: {err}"))),
+            },
+        })
+    };
+
+    let on_ping = Callback::from(move |_event: MouseEvent| send("ping", String::new()));
+
+    html! {
+        <div class="request">
+            <div class="subtabs">
+                <button
+                    class={classes!("subtab", if *active == "message" { "active" } else { "" })}
+                    onclick={on_select("message", active.clone())}
+                >
+                    { "Message" }
+                </button>
+                <button
+                    class={classes!("subtab", if *active == "headers" { "active" } else { "" })}
+                    onclick={on_select("headers", active.clone())}
+                >
+                    { "Headers" }
+                </button>
+                <button
+                    class={classes!("subtab", if *active == "close" { "active" } else { "" })}
+                    onclick={on_select("close", active.clone())}
+                >
+                    { "Close" }
+                </button>
+            </div>
+
+            {
+                match active.as_str() {
+                    "headers" => html! {
+                        <HeaderTable tab_index={index} headers={props.content.headers.clone()} />
+                    },
+                    "close" => html! {
+                        <div class="table-wrap">
+                            <div class="modal-row">
+                                <label class="modal-column">
+                                    { "Close code" }
+                                    <input
+                                        type="text"
+                                        inputmode="numeric"
+                                        value={composer.close_code.clone()}
+                                        oninput={on_close_code_change}
+                                    />
+                                </label>
+                                <label class="modal-column">
+                                    { "Reason" }
+                                    <input
+                                        type="text"
+                                        value={composer.close_reason.clone()}
+                                        oninput={on_close_reason_change}
+                                    />
+                                </label>
+                            </div>
+                        </div>
+                    },
+                    _ => html! {
+                        <div class="table-wrap body-wrap">
+                            <div class="request-title">
+                                <div class="body-actions">
+                                    <select class="body-mode" onchange={on_payload_change}>
+                                        { for WsPayload::all().iter().map(|option| html! {
+                                            <option value={option.key()} selected={*option == composer.payload}>
+                                                { option.label() }
+                                            </option>
+                                        }) }
+                                    </select>
+                                    if let Some(error) = (*send_error).clone() {
+                                        <span class="socket-error">{ error }</span>
+                                    }
+                                </div>
+                                <div class="body-actions">
+                                    <button class="button secondary" onclick={on_ping} disabled={!is_open}>
+                                        { "Ping" }
+                                    </button>
+                                    <button class="button" onclick={on_send} disabled={!is_open}>
+                                        { "Send" }
+                                    </button>
+                                </div>
+                            </div>
+                            <hr class="section-divider" />
+                            <div class="body-editor-wrap">
+                                <textarea
+                                    class="editor body-editor"
+                                    value={composer.text.clone()}
+                                    oninput={on_text_change}
+                                />
+                            </div>
+                        </div>
+                    },
+                }
+            }
+        </div>
+    }
+}
+
+fn input_value(event: &InputEvent) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+fn select_value(event: &Event) -> String {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlSelectElement>().ok())
+        .map(|select| select.value())
+        .unwrap_or_default()
+}
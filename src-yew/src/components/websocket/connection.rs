@@ -0,0 +1,161 @@
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::SubmitEvent;
+use yew::prelude::*;
+
+use crate::components::request::url::{build_socket_target, new_request_id};
+use crate::environment::Variables;
+use crate::state::{
+    HeaderList, TabAction, TabContent, TabState, TlsSettings, TreeState, WsClosed, WsSession, WsStatus,
+};
+use crate::tauri_api;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SocketConnectionProps {
+    pub tab_index: usize,
+    pub content: TabContent,
+    pub session: WsSession,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SocketRequest {
+    connection_id: String,
+    url: String,
+    headers: HeaderList,
+    server_url: Option<String>,
+    timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tls: Option<TlsSettings>,
+}
+
+/// URL bar of a WebSocket tab: connects with the selected server's auth and TLS settings.
+#[function_component(SocketConnection)]
+pub fn socket_connection(props: &SocketConnectionProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let tree_state = use_context::<UseReducerHandle<TreeState>>();
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let Some(tree_state) = tree_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let session = props.session.clone();
+    let variables = Variables::active(&tree_state);
+    let server = tree_state
+        .selected_server
+        .and_then(|index| tree_state.servers.get(index));
+    let unlocked = variables.check_vault(&props.content, server);
+    let selected_server = server.map(|server| variables.resolve_server(server));
+
+    let on_url_change = {
+        let tab_state = tab_state.clone();
+        Callback::from(move |event: InputEvent| {
+            let url = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                .map(|input| input.value())
+                .unwrap_or_default();
+            tab_state.dispatch(TabAction::UpdateUrl { index, url });
+        })
+    };
+
+    let on_submit = {
+        let tab_state = tab_state.clone();
+        let content = props.content.clone();
+        let session = session.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            match session.status {
+                WsStatus::Open => {
+                    let Some(connection_id) = session.connection_id.clone() else {
+                        return;
+                    };
+                    let composer = session.composer.clone();
+                    tab_state.dispatch(TabAction::WsClosing(index));
+                    spawn_local(async move {
+                        let code = composer.close_code.trim().parse::<u16>().ok();
+                        let _ =
+                            tauri_api::ws_close(&connection_id, code, &composer.close_reason).await;
+                    });
+                }
+                WsStatus::Connecting | WsStatus::Closing => {}
+                WsStatus::Disconnected => {
+                    let connection_id = new_request_id();
+                    tab_state.dispatch(TabAction::WsConnecting {
+                        index,
+                        connection_id: connection_id.clone(),
+                    });
+                    let tab_state = tab_state.clone();
+                    let content = variables.resolve(&content);
+                    let server = selected_server.clone();
+                    let unlocked = unlocked.clone();
+                    spawn_local(async move {
+                        let result = async {
+                            unlocked?;
+                            let (url, headers) = build_socket_target(&content, server.as_ref())?;
+                            let request = SocketRequest {
+                                connection_id: connection_id.clone(),
+                                url,
+                                headers,
+                                server_url: server.as_ref().map(|server| server.url.clone()),
+                                timeout_ms: server
+                                    .as_ref()
+                                    .and_then(|server| server.settings.timeout_ms),
+                                tls: server
+                                    .as_ref()
+                                    .map(|server| server.settings.tls.clone())
+                                    .filter(|tls| !tls.is_default()),
+                            };
+                            tauri_api::ws_connect(&request)
+                                .await
+                                .map_err(|err| tauri_api::js_error_to_string(&err))
+                        }
+                        .await;
+                        match result {
+                            Ok(handshake) => tab_state.dispatch(TabAction::WsOpened {
+                                connection_id,
+                                handshake,
+                            }),
+                            Err(error) => tab_state.dispatch(TabAction::WsClosed(WsClosed {
+                                connection_id,
+                                code: None,
+                                reason: String::new(),
+                                error: Some(error),
+                            })),
+                        }
+                    });
+                }
+            }
+        })
+    };
+
+    let (label, class) = match session.status {
+        WsStatus::Disconnected => ("Connect", "button"),
+        WsStatus::Connecting => ("Connecting...", "button"),
+        WsStatus::Open => ("Disconnect", "button danger"),
+        WsStatus::Closing => ("Closing...", "button danger"),
+    };
+    let busy = matches!(session.status, WsStatus::Connecting | WsStatus::Closing);
+    let locked = session.status != WsStatus::Disconnected;
+
+    html! {
+        <form class="form-row" onsubmit={on_submit}>
+            <div class="request-url">
+                <span class="socket-scheme">{ "WS" }</span>
+                <input
+                    class="url-input"
+                    placeholder="/socket or wss://host/socket"
+                    value={props.content.url.clone()}
+                    oninput={on_url_change}
+                    disabled={locked}
+                />
+            </div>
+            <button type="submit" class={class} disabled={busy} aria-busy={busy.to_string()}>
+                { label }
+            </button>
+        </form>
+    }
+}
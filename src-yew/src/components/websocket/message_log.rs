@@ -0,0 +1,114 @@
+use yew::prelude::*;
+
+use crate::components::response::binary::format_size;
+use crate::components::response::event_stream::format_time;
+use crate::state::{TabAction, TabState, WsMessage, WsSession, WsStatus};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SocketLogProps {
+    pub tab_index: usize,
+    pub session: WsSession,
+}
+
+/// Frames sent and received on a WebSocket tab, newest last.
+#[function_component(SocketLog)]
+pub fn socket_log(props: &SocketLogProps) -> Html {
+    let tab_state = use_context::<UseReducerHandle<TabState>>();
+    let list_ref = use_node_ref();
+
+    {
+        let list_ref = list_ref.clone();
+        use_effect_with(props.session.messages.len(), move |_| {
+            if let Some(list) = list_ref.cast::<web_sys::Element>() {
+                list.set_scroll_top(list.scroll_height());
+            }
+            || ()
+        });
+    }
+
+    let Some(tab_state) = tab_state else {
+        return html! {};
+    };
+    let index = props.tab_index;
+    let session = &props.session;
+
+    let on_clear = Callback::from(move |_event: MouseEvent| {
+        tab_state.dispatch(TabAction::ClearWsMessages(index));
+    });
+
+    let status = match session.status {
+        WsStatus::Disconnected => session
+            .last_close
+            .clone()
+            .unwrap_or_else(|| "Not connected".to_string()),
+        WsStatus::Connecting => "Connecting...".to_string(),
+        WsStatus::Open | WsStatus::Closing => {
+            let mut status = format!("Connected · {} messages", session.messages.len());
+            if let Some(protocol) = session
+                .handshake
+                .as_ref()
+                .and_then(|handshake| handshake.protocol.as_ref())
+            {
+                status.push_str(&format!(" · {protocol}"));
+            }
+            status
+        }
+    };
+    let live = matches!(session.status, WsStatus::Open | WsStatus::Closing);
+    let failed = session.status == WsStatus::Disconnected
+        && session
+            .last_close
+            .as_deref()
+            .is_some_and(|close| !close.starts_with("Closed"));
+
+    html! {
+        <div class="response">
+            <div class="request-title">
+                <h1>{ "Messages" }</h1>
+                <span class={classes!("event-stream-status", live.then_some("live"), failed.then_some("error"))}>
+                    { status }
+                </span>
+                <div class="request-actions">
+                    <button class="button secondary" onclick={on_clear}>{ "Clear" }</button>
+                </div>
+            </div>
+            <div class="response-body">
+                <div class="event-stream-list" ref={list_ref}>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{ "TIME" }</th>
+                                <th>{ "" }</th>
+                                <th>{ "TYPE" }</th>
+                                <th>{ "SIZE" }</th>
+                                <th>{ "DATA" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for session.messages.iter().map(|message| html! {
+                                <tr class={classes!("socket-message", message.direction.clone())}>
+                                    <td class="event-stream-time">{ format_time(message.timestamp) }</td>
+                                    <td class="socket-direction">
+                                        { if message.direction == "out" { "↑" } else { "↓" } }
+                                    </td>
+                                    <td>{ message.kind.clone() }</td>
+                                    <td class="event-stream-time">{ format_size(message.size as u64) }</td>
+                                    <td><pre class="event-stream-data">{ message_data(message) }</pre></td>
+                                </tr>
+                            }) }
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// Close frames show their code next to the reason; other payloads are shown as sent.
+fn message_data(message: &WsMessage) -> String {
+    match message.close_code {
+        Some(code) if message.data.is_empty() => code.to_string(),
+        Some(code) => format!("{code} {}", message.data),
+        None => message.data.clone(),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod composer;
+pub mod connection;
+pub mod message_log;
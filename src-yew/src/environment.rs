@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use crate::state::{
+    BodyMode, Environment, FormField, Header, Param, ServerAuth, ServerEntry, TabContent, TreeState,
+};
+use crate::vault;
+
+/// Values for `{{name}}` placeholders, taken from the active environment. Placeholders
+/// without a value are left as written.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Variables {
+    values: HashMap<String, String>,
+}
+
+impl Variables {
+    pub fn of(environment: Option<&Environment>) -> Self {
+        let values = environment
+            .map(|environment| {
+                environment
+                    .variables
+                    .iter()
+                    .filter(|variable| variable.enable && !variable.key.trim().is_empty())
+                    .map(|variable| (variable.key.trim().to_string(), variable.value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { values }
+    }
+
+    pub fn active(tree: &TreeState) -> Self {
+        Self::of(
+            tree.active_environment
+                .and_then(|index| tree.environments.get(index)),
+        )
+    }
+
+    pub fn interpolate(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some((before, name, after)) = next_placeholder(rest) {
+            result.push_str(before);
+            match self.values.get(name) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[before.len()..rest.len() - after.len()]),
+            }
+            rest = after;
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Names in `text` that have no value, in order of appearance.
+    pub fn unresolved(&self, text: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut rest = text;
+        while let Some((_, name, after)) = next_placeholder(rest) {
+            if !self.values.contains_key(name) && !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+            rest = after;
+        }
+        names
+    }
+
+    pub fn has_unresolved(&self, text: &str) -> bool {
+        !self.unresolved(text).is_empty()
+    }
+
+    /// Names without a value anywhere the request reads from: URL, enabled params and
+    /// headers, and the body of the current mode.
+    pub fn unresolved_in(&self, content: &TabContent) -> Vec<String> {
+        let mut names = Vec::new();
+        for text in sent_texts(content) {
+            for name in self.unresolved(text) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Refuses a send that would carry a vault reference in place of a secret, which is
+    /// what `content` and `server` hold while the vault is locked.
+    pub fn check_vault(
+        &self,
+        content: &TabContent,
+        server: Option<&ServerEntry>,
+    ) -> Result<(), String> {
+        let locked_variable = sent_texts(content).into_iter().any(|text| {
+            let mut rest = text;
+            while let Some((_, name, after)) = next_placeholder(rest) {
+                if self
+                    .values
+                    .get(name)
+                    .is_some_and(|value| vault::is_reference(value))
+                {
+                    return true;
+                }
+                rest = after;
+            }
+            false
+        });
+        let locked_server =
+            server.is_some_and(|server| vault::has_references(&[self.resolve_server(server)], &[]));
+        if locked_variable || locked_server {
+            return Err(
+                "Cofre trancado: desbloqueie o cofre para enviar os segredos guardados nele."
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// `content` with every placeholder it sends replaced.
+    pub fn resolve(&self, content: &TabContent) -> TabContent {
+        let params = |params: &[Param]| {
+            params
+                .iter()
+                .map(|param| Param {
+                    enable: param.enable,
+                    key: self.interpolate(&param.key),
+                    value: self.interpolate(&param.value),
+                })
+                .collect()
+        };
+        TabContent {
+            url: self.interpolate(&content.url),
+            body: self.interpolate(&content.body),
+            form_fields: content
+                .form_fields
+                .iter()
+                .map(|field| FormField {
+                    key: self.interpolate(&field.key),
+                    value: self.interpolate(&field.value),
+                    ..field.clone()
+                })
+                .collect(),
+            body_file: self.interpolate(&content.body_file),
+            graphql_query: self.interpolate(&content.graphql_query),
+            graphql_variables: self.interpolate(&content.graphql_variables),
+            headers: content
+                .headers
+                .iter()
+                .map(|header| Header {
+                    enable: header.enable,
+                    key: self.interpolate(&header.key),
+                    value: self.interpolate(&header.value),
+                })
+                .collect(),
+            params: params(&content.params),
+            path_params: params(&content.path_params),
+            ..content.clone()
+        }
+    }
+
+    /// `server` with placeholders in its credentials replaced.
+    pub fn resolve_server(&self, server: &ServerEntry) -> ServerEntry {
+        let auth = match &server.auth {
+            ServerAuth::ApiKey {
+                name,
+                location,
+                value,
+            } => ServerAuth::ApiKey {
+                name: self.interpolate(name),
+                location: location.clone(),
+                value: self.interpolate(value),
+            },
+            ServerAuth::HttpBasic { username, password } => ServerAuth::HttpBasic {
+                username: self.interpolate(username),
+                password: self.interpolate(password),
+            },
+            ServerAuth::HttpBearer {
+                token,
+                bearer_format,
+                auto_update,
+                token_path,
+            } => ServerAuth::HttpBearer {
+                token: self.interpolate(token),
+                bearer_format: bearer_format.clone(),
+                auto_update: *auto_update,
+                token_path: token_path.clone(),
+            },
+            ServerAuth::OAuth2 {
+                flow,
+                auth_url,
+                token_url,
+                refresh_url,
+                scopes,
+                access_token,
+            } => ServerAuth::OAuth2 {
+                flow: flow.clone(),
+                auth_url: auth_url.clone(),
+                token_url: token_url.clone(),
+                refresh_url: refresh_url.clone(),
+                scopes: scopes.clone(),
+                access_token: self.interpolate(access_token),
+            },
+            ServerAuth::OpenIdConnect { url, access_token } => ServerAuth::OpenIdConnect {
+                url: url.clone(),
+                access_token: self.interpolate(access_token),
+            },
+            ServerAuth::None => ServerAuth::None,
+        };
+        ServerEntry {
+            auth,
+            ..server.clone()
+        }
+    }
+}
+
+/// Copies secret values from `current` into `environments` where the collection file
+/// has them blank, matching on environment name and variable key.
+pub fn keep_secrets(environments: &mut [Environment], current: &[Environment]) {
+    for environment in environments.iter_mut() {
+        let Some(previous) = current
+            .iter()
+            .find(|previous| previous.name == environment.name)
+        else {
+            continue;
+        };
+        for variable in environment
+            .variables
+            .iter_mut()
+            .filter(|variable| variable.secret && variable.value.is_empty())
+        {
+            if let Some(old) = previous
+                .variables
+                .iter()
+                .find(|old| old.key == variable.key)
+            {
+                variable.value = old.value.clone();
+            }
+        }
+    }
+}
+
+/// Splits `text` around its first `{{name}}` placeholder into the text before it, the
+/// trimmed name and the text after it.
+fn next_placeholder(text: &str) -> Option<(&str, &str, &str)> {
+    let mut offset = 0;
+    loop {
+        let start = offset + text[offset..].find("{{")?;
+        let end = start + 2 + text[start + 2..].find("}}")?;
+        let name = text[start + 2..end].trim();
+        if !name.is_empty() && !name.contains(['{', '}', '\n']) {
+            return Some((&text[..start], name, &text[end + 2..]));
+        }
+        offset = start + 2;
+    }
+}
+
+fn sent_texts(content: &TabContent) -> Vec<&str> {
+    let mut texts = vec![content.url.as_str()];
+    texts.extend(
+        content
+            .params
+            .iter()
+            .chain(&content.path_params)
+            .filter(|param| param.enable)
+            .flat_map(|param| [param.key.as_str(), param.value.as_str()]),
+    );
+    texts.extend(
+        content
+            .headers
+            .iter()
+            .filter(|header| header.enable)
+            .flat_map(|header| [header.key.as_str(), header.value.as_str()]),
+    );
+    match content.body_mode {
+        BodyMode::Raw => texts.push(&content.body),
+        BodyMode::UrlEncoded | BodyMode::Multipart => texts.extend(
+            content
+                .form_fields
+                .iter()
+                .filter(|field| field.enable)
+                .flat_map(|field| [field.key.as_str(), field.value.as_str()]),
+        ),
+        BodyMode::Binary => texts.push(&content.body_file),
+        BodyMode::GraphQl => {
+            texts.push(&content.graphql_query);
+            texts.push(&content.graphql_variables);
+        }
+    }
+    texts
+}
@@ -0,0 +1,908 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use graphql_parser::query::{
+    self, Definition, Document, OperationDefinition, Selection, SelectionSet, TypeCondition, Value,
+};
+use graphql_parser::Pos;
+use serde::Deserialize;
+
+/// Standard introspection query, trimmed to what validation and completion use.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { name description type { ...TypeRef } defaultValue }
+        type { ...TypeRef }
+      }
+      inputFields { name description type { ...TypeRef } defaultValue }
+      enumValues(includeDeprecated: true) { name }
+      possibleTypes { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType { kind name }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Schema of a GraphQL server, built from an introspection result.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GraphQlSchema {
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub types: HashMap<String, SchemaType>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaType {
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub fields: Vec<SchemaField>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub input_fields: Vec<InputValue>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub enum_values: Vec<NamedRef>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub possible_types: Vec<NamedRef>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaField {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+    #[serde(default)]
+    pub default_value: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct NamedRef {
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    /// Name of the innermost named type, e.g. `User` for `[User!]!`.
+    pub fn named(&self) -> &str {
+        match (&self.name, &self.of_type) {
+            (Some(name), _) => name,
+            (None, Some(inner)) => inner.named(),
+            (None, None) => "",
+        }
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        self.kind == "NON_NULL"
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind.as_str(), &self.of_type) {
+            ("NON_NULL", Some(inner)) => write!(f, "{inner}!"),
+            ("LIST", Some(inner)) => write!(f, "[{inner}]"),
+            _ => f.write_str(self.named()),
+        }
+    }
+}
+
+fn null_as_empty<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    #[serde(default)]
+    query_type: Option<NamedRef>,
+    #[serde(default)]
+    mutation_type: Option<NamedRef>,
+    #[serde(default)]
+    subscription_type: Option<NamedRef>,
+    types: Vec<SchemaType>,
+}
+
+impl GraphQlSchema {
+    /// Reads an introspection response, either the full `{ "data": ... }` envelope or
+    /// the bare `__schema` object.
+    pub fn from_introspection(body: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(body).map_err(|err| format!("Resposta inválida: {err}"))?;
+        if let Some(message) = value
+            .get("errors")
+            .and_then(|errors| errors.get(0))
+            .and_then(|error| error.get("message"))
+            .and_then(|message| message.as_str())
+        {
+            return Err(format!("Introspection falhou: {message}"));
+        }
+        let schema = value
+            .pointer("/data/__schema")
+            .or_else(|| value.get("__schema"))
+            .cloned()
+            .ok_or_else(|| "A resposta não contém __schema.".to_string())?;
+        let schema: IntrospectionSchema =
+            serde_json::from_value(schema).map_err(|err| format!("Schema inválido: {err}"))?;
+        Ok(Self {
+            query_type: schema.query_type.map(|named| named.name),
+            mutation_type: schema.mutation_type.map(|named| named.name),
+            subscription_type: schema.subscription_type.map(|named| named.name),
+            types: schema
+                .types
+                .into_iter()
+                .map(|ty| (ty.name.clone(), ty))
+                .collect(),
+        })
+    }
+
+    /// Types defined by the server, leaving out the introspection ones.
+    pub fn user_type_count(&self) -> usize {
+        self.types
+            .keys()
+            .filter(|name| !name.starts_with("__"))
+            .count()
+    }
+
+    fn field(&self, type_name: &str, field_name: &str) -> Option<&SchemaField> {
+        self.types
+            .get(type_name)?
+            .fields
+            .iter()
+            .find(|field| field.name == field_name)
+    }
+
+    fn is_composite(&self, type_name: &str) -> bool {
+        self.types
+            .get(type_name)
+            .is_some_and(|ty| matches!(ty.kind.as_str(), "OBJECT" | "INTERFACE" | "UNION"))
+    }
+}
+
+/// JSON body for a GraphQL POST. Variables must be empty or a JSON object.
+pub fn request_body(query: &str, variables: &str) -> Result<String, String> {
+    let mut body = serde_json::Map::new();
+    body.insert("query".to_string(), serde_json::Value::from(query));
+    if let Some(variables) = parse_variables(variables)? {
+        body.insert("variables".to_string(), variables);
+    }
+    if let Some(name) = operation_name(query) {
+        body.insert("operationName".to_string(), serde_json::Value::from(name));
+    }
+    serde_json::to_string(&serde_json::Value::Object(body)).map_err(|err| err.to_string())
+}
+
+/// Query string pairs for a GraphQL GET, following the GraphQL over HTTP convention.
+pub fn query_pairs(query: &str, variables: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = vec![("query".to_string(), query.to_string())];
+    if let Some(variables) = parse_variables(variables)? {
+        pairs.push(("variables".to_string(), variables.to_string()));
+    }
+    if let Some(name) = operation_name(query) {
+        pairs.push(("operationName".to_string(), name));
+    }
+    Ok(pairs)
+}
+
+fn parse_variables(variables: &str) -> Result<Option<serde_json::Value>, String> {
+    if variables.trim().is_empty() {
+        return Ok(None);
+    }
+    let value: serde_json::Value = serde_json::from_str(variables)
+        .map_err(|err| format!("Variables: JSON inválido: {err}"))?;
+    if !value.is_object() {
+        return Err("Variables: esperado um objeto JSON.".to_string());
+    }
+    Ok(Some(value))
+}
+
+/// Name of the operation to run when the document holds several named ones.
+fn operation_name(query: &str) -> Option<String> {
+    let document = query::parse_query::<String>(query).ok()?;
+    let names: Vec<_> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation_parts(operation).name),
+            Definition::Fragment(_) => None,
+        })
+        .collect();
+    match names.as_slice() {
+        [Some(name), _, ..] => Some(name.clone()),
+        _ => None,
+    }
+}
+
+struct OperationParts<'q> {
+    kind: &'static str,
+    name: Option<String>,
+    position: Pos,
+    variables: &'q [query::VariableDefinition<'q, String>],
+    selection_set: &'q SelectionSet<'q, String>,
+}
+
+fn operation_parts<'q>(operation: &'q OperationDefinition<'q, String>) -> OperationParts<'q> {
+    match operation {
+        OperationDefinition::SelectionSet(set) => OperationParts {
+            kind: "query",
+            name: None,
+            position: set.span.0,
+            variables: &[],
+            selection_set: set,
+        },
+        OperationDefinition::Query(op) => OperationParts {
+            kind: "query",
+            name: op.name.clone(),
+            position: op.position,
+            variables: &op.variable_definitions,
+            selection_set: &op.selection_set,
+        },
+        OperationDefinition::Mutation(op) => OperationParts {
+            kind: "mutation",
+            name: op.name.clone(),
+            position: op.position,
+            variables: &op.variable_definitions,
+            selection_set: &op.selection_set,
+        },
+        OperationDefinition::Subscription(op) => OperationParts {
+            kind: "subscription",
+            name: op.name.clone(),
+            position: op.position,
+            variables: &op.variable_definitions,
+            selection_set: &op.selection_set,
+        },
+    }
+}
+
+/// Problems found in `query`, as `line:column: message`. Without a schema only syntax,
+/// fragments and variables are checked.
+pub fn validate(query: &str, schema: Option<&GraphQlSchema>) -> Vec<String> {
+    if query.trim().is_empty() {
+        return vec!["Query vazia.".to_string()];
+    }
+    let document = match query::parse_query::<String>(query) {
+        Ok(document) => document,
+        Err(err) => return vec![err.to_string().trim().to_string()],
+    };
+    let mut validator = Validator {
+        schema,
+        fragments: fragments(&document),
+        errors: Vec::new(),
+        used_variables: HashSet::new(),
+        visiting: HashSet::new(),
+    };
+    validator.document(&document);
+    validator.errors
+}
+
+fn fragments<'q>(
+    document: &'q Document<'q, String>,
+) -> BTreeMap<&'q str, &'q query::FragmentDefinition<'q, String>> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            Definition::Operation(_) => None,
+        })
+        .collect()
+}
+
+struct Validator<'q, 's> {
+    schema: Option<&'s GraphQlSchema>,
+    fragments: BTreeMap<&'q str, &'q query::FragmentDefinition<'q, String>>,
+    errors: Vec<String>,
+    used_variables: HashSet<&'q str>,
+    /// Fragments being walked, so cyclic spreads terminate.
+    visiting: HashSet<&'q str>,
+}
+
+impl<'q> Validator<'q, '_> {
+    fn error(&mut self, position: Pos, message: String) {
+        self.errors
+            .push(format!("{}:{}: {message}", position.line, position.column));
+    }
+
+    fn document(&mut self, document: &'q Document<'q, String>) {
+        let operations: Vec<_> = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Operation(operation) => Some(operation_parts(operation)),
+                Definition::Fragment(_) => None,
+            })
+            .collect();
+        if operations.len() > 1 && operations.iter().any(|operation| operation.name.is_none()) {
+            self.error(
+                operations[0].position,
+                "Operações anônimas devem ser as únicas do documento.".to_string(),
+            );
+        }
+
+        for operation in operations {
+            let root = self.schema.map(|schema| match operation.kind {
+                "mutation" => schema.mutation_type.clone(),
+                "subscription" => schema.subscription_type.clone(),
+                _ => schema.query_type.clone(),
+            });
+            let root = match root {
+                Some(Some(root)) => Some(root),
+                Some(None) => {
+                    self.error(
+                        operation.position,
+                        format!("O schema não define operações {}.", operation.kind),
+                    );
+                    continue;
+                }
+                None => None,
+            };
+
+            self.used_variables.clear();
+            self.selection_set(operation.selection_set, root.as_deref());
+            self.variables(&operation);
+        }
+
+        for fragment in self.fragments.values().copied().collect::<Vec<_>>() {
+            let TypeCondition::On(type_name) = &fragment.type_condition;
+            self.type_condition(fragment.position, type_name);
+        }
+    }
+
+    fn variables(&mut self, operation: &OperationParts<'q>) {
+        let defined: HashSet<&str> = operation
+            .variables
+            .iter()
+            .map(|variable| variable.name.as_str())
+            .collect();
+        let mut used: Vec<_> = self.used_variables.iter().copied().collect();
+        used.sort_unstable();
+        for name in used {
+            if !defined.contains(name) {
+                self.error(
+                    operation.position,
+                    format!("Variável ${name} não declarada."),
+                );
+            }
+        }
+        for variable in operation.variables {
+            if !self.used_variables.contains(variable.name.as_str()) {
+                self.error(
+                    variable.position,
+                    format!("Variável ${} não utilizada.", variable.name),
+                );
+            }
+            let type_name = named_type(&variable.var_type);
+            if let Some(schema) = self.schema {
+                if !schema.types.contains_key(type_name) {
+                    self.error(
+                        variable.position,
+                        format!("Tipo desconhecido \"{type_name}\"."),
+                    );
+                }
+            }
+        }
+    }
+
+    fn type_condition(&mut self, position: Pos, type_name: &str) -> bool {
+        let Some(schema) = self.schema else {
+            return true;
+        };
+        if schema.is_composite(type_name) {
+            return true;
+        }
+        self.error(position, format!("Tipo desconhecido \"{type_name}\"."));
+        false
+    }
+
+    /// Walks a selection set; `parent` is `None` when types cannot be checked.
+    fn selection_set(&mut self, set: &'q SelectionSet<'q, String>, parent: Option<&str>) {
+        for selection in &set.items {
+            match selection {
+                Selection::Field(field) => self.field(field, parent),
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_str();
+                    let Some(fragment) = self.fragments.get(name).copied() else {
+                        self.error(
+                            spread.position,
+                            format!("Fragment \"{name}\" não definido."),
+                        );
+                        continue;
+                    };
+                    if !self.visiting.insert(name) {
+                        continue;
+                    }
+                    let TypeCondition::On(type_name) = &fragment.type_condition;
+                    let known = parent.is_some()
+                        && self
+                            .schema
+                            .is_some_and(|schema| schema.is_composite(type_name));
+                    self.selection_set(
+                        &fragment.selection_set,
+                        known.then_some(type_name.as_str()),
+                    );
+                    self.visiting.remove(name);
+                }
+                Selection::InlineFragment(fragment) => {
+                    let scope = match &fragment.type_condition {
+                        Some(TypeCondition::On(type_name)) => {
+                            let known = self.type_condition(fragment.position, type_name);
+                            known.then_some(type_name.as_str())
+                        }
+                        None => parent,
+                    };
+                    self.selection_set(&fragment.selection_set, parent.and(scope));
+                }
+            }
+        }
+    }
+
+    fn field(&mut self, field: &'q query::Field<'q, String>, parent: Option<&str>) {
+        for (_, value) in &field.arguments {
+            self.collect_variables(value);
+        }
+        for directive in &field.directives {
+            for (_, value) in &directive.arguments {
+                self.collect_variables(value);
+            }
+        }
+
+        let name = field.name.as_str();
+        let (Some(schema), Some(parent)) = (self.schema, parent) else {
+            self.selection_set(&field.selection_set, None);
+            return;
+        };
+        if name == "__typename" {
+            return;
+        }
+        if matches!(name, "__schema" | "__type") && schema.query_type.as_deref() == Some(parent) {
+            self.selection_set(&field.selection_set, None);
+            return;
+        }
+        let Some(definition) = schema.field(parent, name) else {
+            self.error(
+                field.position,
+                format!("Campo \"{name}\" não existe em \"{parent}\"."),
+            );
+            return;
+        };
+
+        for (argument, _) in &field.arguments {
+            if !definition.args.iter().any(|arg| arg.name == *argument) {
+                self.error(
+                    field.position,
+                    format!("Argumento \"{argument}\" não existe em \"{parent}.{name}\"."),
+                );
+            }
+        }
+        for arg in &definition.args {
+            let provided = field
+                .arguments
+                .iter()
+                .any(|(argument, _)| *argument == arg.name);
+            if arg.ty.is_non_null() && arg.default_value.is_none() && !provided {
+                self.error(
+                    field.position,
+                    format!(
+                        "Argumento obrigatório \"{}: {}\" ausente em \"{name}\".",
+                        arg.name, arg.ty
+                    ),
+                );
+            }
+        }
+
+        let type_name = definition.ty.named();
+        let composite = schema.is_composite(type_name);
+        let has_selection = !field.selection_set.items.is_empty();
+        if composite && !has_selection {
+            self.error(
+                field.position,
+                format!(
+                    "Campo \"{name}\" do tipo \"{}\" precisa de uma seleção de subcampos.",
+                    definition.ty
+                ),
+            );
+        } else if !composite && has_selection {
+            self.error(
+                field.position,
+                format!(
+                    "Campo \"{name}\" do tipo \"{}\" não aceita subcampos.",
+                    definition.ty
+                ),
+            );
+        } else if composite {
+            self.selection_set(&field.selection_set, Some(type_name));
+        }
+    }
+
+    fn collect_variables(&mut self, value: &'q Value<'q, String>) {
+        match value {
+            Value::Variable(name) => {
+                self.used_variables.insert(name.as_str());
+            }
+            Value::List(items) => items.iter().for_each(|item| self.collect_variables(item)),
+            Value::Object(fields) => fields
+                .values()
+                .for_each(|item| self.collect_variables(item)),
+            _ => {}
+        }
+    }
+}
+
+fn named_type<'q>(ty: &'q query::Type<'q, String>) -> &'q str {
+    match ty {
+        query::Type::NamedType(name) => name,
+        query::Type::ListType(inner) | query::Type::NonNullType(inner) => named_type(inner),
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompletionItem {
+    pub label: String,
+    pub detail: String,
+}
+
+/// Suggestions for the word ending at `cursor` (a byte offset into the query).
+#[derive(Clone, PartialEq, Debug)]
+pub struct Completion {
+    /// Byte offset where the word being completed starts.
+    pub start: usize,
+    pub items: Vec<CompletionItem>,
+}
+
+#[derive(Debug)]
+enum Scope {
+    /// Inside `{ }` of the named type, or of an unknown one.
+    Selection(Option<String>),
+    /// Inside the argument list of `type.field`.
+    Arguments {
+        parent: Option<String>,
+        field: String,
+    },
+    /// Inside an input object value or a variable list; nothing to suggest.
+    Opaque,
+}
+
+/// Field and argument names valid at `cursor`, filtered by the word typed so far.
+pub fn complete(schema: &GraphQlSchema, query: &str, cursor: usize) -> Option<Completion> {
+    let before = query.get(..cursor)?;
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_name_char(*ch))
+        .last()
+        .map(|(index, _)| index)
+        .unwrap_or(cursor);
+    let prefix = &before[start..];
+    let tokens = tokenize(&before[..start])?;
+    if tokens
+        .last()
+        .is_some_and(|token| token == "$" || token == "...")
+    {
+        return None;
+    }
+
+    let mut scopes: Vec<Scope> = Vec::new();
+    // The field that opens the next `{` or `(`, or the type named after `on`.
+    let mut pending: Option<String> = None;
+    let mut pending_type: Option<String> = None;
+    let mut operation: Option<&str> = None;
+    let mut directive = false;
+    let mut expect_value = false;
+    let mut previous: Option<&str> = None;
+
+    for token in &tokens {
+        let token = token.as_str();
+        match token {
+            "{" => {
+                let scope = match scopes.last() {
+                    None => Scope::Selection(match operation.take() {
+                        Some("mutation") => schema.mutation_type.clone(),
+                        Some("subscription") => schema.subscription_type.clone(),
+                        Some("fragment") => pending_type.take(),
+                        _ => schema.query_type.clone(),
+                    }),
+                    Some(Scope::Selection(parent)) => {
+                        Scope::Selection(match (pending_type.take(), pending.take()) {
+                            (Some(type_name), _) => Some(type_name),
+                            (None, Some(field)) => parent
+                                .as_deref()
+                                .and_then(|parent| schema.field(parent, &field))
+                                .map(|field| field.ty.named().to_string()),
+                            (None, None) => None,
+                        })
+                    }
+                    Some(_) => Scope::Opaque,
+                };
+                scopes.push(scope);
+                expect_value = false;
+            }
+            "(" => {
+                let scope = match (scopes.last(), &pending) {
+                    (Some(Scope::Selection(parent)), Some(field)) if !directive => {
+                        Scope::Arguments {
+                            parent: parent.clone(),
+                            field: field.clone(),
+                        }
+                    }
+                    _ => Scope::Opaque,
+                };
+                scopes.push(scope);
+                directive = false;
+                expect_value = false;
+            }
+            "}" | ")" => {
+                scopes.pop();
+                expect_value = false;
+            }
+            ":" => {
+                // `alias: field` in a selection, `name: value` in arguments.
+                if matches!(scopes.last(), Some(Scope::Selection(_))) {
+                    pending = None;
+                }
+                expect_value = true;
+            }
+            "[" | "]" | "," | "=" | "!" | "@" | "$" | "..." | "|" | "&" => {}
+            _ => match scopes.last() {
+                None if previous == Some("on") => pending_type = Some(token.to_string()),
+                None => {
+                    if matches!(token, "query" | "mutation" | "subscription" | "fragment") {
+                        operation = Some(token);
+                    }
+                }
+                Some(Scope::Selection(_)) => match previous {
+                    Some("on") => pending_type = Some(token.to_string()),
+                    Some("@") => directive = true,
+                    Some("...") => {}
+                    _ if token == "on" => {}
+                    _ => {
+                        pending = Some(token.to_string());
+                        pending_type = None;
+                        directive = false;
+                    }
+                },
+                Some(_) => expect_value = false,
+            },
+        }
+        previous = Some(token);
+    }
+
+    let items: Vec<CompletionItem> = match scopes.last()? {
+        Scope::Selection(parent) => {
+            if previous == Some("...") || previous == Some("on") {
+                return None;
+            }
+            let mut items: Vec<_> = parent
+                .as_deref()
+                .and_then(|parent| schema.types.get(parent))
+                .map(|ty| {
+                    ty.fields
+                        .iter()
+                        .map(|field| CompletionItem {
+                            label: field.name.clone(),
+                            detail: field_signature(field),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            items.push(CompletionItem {
+                label: "__typename".to_string(),
+                detail: "String!".to_string(),
+            });
+            items
+        }
+        Scope::Arguments { parent, field } => {
+            let definition = parent
+                .as_deref()
+                .and_then(|parent| schema.field(parent, field))?;
+            if expect_value {
+                let arg_name = argument_before(&tokens)?;
+                let arg = definition.args.iter().find(|arg| arg.name == arg_name)?;
+                value_suggestions(schema, &arg.ty)
+            } else {
+                definition
+                    .args
+                    .iter()
+                    .map(|arg| CompletionItem {
+                        label: arg.name.clone(),
+                        detail: arg.ty.to_string(),
+                    })
+                    .collect()
+            }
+        }
+        Scope::Opaque => return None,
+    };
+
+    let items: Vec<_> = items
+        .into_iter()
+        .filter(|item| item.label.starts_with(prefix) && item.label != prefix)
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+    Some(Completion { start, items })
+}
+
+/// The argument whose value is being typed: the name before the last `:`.
+fn argument_before(tokens: &[String]) -> Option<&str> {
+    let colon = tokens.iter().rposition(|token| token == ":")?;
+    tokens.get(colon.checked_sub(1)?).map(String::as_str)
+}
+
+fn value_suggestions(schema: &GraphQlSchema, ty: &TypeRef) -> Vec<CompletionItem> {
+    let type_name = ty.named();
+    match schema.types.get(type_name) {
+        Some(schema_type) if schema_type.kind == "ENUM" => schema_type
+            .enum_values
+            .iter()
+            .map(|value| CompletionItem {
+                label: value.name.clone(),
+                detail: type_name.to_string(),
+            })
+            .collect(),
+        _ if type_name == "Boolean" => ["true", "false"]
+            .iter()
+            .map(|value| CompletionItem {
+                label: value.to_string(),
+                detail: "Boolean".to_string(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn field_signature(field: &SchemaField) -> String {
+    if field.args.is_empty() {
+        return field.ty.to_string();
+    }
+    let args = field
+        .args
+        .iter()
+        .map(|arg| format!("{}: {}", arg.name, arg.ty))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("({args}): {}", field.ty)
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+/// Splits GraphQL source into names and punctuators, dropping strings, numbers and
+/// comments. Returns `None` when the text ends inside a string or comment.
+fn tokenize(source: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            // Comments run to the end of the line.
+            '#' if !chars.by_ref().any(|next| next == '\n') => return None,
+            '"' => {
+                let block = chars.peek() == Some(&'"') && {
+                    let rest: String = chars.clone().take(2).collect();
+                    rest == "\"\""
+                };
+                if block {
+                    chars.next();
+                    chars.next();
+                    let mut quotes = 0;
+                    loop {
+                        match chars.next()? {
+                            '"' => {
+                                quotes += 1;
+                                if quotes == 3 {
+                                    break;
+                                }
+                            }
+                            _ => quotes = 0,
+                        }
+                    }
+                } else {
+                    loop {
+                        match chars.next()? {
+                            '\\' => {
+                                chars.next()?;
+                            }
+                            '"' => break,
+                            '\n' => return None,
+                            _ => {}
+                        }
+                    }
+                }
+                tokens.push("\"\"".to_string());
+            }
+            '.' if chars.peek() == Some(&'.') => {
+                chars.next();
+                chars.next();
+                tokens.push("...".to_string());
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '=' | '!' | '@' | '$' | ',' | '|' | '&' => {
+                tokens.push(ch.to_string())
+            }
+            _ if ch.is_ascii_alphabetic() || ch == '_' => {
+                let mut name = ch.to_string();
+                while let Some(next) = chars.peek().copied().filter(|next| is_name_char(*next)) {
+                    name.push(next);
+                    chars.next();
+                }
+                tokens.push(name);
+            }
+            _ if ch.is_ascii_digit() || ch == '-' => {
+                while chars.peek().is_some_and(|next| {
+                    next.is_ascii_alphanumeric() || matches!(next, '.' | '+' | '-')
+                }) {
+                    chars.next();
+                }
+                tokens.push("0".to_string());
+            }
+            _ => {}
+        }
+    }
+    Some(tokens)
+}
@@ -0,0 +1,129 @@
+use js_sys::Date;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{HistorySummary, RequestDebugInfo, Response, ServerEntry, TabContent};
+
+/// Body bytes kept per recorded response; the rest is cut so the history file stays
+/// small enough to list and search.
+const BODY_LIMIT: usize = 64 * 1024;
+
+/// Stands in for credentials in recorded requests.
+const REDACTED: &str = "[redacted]";
+
+/// Headers whose whole value is a credential, however it was encoded.
+const SECRET_HEADERS: [&str; 3] = ["authorization", "proxy-authorization", "cookie"];
+
+/// A send as written to the history store.
+#[derive(Serialize)]
+pub struct HistoryRecord {
+    id: String,
+    summary: HistorySummary,
+    detail: HistoryDetail,
+}
+
+/// What was sent and what came back, read when an entry is opened or resent.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryDetail {
+    pub content: TabContent,
+    pub response: Response,
+}
+
+impl HistoryRecord {
+    /// `secrets` are the credential and secret variable values the send used; they
+    /// are masked wherever the recorded request repeats them.
+    pub fn new(
+        id: String,
+        tab_label: &str,
+        content: &TabContent,
+        server: Option<&ServerEntry>,
+        response: &Response,
+        secrets: &[String],
+    ) -> Self {
+        let response = recorded(response, secrets);
+        let summary = HistorySummary {
+            id: id.clone(),
+            timestamp: Date::now(),
+            method: content.method,
+            url: response
+                .request
+                .as_ref()
+                .map(|request| request.url.clone())
+                .unwrap_or_else(|| content.url.clone()),
+            status: response.status,
+            ok: response.ok,
+            duration_ms: response.duration_ms,
+            server_url: server.map(|server| server.url.clone()),
+            tab_label: tab_label.to_string(),
+        };
+        let content = TabContent {
+            response: Response::default(),
+            ..content.clone()
+        };
+        Self {
+            id,
+            summary,
+            detail: HistoryDetail { content, response },
+        }
+    }
+
+    pub fn summary(&self) -> &HistorySummary {
+        &self.summary
+    }
+}
+
+impl HistoryDetail {
+    pub fn from_value(value: serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(value).map_err(|err| format!("Histórico inválido: {err}"))
+    }
+}
+
+/// The response as kept in history: spooled files do not outlive the session, large
+/// bodies are cut to `BODY_LIMIT` and credentials in the sent request are redacted.
+fn recorded(response: &Response, secrets: &[String]) -> Response {
+    let redact = |text: &str| {
+        secrets
+            .iter()
+            .filter(|secret| !secret.is_empty())
+            .fold(text.to_string(), |text, secret| {
+                text.replace(secret.as_str(), REDACTED)
+            })
+    };
+    let request = response.request.as_ref().map(|request| RequestDebugInfo {
+        method: request.method.clone(),
+        url: redact(&request.url),
+        headers: request
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if SECRET_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                    REDACTED.to_string()
+                } else {
+                    redact(value)
+                };
+                (name.clone(), value)
+            })
+            .collect(),
+        body: request.body.as_deref().map(redact),
+    });
+    let mut data = response.data.clone();
+    if data.len() > BODY_LIMIT {
+        let mut end = BODY_LIMIT;
+        while !data.is_char_boundary(end) {
+            end -= 1;
+        }
+        data.truncate(end);
+    }
+    Response {
+        data,
+        body_base64: response
+            .body_base64
+            .clone()
+            .filter(|body| body.len() <= BODY_LIMIT),
+        body_file: None,
+        streaming: false,
+        url: redact(&response.url),
+        request,
+        ..response.clone()
+    }
+}
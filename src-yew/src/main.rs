@@ -1,9 +1,16 @@
 mod app;
+mod collection;
 mod components;
+mod environment;
+mod graphql;
+mod history;
 mod openapi;
 mod state;
 mod tauri_api;
+mod undo;
 mod utils;
+mod vault;
+mod workspace;
 
 fn main() {
     yew::Renderer::<app::App>::new().render();
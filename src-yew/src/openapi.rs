@@ -4,8 +4,9 @@ use serde_json::{json, Map, Value};
 use url::Url;
 
 use crate::state::{
-    ApiKeyLocation, Header, MethodEnum, OAuth2Flow, OAuthScope, Param, ServerAuth, ServerEntry,
-    TabContent, TreeNode,
+    add_to_folder, ApiKeyLocation, BodyMode, ContentEncoding, FormField, Header, HttpVersion, MethodEnum,
+    OAuth2Flow, OAuthScope, Param, ProxyConfig, RedirectPolicy, RequestSettings, RetryPolicy,
+    ServerAuth, ServerEntry, ServerProxy, ServerSettings, TabContent, TlsSettings, TreeNode,
 };
 
 pub fn build_tree_from_openapi(text: &str) -> Result<(TreeNode, Vec<ServerEntry>), String> {
@@ -46,6 +47,8 @@ pub fn build_tree_from_openapi(text: &str) -> Result<(TreeNode, Vec<ServerEntry>
             Some(ServerEntry {
                 url: url.to_string(),
                 auth,
+                settings: server_settings_from_value(server),
+                graphql_schema: None,
             })
         })
         .collect();
@@ -53,13 +56,18 @@ pub fn build_tree_from_openapi(text: &str) -> Result<(TreeNode, Vec<ServerEntry>
     let mut servers = server_list;
     if servers.is_empty() && !paths.is_empty() {
         servers.push(ServerEntry {
-            url: "http://localhost".to_string(),
             auth: default_auth.unwrap_or(ServerAuth::None),
+            ..ServerEntry::new("http://localhost".to_string())
         });
     }
 
-    let mut tag_map: std::collections::BTreeMap<String, Vec<TreeNode>> =
-        std::collections::BTreeMap::new();
+    // Holds the folders only; requests outside any folder stay in `root_nodes`.
+    let mut folder_root = TreeNode {
+        label: String::new(),
+        content: None,
+        expanded: true,
+        children: Vec::new(),
+    };
     let mut root_nodes: Vec<TreeNode> = Vec::new();
     let mut path_entries: Vec<_> = paths.iter().collect();
     path_entries.sort_by(|(a, _), (b, _)| a.cmp(b));
@@ -79,35 +87,20 @@ pub fn build_tree_from_openapi(text: &str) -> Result<(TreeNode, Vec<ServerEntry>
                 expanded: false,
                 children: Vec::new(),
             };
-            let tag_label = method_value
-                .get("tags")
-                .and_then(|value| value.as_array())
-                .and_then(|tags| tags.first())
-                .and_then(|value| value.as_str())
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty());
-
-            if let Some(tag_label) = tag_label {
-                tag_map.entry(tag_label.to_string()).or_default().push(node);
-            } else {
+            let folders = operation_folders(method_value);
+            if folders.is_empty() {
                 root_nodes.push(node);
+            } else {
+                add_to_folder(&mut folder_root, &folders, Some(node));
             }
         }
     }
-
-    let mut tag_nodes: Vec<TreeNode> = tag_map
-        .into_iter()
-        .map(|(label, children)| TreeNode {
-            label,
-            content: None,
-            expanded: true,
-            children,
-        })
-        .collect();
+    let mut folder_nodes = folder_root.children;
+    folder_nodes.sort_by(|a, b| a.label.cmp(&b.label));
 
     let mut children = Vec::new();
     children.append(&mut root_nodes);
-    children.append(&mut tag_nodes);
+    children.append(&mut folder_nodes);
 
     Ok((
         TreeNode {
@@ -120,6 +113,35 @@ pub fn build_tree_from_openapi(text: &str) -> Result<(TreeNode, Vec<ServerEntry>
     ))
 }
 
+/// Folder labels an operation sits under: `x-rustman-folder` for nested folders,
+/// otherwise its first tag.
+fn operation_folders(method_value: &Value) -> Vec<String> {
+    let folders: Vec<String> = method_value
+        .get("x-rustman-folder")
+        .and_then(|value| value.as_array())
+        .map(|folders| {
+            folders
+                .iter()
+                .filter_map(|value| value.as_str())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !folders.is_empty() {
+        return folders;
+    }
+    method_value
+        .get("tags")
+        .and_then(|value| value.as_array())
+        .and_then(|tags| tags.first())
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| vec![value.to_string()])
+        .unwrap_or_default()
+}
+
 pub fn build_openapi_from_tree(
     root: &TreeNode,
     servers: &[ServerEntry],
@@ -137,16 +159,250 @@ fn convert_content(
     let body = extract_body(method_value, root);
     let path_params = extract_path_params(path_key);
     let headers = extract_headers(method_value, root);
+    let settings = request_settings_from_value(method_value);
+    let body_file = extract_body_file(method_value, root);
+    let graphql = extract_graphql_body(method_value, root);
+    let (body_mode, form_fields) = match (&body_file, &graphql) {
+        (Some(_), _) => (BodyMode::Binary, vec![FormField::empty()]),
+        (None, Some(_)) => (BodyMode::GraphQl, vec![FormField::empty()]),
+        (None, None) => extract_form_body(method_value, root)
+            .unwrap_or_else(|| (BodyMode::Raw, vec![FormField::empty()])),
+    };
+    let (graphql_query, graphql_variables) = graphql.unwrap_or_default();
     TabContent {
         url: path,
         method,
-        body,
+        body: if body_mode == BodyMode::Raw { body } else { String::new() },
+        body_mode,
+        form_fields,
+        body_file: body_file.unwrap_or_default(),
+        graphql_query,
+        graphql_variables,
         path_params,
         headers,
+        settings,
         ..TabContent::default()
     }
 }
 
+/// Query and variables of a GraphQL body: a JSON example flagged with
+/// `x-rustman-graphql` on the media type.
+fn extract_graphql_body(method_value: &Value, root: &Value) -> Option<(String, String)> {
+    let request_body = method_value.get("requestBody")?;
+    let request_body = resolve_ref(request_body, root, 0).unwrap_or(request_body);
+    let content_value = request_body.get("content")?.get("application/json")?;
+    if content_value.get("x-rustman-graphql").and_then(|value| value.as_bool()) != Some(true) {
+        return None;
+    }
+    let example = content_value.get("example");
+    let query = example
+        .and_then(|example| example.get("query"))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let variables = example
+        .and_then(|example| example.get("variables"))
+        .filter(|value| !value.is_null())
+        .and_then(|value| serde_json::to_string_pretty(value).ok())
+        .unwrap_or_default();
+    Some((query, variables))
+}
+
+/// Path of a binary request body (`type: string, format: binary` schema that is not a
+/// form), stored in `x-rustman-path` on the media type.
+fn extract_body_file(method_value: &Value, root: &Value) -> Option<String> {
+    let request_body = method_value.get("requestBody")?;
+    let request_body = resolve_ref(request_body, root, 0).unwrap_or(request_body);
+    let content = request_body.get("content")?.as_object()?;
+    let (media_type, content_value) = select_content_entry(content)?;
+    if media_type.starts_with("multipart/")
+        || media_type == "application/x-www-form-urlencoded"
+    {
+        return None;
+    }
+    let schema = content_value.get("schema")?;
+    let schema = resolve_ref(schema, root, 0).unwrap_or(schema);
+    if schema.get("format").and_then(|value| value.as_str()) != Some("binary") {
+        return None;
+    }
+    Some(
+        content_value
+            .get("x-rustman-path")
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// Reads a form request body (no JSON alternative offered) into form fields. File
+/// parts are `format: binary` properties; their local path travels in `x-rustman-path`.
+fn extract_form_body(method_value: &Value, root: &Value) -> Option<(BodyMode, Vec<FormField>)> {
+    let request_body = method_value.get("requestBody")?;
+    let request_body = resolve_ref(request_body, root, 0).unwrap_or(request_body);
+    let content = request_body.get("content")?.as_object()?;
+    if content.contains_key("application/json") {
+        return None;
+    }
+    let (mode, content_value) = [BodyMode::Multipart, BodyMode::UrlEncoded]
+        .into_iter()
+        .find_map(|mode| {
+            content
+                .get(mode.media_type()?)
+                .map(|content_value| (mode, content_value))
+        })?;
+    let schema = content_value.get("schema")?;
+    let schema = resolve_ref(schema, root, 0).unwrap_or(schema);
+    let properties = schema.get("properties")?.as_object()?;
+    let example = content_value.get("example");
+
+    let fields: Vec<FormField> = properties
+        .iter()
+        .map(|(name, property)| {
+            let property = resolve_ref(property, root, 0).unwrap_or(property);
+            let file = mode == BodyMode::Multipart
+                && property.get("format").and_then(|value| value.as_str()) == Some("binary");
+            let value = if file {
+                property.get("x-rustman-path")
+            } else {
+                example
+                    .and_then(|example| example.get(name))
+                    .or_else(|| property.get("example"))
+                    .or_else(|| property.get("default"))
+            };
+            FormField {
+                enable: true,
+                key: name.clone(),
+                value: value.map(value_to_string).unwrap_or_default(),
+                file,
+            }
+        })
+        .collect();
+    if fields.is_empty() {
+        return Some((mode, vec![FormField::empty()]));
+    }
+    Some((mode, fields))
+}
+
+fn request_settings_from_value(method_value: &Value) -> RequestSettings {
+    RequestSettings {
+        timeout_ms: method_value
+            .get("x-rustman-timeout")
+            .and_then(|value| value.as_u64()),
+        redirect: method_value
+            .get("x-rustman-redirects")
+            .map(RedirectPolicy::from_extension)
+            .unwrap_or_default(),
+        http_version: http_version_from_value(method_value),
+        decode_disabled: method_value
+            .get("x-rustman-decode-disabled")
+            .and_then(|value| value.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str())
+                    .filter_map(ContentEncoding::from_token)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        compress_body: method_value
+            .get("x-rustman-compress-body")
+            .and_then(|value| value.as_str())
+            .and_then(ContentEncoding::from_token),
+    }
+}
+
+fn http_version_from_value(value: &Value) -> Option<HttpVersion> {
+    value
+        .get("x-rustman-http-version")
+        .and_then(|value| value.as_str())
+        .and_then(HttpVersion::from_key)
+}
+
+fn server_settings_from_value(server: &Value) -> ServerSettings {
+    ServerSettings {
+        timeout_ms: server
+            .get("x-rustman-timeout")
+            .and_then(|value| value.as_u64()),
+        http_version: http_version_from_value(server).unwrap_or_default(),
+        unix_socket: server
+            .get("x-rustman-unix-socket")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        retry: server
+            .get("x-rustman-retry")
+            .and_then(|value| serde_json::from_value::<RetryPolicy>(value.clone()).ok())
+            .unwrap_or_default(),
+        tls: server
+            .get("x-rustman-tls")
+            .map(tls_from_extension)
+            .unwrap_or_default(),
+        proxy: server
+            .get("x-rustman-proxy")
+            .map(proxy_from_extension)
+            .unwrap_or_default(),
+    }
+}
+
+fn proxy_from_extension(value: &Value) -> ServerProxy {
+    match value.get("mode").and_then(|value| value.as_str()) {
+        Some("direct") => ServerProxy::Direct,
+        Some("custom") => ServerProxy::Custom(
+            serde_json::from_value::<ProxyConfig>(value.clone()).unwrap_or_default(),
+        ),
+        _ => ServerProxy::Inherit,
+    }
+}
+
+fn proxy_to_extension(proxy: &ServerProxy) -> Option<Value> {
+    match proxy {
+        ServerProxy::Inherit => None,
+        proxy => serde_json::to_value(proxy).ok(),
+    }
+}
+
+fn tls_from_extension(value: &Value) -> TlsSettings {
+    let text = |key: &str| {
+        value
+            .get(key)
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    TlsSettings {
+        ca_cert_path: text("caCert"),
+        client_cert_path: text("clientCert"),
+        client_key_path: text("clientKey"),
+        pkcs12_path: text("pkcs12"),
+        pkcs12_password: text("pkcs12Password"),
+        allow_invalid_certs: value
+            .get("allowInvalidCerts")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false),
+    }
+}
+
+fn tls_to_extension(tls: &TlsSettings) -> Option<Value> {
+    if tls.is_default() {
+        return None;
+    }
+    let mut map = Map::new();
+    for (key, value) in [
+        ("caCert", &tls.ca_cert_path),
+        ("clientCert", &tls.client_cert_path),
+        ("clientKey", &tls.client_key_path),
+        ("pkcs12", &tls.pkcs12_path),
+        ("pkcs12Password", &tls.pkcs12_password),
+    ] {
+        if !value.is_empty() {
+            map.insert(key.to_string(), Value::String(value.clone()));
+        }
+    }
+    if tls.allow_invalid_certs {
+        map.insert("allowInvalidCerts".to_string(), Value::Bool(true));
+    }
+    Some(Value::Object(map))
+}
+
 fn extract_body(method_value: &Value, root: &Value) -> String {
     let Some(request_body) = method_value.get("requestBody") else {
         return String::new();
@@ -421,7 +677,7 @@ fn build_openapi_from_tree_nodes(
     let mut seen = HashSet::new();
 
     let mut push_operation =
-        |node: &TreeNode, content: &TabContent, tag_label: Option<&str>| {
+        |node: &TreeNode, content: &TabContent, folders: &[String]| {
             let path_key = normalize_path(&strip_query(&content.url));
             let method_key = content.method.key().to_string();
             let dedupe_key = (method_key.clone(), path_key.clone());
@@ -431,12 +687,16 @@ fn build_openapi_from_tree_nodes(
 
             let mut operation = Map::new();
             operation.insert("summary".to_string(), Value::String(node.label.clone()));
-            if let Some(tag_label) = tag_label {
+            // The top folder is the tag; deeper folders only survive in the extension.
+            if let Some(tag_label) = folders.first() {
                 if !tag_label.trim().is_empty() {
                     tag_names.insert(tag_label.to_string());
                     operation.insert("tags".to_string(), json!([tag_label]));
                 }
             }
+            if folders.len() > 1 {
+                operation.insert("x-rustman-folder".to_string(), json!(folders));
+            }
 
         let parameters = build_parameters(content);
         if !parameters.is_empty() {
@@ -447,6 +707,34 @@ fn build_openapi_from_tree_nodes(
             operation.insert("requestBody".to_string(), request_body);
         }
 
+        if let Some(timeout_ms) = content.settings.timeout_ms {
+            operation.insert("x-rustman-timeout".to_string(), json!(timeout_ms));
+        }
+        if let Some(redirects) = content.settings.redirect.to_extension() {
+            operation.insert("x-rustman-redirects".to_string(), redirects);
+        }
+        if !content.settings.decode_disabled.is_empty() {
+            let disabled: Vec<&str> = content
+                .settings
+                .decode_disabled
+                .iter()
+                .map(|encoding| encoding.token())
+                .collect();
+            operation.insert("x-rustman-decode-disabled".to_string(), json!(disabled));
+        }
+        if let Some(encoding) = content.settings.compress_body {
+            operation.insert(
+                "x-rustman-compress-body".to_string(),
+                Value::String(encoding.token().to_string()),
+            );
+        }
+        if let Some(version) = content.settings.http_version {
+            operation.insert(
+                "x-rustman-http-version".to_string(),
+                Value::String(version.key().to_string()),
+            );
+        }
+
         operation.insert("responses".to_string(), json!({ "200": { "description": "OK" } }));
 
         let path_item = paths
@@ -457,19 +745,10 @@ fn build_openapi_from_tree_nodes(
         }
     };
 
-    for node in &root.children {
-        if let Some(content) = node.content.as_ref() {
-            push_operation(node, content, None);
-            continue;
-        }
-
-        let tag_label = node.label.clone();
-        for child in &node.children {
-            let Some(content) = child.content.as_ref() else {
-                continue;
-            };
-            push_operation(child, content, Some(&tag_label));
-        }
+    let mut requests = Vec::new();
+    collect_requests(root, &mut Vec::new(), &mut requests);
+    for (node, content, folders) in requests {
+        push_operation(node, content, &folders);
     }
 
     if paths.is_empty() {
@@ -540,6 +819,24 @@ fn build_openapi_from_tree_nodes(
     serde_yaml::to_string(&Value::Object(doc)).map_err(|err| err.to_string())
 }
 
+/// Requests below `node` in tree order, each with the labels of the folders holding it.
+fn collect_requests<'a>(
+    node: &'a TreeNode,
+    folders: &mut Vec<String>,
+    requests: &mut Vec<(&'a TreeNode, &'a TabContent, Vec<String>)>,
+) {
+    for child in &node.children {
+        match child.content.as_ref() {
+            Some(content) => requests.push((child, content, folders.clone())),
+            None => {
+                folders.push(child.label.clone());
+                collect_requests(child, folders, requests);
+                folders.pop();
+            }
+        }
+    }
+}
+
 fn build_parameters(content: &TabContent) -> Vec<Value> {
     let mut parameters = Vec::new();
     let mut seen = HashSet::new();
@@ -646,6 +943,15 @@ fn push_parameter(
 }
 
 fn build_request_body(content: &TabContent) -> Option<Value> {
+    if let Some(media_type) = content.body_mode.media_type() {
+        return build_form_request_body(content, media_type);
+    }
+    if content.body_mode == BodyMode::Binary {
+        return build_file_request_body(content);
+    }
+    if content.body_mode == BodyMode::GraphQl {
+        return build_graphql_request_body(content);
+    }
     let body = content.body.trim();
     if body.is_empty() {
         return None;
@@ -673,6 +979,94 @@ fn build_request_body(content: &TabContent) -> Option<Value> {
 }
 
 
+fn build_graphql_request_body(content: &TabContent) -> Option<Value> {
+    let query = content.graphql_query.trim();
+    if query.is_empty() {
+        return None;
+    }
+    let mut example = Map::new();
+    example.insert("query".to_string(), Value::String(query.to_string()));
+    if let Ok(variables) = serde_json::from_str::<Value>(&content.graphql_variables) {
+        example.insert("variables".to_string(), variables);
+    }
+
+    Some(json!({
+        "required": false,
+        "content": {
+            "application/json": {
+                "example": example,
+                "x-rustman-graphql": true
+            }
+        }
+    }))
+}
+
+fn build_file_request_body(content: &TabContent) -> Option<Value> {
+    let path = content.body_file.trim();
+    if path.is_empty() {
+        return None;
+    }
+    let media_type = content
+        .headers
+        .iter()
+        .find(|header| header.enable && header.key.trim().eq_ignore_ascii_case("content-type"))
+        .map(|header| header.value.trim())
+        .filter(|value| !value.is_empty())
+        .unwrap_or("application/octet-stream");
+
+    Some(json!({
+        "required": false,
+        "content": {
+            media_type: {
+                "schema": {
+                    "type": "string",
+                    "format": "binary"
+                },
+                "x-rustman-path": path
+            }
+        }
+    }))
+}
+
+fn build_form_request_body(content: &TabContent, media_type: &str) -> Option<Value> {
+    let allow_files = content.body_mode == BodyMode::Multipart;
+    let mut properties = Map::new();
+    for field in content.form_fields.iter().filter(|field| field.enable) {
+        let key = field.key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let property = if allow_files && field.file {
+            json!({
+                "type": "string",
+                "format": "binary",
+                "x-rustman-path": field.value
+            })
+        } else {
+            json!({
+                "type": "string",
+                "example": field.value
+            })
+        };
+        properties.insert(key.to_string(), property);
+    }
+    if properties.is_empty() {
+        return None;
+    }
+
+    Some(json!({
+        "required": false,
+        "content": {
+            media_type: {
+                "schema": {
+                    "type": "object",
+                    "properties": properties
+                }
+            }
+        }
+    }))
+}
+
 fn parse_query_pairs(value: &str) -> Vec<(String, String)> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -718,6 +1112,33 @@ fn server_to_value(server: &ServerEntry) -> Value {
     if let Some(auth) = auth_to_extension(&server.auth) {
         map.insert("x-rustman-auth".to_string(), auth);
     }
+    if let Some(timeout_ms) = server.settings.timeout_ms {
+        map.insert("x-rustman-timeout".to_string(), json!(timeout_ms));
+    }
+    if server.settings.http_version != HttpVersion::Auto {
+        map.insert(
+            "x-rustman-http-version".to_string(),
+            Value::String(server.settings.http_version.key().to_string()),
+        );
+    }
+    if let Some(socket) = server.settings.unix_socket.as_ref() {
+        map.insert(
+            "x-rustman-unix-socket".to_string(),
+            Value::String(socket.clone()),
+        );
+    }
+    if server.settings.retry.is_enabled() {
+        map.insert(
+            "x-rustman-retry".to_string(),
+            json!(server.settings.retry),
+        );
+    }
+    if let Some(tls) = tls_to_extension(&server.settings.tls) {
+        map.insert("x-rustman-tls".to_string(), tls);
+    }
+    if let Some(proxy) = proxy_to_extension(&server.settings.proxy) {
+        map.insert("x-rustman-proxy".to_string(), proxy);
+    }
     Value::Object(map)
 }
 
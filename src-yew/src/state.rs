@@ -3,15 +3,21 @@ use std::rc::Rc;
 use yew::prelude::*;
 use url::Url;
 
+use crate::graphql::GraphQlSchema;
+use crate::undo::{UndoStack, UndoStep};
+
+/// Request headers in the order they are sent; a name may appear more than once.
+pub type HeaderList = Vec<(String, String)>;
+
 #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct RequestDebugInfo {
     pub method: String,
     pub url: String,
-    pub headers: HashMap<String, String>,
+    pub headers: HeaderList,
     pub body: Option<String>,
 }
 
-#[derive(Clone, PartialEq, Debug, serde::Deserialize)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Response {
     pub url: String,
     pub status: u16,
@@ -20,11 +26,82 @@ pub struct Response {
     pub raw_headers: HashMap<String, Vec<String>>,
     pub data: String,
     #[serde(default)]
+    pub body_base64: Option<String>,
+    #[serde(default)]
+    pub body_kind: BodyKind,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Body size on the wire, before decoding.
+    #[serde(default)]
+    pub wire_size: Option<u64>,
+    /// Coding the backend decoded the body from.
+    #[serde(default)]
+    pub content_encoding: Option<String>,
+    /// Temp file with the full body when it was too large to keep in memory; the
+    /// body fields then only hold a preview.
+    #[serde(default)]
+    pub body_file: Option<String>,
+    #[serde(default)]
     pub formatted: bool,
     #[serde(default)]
     pub duration_ms: Option<u64>,
     #[serde(default)]
     pub request: Option<RequestDebugInfo>,
+    #[serde(default)]
+    pub redirects: Vec<RedirectHop>,
+    #[serde(default)]
+    pub attempts: Vec<RetryAttempt>,
+    #[serde(default)]
+    pub timing: Option<ResponseTiming>,
+    /// Protocol the response arrived over, e.g. `HTTP/2.0`.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// The body is an event stream delivered through `EventStream` instead of `data`.
+    #[serde(default)]
+    pub streaming: bool,
+}
+
+/// Network phase durations measured by the backend, in milliseconds.
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTiming {
+    #[serde(default)]
+    pub dns_ms: Option<f64>,
+    #[serde(default)]
+    pub connect_ms: Option<f64>,
+    #[serde(default)]
+    pub tls_ms: Option<f64>,
+    pub ttfb_ms: f64,
+    pub download_ms: f64,
+    #[serde(default)]
+    pub redirect_ms: f64,
+    pub total_ms: f64,
+    #[serde(default)]
+    pub reused_connection: bool,
+}
+
+/// An attempt the backend retried, with what went wrong and how long it waited after.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryAttempt {
+    pub url: String,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    pub delay_ms: u64,
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status: u16,
+    pub location: String,
+    #[serde(default)]
+    pub headers: HashMap<String, Vec<String>>,
 }
 
 impl Default for Response {
@@ -36,14 +113,57 @@ impl Default for Response {
             headers: HashMap::new(),
             raw_headers: HashMap::new(),
             data: String::new(),
+            body_base64: None,
+            body_kind: BodyKind::Text,
+            content_type: None,
+            size: None,
+            wire_size: None,
+            content_encoding: None,
+            body_file: None,
             formatted: false,
             duration_ms: None,
             request: None,
+            redirects: Vec::new(),
+            attempts: Vec::new(),
+            timing: None,
+            version: None,
+            streaming: false,
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyKind {
+    #[default]
+    Text,
+    Image,
+    Pdf,
+    Binary,
+}
+
+/// A cookie held in a server's persistent jar on the backend.
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    #[serde(default)]
+    pub host_only: bool,
+    pub path: String,
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub same_site: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum MethodEnum {
     Get,
     Post,
@@ -55,10 +175,16 @@ pub enum MethodEnum {
     Trace,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ServerEntry {
     pub url: String,
+    #[serde(default)]
     pub auth: ServerAuth,
+    #[serde(default)]
+    pub settings: ServerSettings,
+    /// Result of the last GraphQL introspection against this server.
+    #[serde(skip)]
+    pub graphql_schema: Option<Rc<GraphQlSchema>>,
 }
 
 impl ServerEntry {
@@ -66,11 +192,281 @@ impl ServerEntry {
         Self {
             url,
             auth: ServerAuth::None,
+            settings: ServerSettings::default(),
+            graphql_schema: None,
         }
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+/// Named set of values for `{{name}}` placeholders, e.g. one per deployment.
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Environment {
+    pub name: String,
+    pub variables: Vec<EnvVariable>,
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EnvVariable {
+    pub enable: bool,
+    pub key: String,
+    pub value: String,
+    /// Masked in the editor and kept in the vault when the collection is saved.
+    pub secret: bool,
+}
+
+impl Default for EnvVariable {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            key: String::new(),
+            value: String::new(),
+            secret: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerSettings {
+    pub timeout_ms: Option<u64>,
+    pub http_version: HttpVersion,
+    /// Unix domain socket to dial instead of the URL's host, e.g. `/var/run/docker.sock`.
+    pub unix_socket: Option<String>,
+    pub retry: RetryPolicy,
+    pub tls: TlsSettings,
+    pub proxy: ServerProxy,
+}
+
+/// Automatic retries the backend makes for a server's requests. A single attempt
+/// means retries are off; the other fields keep their values so they can be re-enabled.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub statuses: Vec<u16>,
+    pub network_errors: bool,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            statuses: vec![502, 503, 504],
+            network_errors: true,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1
+    }
+}
+
+/// HTTP protocol to speak. `Auto` offers HTTP/2 through ALPN on TLS and falls back to
+/// HTTP/1.1; `Http2` skips negotiation and assumes the server speaks HTTP/2.
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    Http2,
+}
+
+impl HttpVersion {
+    pub fn all() -> [HttpVersion; 3] {
+        [HttpVersion::Auto, HttpVersion::Http1, HttpVersion::Http2]
+    }
+
+    /// Value used in the `x-rustman-http-version` OpenAPI extension.
+    pub fn key(self) -> &'static str {
+        match self {
+            HttpVersion::Auto => "auto",
+            HttpVersion::Http1 => "http1",
+            HttpVersion::Http2 => "http2",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HttpVersion::Auto => "Auto",
+            HttpVersion::Http1 => "HTTP/1.1 only",
+            HttpVersion::Http2 => "HTTP/2 (prior knowledge)",
+        }
+    }
+
+    pub fn from_key(value: &str) -> Option<Self> {
+        Self::all().into_iter().find(|version| version.key() == value)
+    }
+}
+
+/// Proxy endpoint: `http://`, `https://`, `socks5://` or `socks5h://` URL.
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxyConfig {
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Comma separated hosts, domains and CIDR ranges that bypass the proxy.
+    pub no_proxy: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum ServerProxy {
+    /// Use the global proxy settings.
+    #[default]
+    Inherit,
+    Direct,
+    Custom(ProxyConfig),
+}
+
+/// Size and detected MIME type of a file picked as the request body.
+#[derive(Clone, PartialEq, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    pub name: String,
+    pub size: u64,
+    pub mime: String,
+}
+
+/// App-wide preferences stored by the backend.
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub proxy: GlobalProxy,
+    /// Responses larger than this many megabytes are kept on disk; `None` uses the
+    /// backend default.
+    pub response_limit_mb: Option<u64>,
+    /// Saves each tab's last response with the workspace.
+    pub restore_responses: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GlobalProxy {
+    pub enabled: bool,
+    pub proxy: ProxyConfig,
+    /// Honor HTTP_PROXY / HTTPS_PROXY / NO_PROXY when no proxy is set.
+    pub use_env: bool,
+}
+
+/// TLS material for a server, referenced by file path. Empty strings mean "not set".
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TlsSettings {
+    pub ca_cert_path: String,
+    pub client_cert_path: String,
+    pub client_key_path: String,
+    pub pkcs12_path: String,
+    pub pkcs12_password: String,
+    pub allow_invalid_certs: bool,
+}
+
+impl TlsSettings {
+    pub fn is_default(&self) -> bool {
+        *self == TlsSettings::default()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RequestSettings {
+    pub timeout_ms: Option<u64>,
+    pub redirect: RedirectPolicy,
+    /// Overrides the server's HTTP version when set.
+    pub http_version: Option<HttpVersion>,
+    /// Response codings that are neither advertised nor decoded.
+    pub decode_disabled: Vec<ContentEncoding>,
+    pub compress_body: Option<ContentEncoding>,
+}
+
+/// `Content-Encoding` codings the backend decodes responses from and can compress
+/// request bodies with.
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    #[serde(rename = "br")]
+    Brotli,
+    Zstd,
+}
+
+impl ContentEncoding {
+    pub fn all() -> [ContentEncoding; 4] {
+        [
+            ContentEncoding::Gzip,
+            ContentEncoding::Deflate,
+            ContentEncoding::Brotli,
+            ContentEncoding::Zstd,
+        ]
+    }
+
+    /// Header token, also used by the backend and the OpenAPI extensions.
+    pub fn token(self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "brotli",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    pub fn from_token(value: &str) -> Option<Self> {
+        Self::all().into_iter().find(|encoding| encoding.token() == value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RedirectPolicy {
+    #[default]
+    Follow,
+    NoFollow,
+    Limit(u32),
+}
+
+impl RedirectPolicy {
+    /// Value used in the `x-rustman-redirects` OpenAPI extension.
+    pub fn to_extension(self) -> Option<serde_json::Value> {
+        match self {
+            RedirectPolicy::Follow => None,
+            RedirectPolicy::NoFollow => Some(serde_json::Value::Bool(false)),
+            RedirectPolicy::Limit(limit) => Some(serde_json::Value::from(limit)),
+        }
+    }
+
+    pub fn from_extension(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Bool(false) => RedirectPolicy::NoFollow,
+            serde_json::Value::Number(limit) => limit
+                .as_u64()
+                .map(|limit| RedirectPolicy::Limit(limit as u32))
+                .unwrap_or_default(),
+            _ => RedirectPolicy::Follow,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum ApiKeyLocation {
     Header,
     Query,
@@ -96,7 +492,8 @@ impl ApiKeyLocation {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum OAuth2Flow {
     AuthorizationCode,
     Implicit,
@@ -125,13 +522,14 @@ impl OAuth2Flow {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct OAuthScope {
     pub name: String,
     pub description: String,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum ServerAuth {
     None,
     ApiKey {
@@ -225,29 +623,119 @@ impl MethodEnum {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Header {
     pub enable: bool,
     pub key: String,
     pub value: String,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Param {
     pub enable: bool,
     pub key: String,
     pub value: String,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BodyMode {
+    #[default]
+    Raw,
+    UrlEncoded,
+    Multipart,
+    Binary,
+    GraphQl,
+}
+
+impl BodyMode {
+    pub fn all() -> &'static [BodyMode] {
+        static MODES: [BodyMode; 5] = [
+            BodyMode::Raw,
+            BodyMode::UrlEncoded,
+            BodyMode::Multipart,
+            BodyMode::Binary,
+            BodyMode::GraphQl,
+        ];
+        &MODES
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            BodyMode::Raw => "raw",
+            BodyMode::UrlEncoded => "urlencoded",
+            BodyMode::Multipart => "multipart",
+            BodyMode::Binary => "file",
+            BodyMode::GraphQl => "graphql",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BodyMode::Raw => "Raw",
+            BodyMode::UrlEncoded => "x-www-form-urlencoded",
+            BodyMode::Multipart => "multipart/form-data",
+            BodyMode::Binary => "Binary file",
+            BodyMode::GraphQl => "GraphQL",
+        }
+    }
+
+    pub fn from_key(value: &str) -> Self {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|mode| mode.key() == value)
+            .unwrap_or_default()
+    }
+
+    /// Content type set by the backend for form modes.
+    pub fn media_type(&self) -> Option<&'static str> {
+        match self {
+            BodyMode::Raw | BodyMode::Binary | BodyMode::GraphQl => None,
+            BodyMode::UrlEncoded => Some("application/x-www-form-urlencoded"),
+            BodyMode::Multipart => Some("multipart/form-data"),
+        }
+    }
+}
+
+/// Row of the form body table. For file parts `value` holds the file path.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct FormField {
+    pub enable: bool,
+    pub key: String,
+    pub value: String,
+    pub file: bool,
+}
+
+impl FormField {
+    pub fn empty() -> Self {
+        Self {
+            enable: true,
+            key: String::new(),
+            value: String::new(),
+            file: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct TabContent {
     pub method: MethodEnum,
     pub url: String,
     pub body: String,
     pub body_formatted: bool,
+    pub body_mode: BodyMode,
+    pub form_fields: Vec<FormField>,
+    /// File sent as the body in `BodyMode::Binary`.
+    pub body_file: String,
+    pub graphql_query: String,
+    /// JSON object sent as `variables` with the GraphQL query.
+    pub graphql_variables: String,
     pub headers: Vec<Header>,
     pub params: Vec<Param>,
     pub path_params: Vec<Param>,
+    pub settings: RequestSettings,
     pub response: Response,
 }
 
@@ -258,9 +746,37 @@ impl TabContent {
             url: content.url.clone(),
             body: content.body.clone(),
             body_formatted: false,
+            body_mode: content.body_mode,
+            form_fields: content.form_fields.clone(),
+            body_file: content.body_file.clone(),
+            graphql_query: content.graphql_query.clone(),
+            graphql_variables: content.graphql_variables.clone(),
             headers: content.headers.clone(),
             params: content.params.clone(),
             path_params: content.path_params.clone(),
+            settings: content.settings.clone(),
+            response: Response::default(),
+        }
+    }
+}
+
+impl TabContent {
+    /// A copy of the request alone, without cloning the response.
+    pub fn without_response(&self) -> Self {
+        Self {
+            method: self.method,
+            url: self.url.clone(),
+            body: self.body.clone(),
+            body_formatted: self.body_formatted,
+            body_mode: self.body_mode,
+            form_fields: self.form_fields.clone(),
+            body_file: self.body_file.clone(),
+            graphql_query: self.graphql_query.clone(),
+            graphql_variables: self.graphql_variables.clone(),
+            headers: self.headers.clone(),
+            params: self.params.clone(),
+            path_params: self.path_params.clone(),
+            settings: self.settings.clone(),
             response: Response::default(),
         }
     }
@@ -273,6 +789,11 @@ impl Default for TabContent {
             url: "/".to_string(),
             body: String::new(),
             body_formatted: false,
+            body_mode: BodyMode::Raw,
+            form_fields: vec![FormField::empty()],
+            body_file: String::new(),
+            graphql_query: String::new(),
+            graphql_variables: String::new(),
             headers: vec![
                 Header {
                     enable: true,
@@ -295,22 +816,234 @@ impl Default for TabContent {
                 key: String::new(),
                 value: String::new(),
             }],
+            settings: RequestSettings::default(),
             response: Response::default(),
         }
     }
 }
 
+/// One server-sent event forwarded by the backend.
+#[derive(Clone, PartialEq, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEvent {
+    pub request_id: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    pub event: String,
+    pub data: String,
+    /// Milliseconds since the Unix epoch.
+    pub received_at: f64,
+}
+
+/// Live event list of a streaming response.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct EventStream {
+    pub request_id: String,
+    pub events: Vec<StreamEvent>,
+    pub open: bool,
+    pub error: Option<String>,
+}
+
+impl EventStream {
+    fn new(request_id: String) -> Self {
+        Self {
+            request_id,
+            open: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// What a tab talks to.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum TabKind {
+    #[default]
+    Http,
+    /// Handshake URL and headers live in the tab content; the session holds the rest.
+    WebSocket(Box<WsSession>),
+}
+
+/// How the WebSocket composer text is sent.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WsPayload {
+    #[default]
+    Text,
+    /// Validated as JSON, then sent as a text frame.
+    Json,
+    /// Base64 input, sent as a binary frame.
+    Binary,
+}
+
+impl WsPayload {
+    pub fn all() -> &'static [WsPayload] {
+        static PAYLOADS: [WsPayload; 3] = [WsPayload::Text, WsPayload::Json, WsPayload::Binary];
+        &PAYLOADS
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            WsPayload::Text => "text",
+            WsPayload::Json => "json",
+            WsPayload::Binary => "binary",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WsPayload::Text => "Text",
+            WsPayload::Json => "JSON",
+            WsPayload::Binary => "Binary (base64)",
+        }
+    }
+
+    pub fn from_key(value: &str) -> Self {
+        match value {
+            "json" => WsPayload::Json,
+            "binary" => WsPayload::Binary,
+            _ => WsPayload::Text,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WsComposer {
+    pub payload: WsPayload,
+    pub text: String,
+    pub close_code: String,
+    pub close_reason: String,
+}
+
+impl Default for WsComposer {
+    fn default() -> Self {
+        Self {
+            payload: WsPayload::Text,
+            text: String::new(),
+            close_code: "1000".to_string(),
+            close_reason: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WsStatus {
+    #[default]
+    Disconnected,
+    Connecting,
+    Open,
+    Closing,
+}
+
+/// Server answer to the opening handshake.
+#[derive(Clone, PartialEq, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsHandshake {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub protocol: Option<String>,
+}
+
+/// One frame sent or received on a WebSocket connection, as reported by the backend.
+#[derive(Clone, PartialEq, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsMessage {
+    pub connection_id: String,
+    /// `in` or `out`.
+    pub direction: String,
+    /// `text`, `binary`, `ping`, `pong` or `close`.
+    pub kind: String,
+    pub data: String,
+    #[serde(default)]
+    pub size: usize,
+    #[serde(default)]
+    pub close_code: Option<u16>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: f64,
+}
+
+/// Sent once when a connection ends.
+#[derive(Clone, PartialEq, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsClosed {
+    pub connection_id: String,
+    #[serde(default)]
+    pub code: Option<u16>,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WsSession {
+    pub connection_id: Option<String>,
+    pub status: WsStatus,
+    pub handshake: Option<WsHandshake>,
+    pub messages: Vec<WsMessage>,
+    pub composer: WsComposer,
+    /// Close code and reason, or the error, of the last connection.
+    pub last_close: Option<String>,
+}
+
+impl WsSession {
+    fn owns(&self, connection_id: &str) -> bool {
+        self.connection_id.as_deref() == Some(connection_id)
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Tab {
     pub label: String,
     pub content: TabContent,
     pub dirty: bool,
+    pub pending_request: Option<String>,
+    pub event_stream: Option<EventStream>,
+    pub kind: TabKind,
+}
+
+impl Tab {
+    fn owns_request(&self, request_id: &str) -> bool {
+        self.pending_request.as_deref() == Some(request_id)
+            || self
+                .event_stream
+                .as_ref()
+                .is_some_and(|stream| stream.request_id == request_id)
+    }
+
+    pub fn socket(&self) -> Option<&WsSession> {
+        match &self.kind {
+            TabKind::WebSocket(session) => Some(session),
+            TabKind::Http => None,
+        }
+    }
+
+    fn socket_mut(&mut self) -> Option<&mut WsSession> {
+        match &mut self.kind {
+            TabKind::WebSocket(session) => Some(session),
+            TabKind::Http => None,
+        }
+    }
+
+    /// The event stream of `request_id`, replacing one left over from an older request.
+    fn stream_for(&mut self, request_id: &str) -> &mut EventStream {
+        let stream = self
+            .event_stream
+            .get_or_insert_with(|| EventStream::new(request_id.to_string()));
+        if stream.request_id != request_id {
+            *stream = EventStream::new(request_id.to_string());
+        }
+        stream
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct TabState {
     pub active_tab_id: usize,
     pub tabs: Vec<Tab>,
+    pub undo: UndoStack<TabSnapshot>,
 }
 
 impl Default for TabState {
@@ -318,13 +1051,92 @@ impl Default for TabState {
         Self {
             active_tab_id: 0,
             tabs: Vec::new(),
+            undo: UndoStack::default(),
         }
     }
 }
 
+/// The tabs as undo restores them: requests only, without responses or connections.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TabSnapshot {
+    active_tab_id: usize,
+    tabs: Vec<Tab>,
+}
+
+impl TabSnapshot {
+    fn of(state: &TabState) -> Self {
+        let tabs = state
+            .tabs
+            .iter()
+            .map(|tab| Tab {
+                label: tab.label.clone(),
+                content: tab.content.without_response(),
+                dirty: tab.dirty,
+                pending_request: None,
+                event_stream: None,
+                kind: match &tab.kind {
+                    TabKind::Http => TabKind::Http,
+                    TabKind::WebSocket(session) => TabKind::WebSocket(Box::new(WsSession {
+                        composer: session.composer.clone(),
+                        ..WsSession::default()
+                    })),
+                },
+            })
+            .collect();
+        Self {
+            active_tab_id: state.active_tab_id,
+            tabs,
+        }
+    }
+
+    /// Puts the snapshot back into `state`. While the tab count is unchanged the
+    /// requests are restored in place; otherwise only the tabs between the ends both
+    /// share are swapped, so every other tab keeps its response and connection.
+    fn restore(self, state: &mut TabState) {
+        if self.tabs.len() == state.tabs.len() {
+            for (tab, saved) in state.tabs.iter_mut().zip(self.tabs) {
+                let response = std::mem::take(&mut tab.content.response);
+                tab.label = saved.label;
+                tab.content = TabContent {
+                    response,
+                    ..saved.content
+                };
+                tab.dirty = saved.dirty;
+            }
+        } else {
+            let current = TabSnapshot::of(state).tabs;
+            let same = |(saved, tab): &(&Tab, &Tab)| {
+                saved.label == tab.label && saved.content == tab.content
+            };
+            let prefix = self.tabs.iter().zip(&current).take_while(same).count();
+            let suffix = self
+                .tabs
+                .iter()
+                .rev()
+                .zip(current.iter().rev())
+                .take(self.tabs.len().min(current.len()) - prefix)
+                .take_while(same)
+                .count();
+            let mut saved = self.tabs;
+            let restored: Vec<Tab> = saved.drain(prefix..saved.len() - suffix).collect();
+            let end = state.tabs.len() - suffix;
+            state.tabs.splice(prefix..end, restored);
+        }
+        state.active_tab_id = self.active_tab_id.min(state.tabs.len().saturating_sub(1));
+    }
+}
+
 pub enum TabAction {
     AddTab,
-    OpenTab { label: String, content: TabContent },
+    /// Replaces every tab, e.g. with the workspace saved by the previous session.
+    Restore {
+        tabs: Vec<Tab>,
+        active_tab_id: usize,
+    },
+    OpenTab {
+        label: String,
+        content: Box<TabContent>,
+    },
     CloseTab(usize),
     SetActive(usize),
     RenameTab { index: usize, label: String },
@@ -337,6 +1149,17 @@ pub enum TabAction {
         body: String,
         formatted: bool,
     },
+    UpdateBodyMode { index: usize, mode: BodyMode },
+    SetFormFields {
+        index: usize,
+        fields: Vec<FormField>,
+    },
+    UpdateBodyFile { index: usize, path: String },
+    UpdateGraphQl {
+        index: usize,
+        query: String,
+        variables: String,
+    },
     SetHeaders { index: usize, headers: Vec<Header> },
     UpdateUrlAndParams {
         index: usize,
@@ -347,28 +1170,116 @@ pub enum TabAction {
         index: usize,
         path_params: Vec<Param>,
     },
-    SetResponse { index: usize, response: Response },
+    UpdateSettings {
+        index: usize,
+        settings: RequestSettings,
+    },
+    SetResponse {
+        index: usize,
+        response: Box<Response>,
+    },
+    SetPendingRequest {
+        index: usize,
+        request_id: Option<String>,
+    },
+    PushStreamEvent(StreamEvent),
+    CloseStream {
+        request_id: String,
+        error: Option<String>,
+    },
+    ClearStreamEvents(usize),
+    AddWebSocketTab,
+    UpdateWsComposer {
+        index: usize,
+        composer: WsComposer,
+    },
+    WsConnecting {
+        index: usize,
+        connection_id: String,
+    },
+    WsOpened {
+        connection_id: String,
+        handshake: WsHandshake,
+    },
+    WsClosing(usize),
+    PushWsMessage(WsMessage),
+    WsClosed(WsClosed),
+    ClearWsMessages(usize),
+    Undo,
+    Redo,
+}
+
+impl TabAction {
+    /// Tab and request edits are undoable; responses, streams and sockets are not.
+    fn undo_step(&self) -> UndoStep {
+        match self {
+            TabAction::AddTab
+            | TabAction::OpenTab { .. }
+            | TabAction::CloseTab(_)
+            | TabAction::AddWebSocketTab
+            | TabAction::UpdateMethod { .. }
+            | TabAction::SetBodyState { .. }
+            | TabAction::UpdateBodyMode { .. }
+            | TabAction::UpdateBodyFile { .. } => UndoStep::Single,
+            TabAction::RenameTab { index, .. } => UndoStep::Grouped(("label", *index)),
+            TabAction::UpdateUrl { index, .. } | TabAction::UpdateUrlAndParams { index, .. } => {
+                UndoStep::Grouped(("url", *index))
+            }
+            TabAction::UpdateBody { index, .. } => UndoStep::Grouped(("body", *index)),
+            TabAction::SetFormFields { index, .. } => UndoStep::Grouped(("form", *index)),
+            TabAction::UpdateGraphQl { index, .. } => UndoStep::Grouped(("graphql", *index)),
+            TabAction::SetHeaders { index, .. } => UndoStep::Grouped(("headers", *index)),
+            TabAction::UpdatePathParams { index, .. } => UndoStep::Grouped(("path-params", *index)),
+            TabAction::UpdateSettings { index, .. } => UndoStep::Grouped(("settings", *index)),
+            _ => UndoStep::Skip,
+        }
+    }
 }
 
 impl Reducible for TabState {
     type Action = TabAction;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let step = action.undo_step();
         let mut state = (*self).clone();
         match action {
+            TabAction::Undo => {
+                if let Some(snapshot) = state.undo.undo(TabSnapshot::of(&self)) {
+                    snapshot.restore(&mut state);
+                }
+            }
+            TabAction::Redo => {
+                if let Some(snapshot) = state.undo.redo(TabSnapshot::of(&self)) {
+                    snapshot.restore(&mut state);
+                }
+            }
+            TabAction::Restore {
+                tabs,
+                active_tab_id,
+            } => {
+                state.active_tab_id = active_tab_id.min(tabs.len().saturating_sub(1));
+                state.tabs = tabs;
+                state.undo = UndoStack::default();
+            }
             TabAction::AddTab => {
                 state.tabs.push(Tab {
                     label: "/".to_string(),
                     content: TabContent::default(),
                     dirty: false,
+                    pending_request: None,
+                    event_stream: None,
+                    kind: TabKind::Http,
                 });
                 state.active_tab_id = state.tabs.len().saturating_sub(1);
             }
             TabAction::OpenTab { label, content } => {
                 state.tabs.push(Tab {
                     label,
-                    content,
+                    content: *content,
                     dirty: false,
+                    pending_request: None,
+                    event_stream: None,
+                    kind: TabKind::Http,
                 });
                 state.active_tab_id = state.tabs.len().saturating_sub(1);
             }
@@ -432,6 +1343,35 @@ impl Reducible for TabState {
                     tab.dirty = true;
                 }
             }
+            TabAction::UpdateBodyMode { index, mode } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    tab.content.body_mode = mode;
+                    tab.dirty = true;
+                }
+            }
+            TabAction::SetFormFields { index, fields } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    tab.content.form_fields = fields;
+                    tab.dirty = true;
+                }
+            }
+            TabAction::UpdateBodyFile { index, path } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    tab.content.body_file = path;
+                    tab.dirty = true;
+                }
+            }
+            TabAction::UpdateGraphQl {
+                index,
+                query,
+                variables,
+            } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    tab.content.graphql_query = query;
+                    tab.content.graphql_variables = variables;
+                    tab.dirty = true;
+                }
+            }
             TabAction::SetHeaders { index, headers } => {
                 if let Some(tab) = state.tabs.get_mut(index) {
                     tab.content.headers = headers;
@@ -454,17 +1394,152 @@ impl Reducible for TabState {
                     tab.dirty = true;
                 }
             }
+            TabAction::UpdateSettings { index, settings } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    tab.content.settings = settings;
+                    tab.dirty = true;
+                }
+            }
             TabAction::SetResponse { index, response } => {
                 if let Some(tab) = state.tabs.get_mut(index) {
-                    tab.content.response = response;
+                    if response.streaming {
+                        if let Some(request_id) = tab.pending_request.clone() {
+                            tab.stream_for(&request_id);
+                        }
+                    }
+                    tab.content.response = *response;
+                }
+            }
+            TabAction::SetPendingRequest { index, request_id } => {
+                if let Some(tab) = state.tabs.get_mut(index) {
+                    if request_id.is_some() {
+                        tab.event_stream = None;
+                    }
+                    tab.pending_request = request_id;
+                }
+            }
+            TabAction::PushStreamEvent(event) => {
+                // Events are matched by request id: they may arrive before the
+                // response itself, and tab indexes shift while a stream stays open.
+                if let Some(tab) = state
+                    .tabs
+                    .iter_mut()
+                    .find(|tab| tab.owns_request(&event.request_id))
+                {
+                    let request_id = event.request_id.clone();
+                    tab.stream_for(&request_id).events.push(event);
+                }
+            }
+            TabAction::CloseStream { request_id, error } => {
+                if let Some(tab) = state
+                    .tabs
+                    .iter_mut()
+                    .find(|tab| tab.owns_request(&request_id))
+                {
+                    let stream = tab.stream_for(&request_id);
+                    stream.open = false;
+                    stream.error = error;
+                }
+            }
+            TabAction::ClearStreamEvents(index) => {
+                if let Some(stream) = state
+                    .tabs
+                    .get_mut(index)
+                    .and_then(|tab| tab.event_stream.as_mut())
+                {
+                    stream.events.clear();
                 }
             }
+            TabAction::AddWebSocketTab => {
+                state.tabs.push(Tab {
+                    label: "WebSocket".to_string(),
+                    content: TabContent {
+                        headers: vec![Header {
+                            enable: true,
+                            key: String::new(),
+                            value: String::new(),
+                        }],
+                        ..TabContent::default()
+                    },
+                    dirty: false,
+                    pending_request: None,
+                    event_stream: None,
+                    kind: TabKind::WebSocket(Box::default()),
+                });
+                state.active_tab_id = state.tabs.len().saturating_sub(1);
+            }
+            TabAction::UpdateWsComposer { index, composer } => {
+                if let Some(session) = state.tabs.get_mut(index).and_then(Tab::socket_mut) {
+                    session.composer = composer;
+                }
+            }
+            TabAction::WsConnecting {
+                index,
+                connection_id,
+            } => {
+                if let Some(session) = state.tabs.get_mut(index).and_then(Tab::socket_mut) {
+                    session.connection_id = Some(connection_id);
+                    session.status = WsStatus::Connecting;
+                    session.handshake = None;
+                    session.last_close = None;
+                }
+            }
+            // Connection events are matched by id, like stream events, since frames
+            // can arrive before `ws_connect` returns.
+            TabAction::WsOpened {
+                connection_id,
+                handshake,
+            } => {
+                if let Some(session) = find_socket(&mut state.tabs, &connection_id) {
+                    if session.status == WsStatus::Connecting {
+                        session.status = WsStatus::Open;
+                    }
+                    session.handshake = Some(handshake);
+                }
+            }
+            TabAction::WsClosing(index) => {
+                if let Some(session) = state.tabs.get_mut(index).and_then(Tab::socket_mut) {
+                    if session.status == WsStatus::Open {
+                        session.status = WsStatus::Closing;
+                    }
+                }
+            }
+            TabAction::PushWsMessage(message) => {
+                if let Some(session) = find_socket(&mut state.tabs, &message.connection_id) {
+                    session.messages.push(message);
+                }
+            }
+            TabAction::WsClosed(closed) => {
+                if let Some(session) = find_socket(&mut state.tabs, &closed.connection_id) {
+                    session.status = WsStatus::Disconnected;
+                    session.last_close = Some(match (closed.error, closed.code) {
+                        (Some(error), _) => error,
+                        (None, Some(code)) if closed.reason.is_empty() => format!("Closed · {code}"),
+                        (None, Some(code)) => format!("Closed · {code} {}", closed.reason),
+                        (None, None) => "Closed".to_string(),
+                    });
+                }
+            }
+            TabAction::ClearWsMessages(index) => {
+                if let Some(session) = state.tabs.get_mut(index).and_then(Tab::socket_mut) {
+                    session.messages.clear();
+                }
+            }
+        }
+        if step != UndoStep::Skip && state.tabs != self.tabs {
+            state.undo.record(TabSnapshot::of(&self), step);
         }
         Rc::new(state)
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+fn find_socket<'a>(tabs: &'a mut [Tab], connection_id: &str) -> Option<&'a mut WsSession> {
+    tabs.iter_mut()
+        .filter_map(Tab::socket_mut)
+        .find(|session| session.owns(connection_id))
+}
+
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct TreeNode {
     pub label: String,
     pub content: Option<TabContent>,
@@ -481,6 +1556,9 @@ pub struct TreeState {
     pub pending_move: Option<PendingMove>,
     pub selected_server: Option<usize>,
     pub pending_auth: Option<usize>,
+    pub environments: Vec<Environment>,
+    pub active_environment: Option<usize>,
+    pub undo: UndoStack<TreeSnapshot>,
 }
 
 impl Default for TreeState {
@@ -498,10 +1576,73 @@ impl Default for TreeState {
             pending_move: None,
             selected_server: None,
             pending_auth: None,
+            environments: Vec::new(),
+            active_environment: None,
+            undo: UndoStack::default(),
         }
     }
 }
 
+/// The collection as undo restores it: the tree, servers and environments. Fetched
+/// GraphQL schemas are left out and kept as they are when restoring.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TreeSnapshot {
+    root: TreeNode,
+    servers: Vec<ServerEntry>,
+    selected_server: Option<usize>,
+    environments: Vec<Environment>,
+    active_environment: Option<usize>,
+}
+
+impl TreeSnapshot {
+    fn of(state: &TreeState) -> Self {
+        Self {
+            root: state.root.clone(),
+            servers: without_schemas(&state.servers),
+            selected_server: state.selected_server,
+            environments: state.environments.clone(),
+            active_environment: state.active_environment,
+        }
+    }
+
+    /// Puts the snapshot back into `state`, dropping the selection and pending
+    /// dialogs since their paths may no longer point at the same nodes.
+    fn restore(mut self, state: &mut TreeState) {
+        for server in self.servers.iter_mut() {
+            server.graphql_schema = state
+                .servers
+                .iter()
+                .find(|current| current.url == server.url)
+                .and_then(|current| current.graphql_schema.clone());
+        }
+        state.root = self.root;
+        state.servers = self.servers;
+        state.selected_server = self.selected_server;
+        state.environments = self.environments;
+        state.active_environment = self.active_environment;
+        state.selected_path = None;
+        state.pending_delete = None;
+        state.pending_move = None;
+        state.pending_auth = None;
+    }
+
+    fn differs_from(&self, state: &TreeState) -> bool {
+        self.root != state.root
+            || self.servers != without_schemas(&state.servers)
+            || self.environments != state.environments
+    }
+}
+
+fn without_schemas(servers: &[ServerEntry]) -> Vec<ServerEntry> {
+    servers
+        .iter()
+        .map(|server| ServerEntry {
+            graphql_schema: None,
+            ..server.clone()
+        })
+        .collect()
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct PendingDelete {
     pub path: Vec<usize>,
@@ -520,10 +1661,24 @@ pub enum TreeAction {
     RemoveServer { index: usize },
     SetSelectedServer { index: usize },
     UpdateServerAuth { index: usize, auth: ServerAuth },
+    UpdateServerSettings {
+        index: usize,
+        settings: ServerSettings,
+    },
+    SetServerSchema {
+        index: usize,
+        schema: Option<Rc<GraphQlSchema>>,
+    },
     RequestAuth { index: usize },
     ClearPendingAuth,
     SetTree { root: TreeNode, servers: Vec<ServerEntry> },
     AddChild { path: Vec<usize>, node: TreeNode },
+    /// Adds `node` to the folder named by `folders`, creating the missing folders in
+    /// the same undo step. With no `node` only the folders are created.
+    AddToFolder {
+        folders: Vec<String>,
+        node: Option<TreeNode>,
+    },
     ReplaceNode { path: Vec<usize>, node: TreeNode },
     Rename { path: Vec<usize>, label: String },
     SetSelected { path: Vec<usize> },
@@ -532,15 +1687,62 @@ pub enum TreeAction {
     RemoveNode { path: Vec<usize> },
     RequestMove { path: Vec<usize>, label: String },
     ClearPendingMove,
-    MoveNode { from: Vec<usize>, to: Vec<usize> },
+    /// Moves the node at `from` into the folder at `to`, before the child currently at
+    /// `index`, or after the last child when `index` is `None`.
+    MoveNode {
+        from: Vec<usize>,
+        to: Vec<usize>,
+        index: Option<usize>,
+    },
+    SetEnvironments { environments: Vec<Environment> },
+    SetActiveEnvironment { index: Option<usize> },
+    Undo,
+    Redo,
+    /// Forgets undo history, e.g. once the previous session is restored.
+    ClearUndo,
+}
+
+impl TreeAction {
+    /// Changes to the collection are undoable; selection, expansion and dialogs are not.
+    fn undo_step(&self) -> UndoStep {
+        match self {
+            TreeAction::AddServer { .. }
+            | TreeAction::RemoveServer { .. }
+            | TreeAction::UpdateServerAuth { .. }
+            | TreeAction::UpdateServerSettings { .. }
+            | TreeAction::SetTree { .. }
+            | TreeAction::AddChild { .. }
+            | TreeAction::AddToFolder { .. }
+            | TreeAction::ReplaceNode { .. }
+            | TreeAction::RemoveNode { .. }
+            | TreeAction::MoveNode { .. }
+            | TreeAction::Rename { .. }
+            | TreeAction::SetEnvironments { .. } => UndoStep::Single,
+            _ => UndoStep::Skip,
+        }
+    }
 }
 
 impl Reducible for TreeState {
     type Action = TreeAction;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let step = action.undo_step();
         let mut state = (*self).clone();
         match action {
+            TreeAction::Undo => {
+                if let Some(snapshot) = state.undo.undo(TreeSnapshot::of(&self)) {
+                    snapshot.restore(&mut state);
+                }
+            }
+            TreeAction::Redo => {
+                if let Some(snapshot) = state.undo.redo(TreeSnapshot::of(&self)) {
+                    snapshot.restore(&mut state);
+                }
+            }
+            TreeAction::ClearUndo => {
+                state.undo = UndoStack::default();
+            }
             TreeAction::SetExpanded { path, open } => {
                 set_expanded(&mut state.root, &path, open);
             }
@@ -569,6 +1771,16 @@ impl Reducible for TreeState {
                     server.auth = auth;
                 }
             }
+            TreeAction::UpdateServerSettings { index, settings } => {
+                if let Some(server) = state.servers.get_mut(index) {
+                    server.settings = settings;
+                }
+            }
+            TreeAction::SetServerSchema { index, schema } => {
+                if let Some(server) = state.servers.get_mut(index) {
+                    server.graphql_schema = schema;
+                }
+            }
             TreeAction::RequestAuth { index } => {
                 if index < state.servers.len() {
                     state.pending_auth = Some(index);
@@ -593,6 +1805,9 @@ impl Reducible for TreeState {
             TreeAction::AddChild { path, node } => {
                 add_child(&mut state.root, &path, node);
             }
+            TreeAction::AddToFolder { folders, node } => {
+                add_to_folder(&mut state.root, &folders, node);
+            }
             TreeAction::ReplaceNode { path, node } => {
                 replace_node(&mut state.root, &path, node);
             }
@@ -619,12 +1834,82 @@ impl Reducible for TreeState {
             TreeAction::ClearPendingMove => {
                 state.pending_move = None;
             }
-            TreeAction::MoveNode { from, to } => {
-                if let Some(new_path) = move_node(&mut state.root, &from, &to) {
+            TreeAction::MoveNode { from, to, index } => {
+                if let Some(new_path) = move_node(&mut state.root, &from, &to, index) {
                     state.selected_path = Some(new_path);
                 }
                 state.pending_move = None;
             }
+            TreeAction::SetEnvironments { environments } => {
+                // The active environment is followed by name, so removing or reordering
+                // others does not switch it.
+                let active_name = state
+                    .active_environment
+                    .and_then(|index| state.environments.get(index))
+                    .map(|environment| environment.name.clone());
+                state.environments = environments;
+                state.active_environment = active_name.and_then(|name| {
+                    state
+                        .environments
+                        .iter()
+                        .position(|environment| environment.name == name)
+                });
+            }
+            TreeAction::SetActiveEnvironment { index } => {
+                state.active_environment = index.filter(|index| *index < state.environments.len());
+            }
+        }
+        if step != UndoStep::Skip {
+            let before = TreeSnapshot::of(&self);
+            if before.differs_from(&state) {
+                state.undo.record(before, step);
+            }
+        }
+        Rc::new(state)
+    }
+}
+
+/// A recorded send as listed in the history panel; the request and response it
+/// produced are fetched by `id` when the entry is opened.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySummary {
+    pub id: String,
+    /// Milliseconds since the epoch.
+    pub timestamp: f64,
+    pub method: MethodEnum,
+    pub url: String,
+    /// `0` when no response arrived.
+    pub status: u16,
+    pub ok: bool,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub server_url: Option<String>,
+    pub tab_label: String,
+}
+
+/// Send history, newest first.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HistoryState {
+    pub entries: Vec<HistorySummary>,
+}
+
+pub enum HistoryAction {
+    Load(Vec<HistorySummary>),
+    Push(HistorySummary),
+    Clear,
+}
+
+impl Reducible for HistoryState {
+    type Action = HistoryAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        match action {
+            HistoryAction::Load(entries) => state.entries = entries,
+            HistoryAction::Push(summary) => state.entries.insert(0, summary),
+            HistoryAction::Clear => state.entries.clear(),
         }
         Rc::new(state)
     }
@@ -666,6 +1951,32 @@ fn add_child(node: &mut TreeNode, path: &[usize], child: TreeNode) {
     }
 }
 
+/// Adds `child` below the folder path `folders` of `node`, creating missing folders.
+pub fn add_to_folder(node: &mut TreeNode, folders: &[String], child: Option<TreeNode>) {
+    let Some((first, rest)) = folders.split_first() else {
+        node.children.extend(child);
+        node.expanded = true;
+        return;
+    };
+    let index = match node
+        .children
+        .iter()
+        .position(|existing| existing.content.is_none() && &existing.label == first)
+    {
+        Some(index) => index,
+        None => {
+            node.children.push(TreeNode {
+                label: first.clone(),
+                content: None,
+                expanded: true,
+                children: Vec::new(),
+            });
+            node.children.len() - 1
+        }
+    };
+    add_to_folder(&mut node.children[index], rest, child);
+}
+
 fn rename_node(node: &mut TreeNode, path: &[usize], label: String) {
     if path.is_empty() {
         node.label = label;
@@ -730,21 +2041,40 @@ fn remove_node_at(node: &mut TreeNode, path: &[usize]) -> Option<TreeNode> {
     remove_node_at(target, rest)
 }
 
-fn move_node(root: &mut TreeNode, from: &[usize], to: &[usize]) -> Option<Vec<usize>> {
-    if from.is_empty() {
+fn move_node(
+    root: &mut TreeNode,
+    from: &[usize],
+    to: &[usize],
+    index: Option<usize>,
+) -> Option<Vec<usize>> {
+    let (&from_index, from_parent) = from.split_last()?;
+    if is_prefix_path(from, to) {
         return None;
     }
-    if is_prefix_path(from, to) {
+    if node_at_path_mut(root, to)?.content.is_some() {
         return None;
     }
 
+    // Taking the node out shifts its later siblings, which `to` and `index` may count.
+    let mut to = to.to_vec();
+    let depth = from_parent.len();
+    if is_prefix_path(from_parent, &to) && to.len() > depth && to[depth] > from_index {
+        to[depth] -= 1;
+    }
+    let index = match index {
+        Some(index) if to == from_parent && index > from_index => Some(index - 1),
+        other => other,
+    };
+
     let node = remove_node_at(root, from)?;
-    let target = node_at_path_mut(root, to)?;
-    target.children.push(node);
+    let target = node_at_path_mut(root, &to)?;
+    let position = index
+        .unwrap_or(target.children.len())
+        .min(target.children.len());
+    target.children.insert(position, node);
     target.expanded = true;
-    let mut new_path = to.to_vec();
-    new_path.push(target.children.len().saturating_sub(1));
-    Some(new_path)
+    to.push(position);
+    Some(to)
 }
 
 fn node_at_path_mut<'a>(root: &'a mut TreeNode, path: &[usize]) -> Option<&'a mut TreeNode> {
@@ -800,7 +2130,7 @@ fn tab_label_from_url(value: &str) -> Option<String> {
     }
 
     if let Ok(url) = Url::parse(trimmed) {
-        if matches!(url.scheme(), "http" | "https") {
+        if matches!(url.scheme(), "http" | "https" | "ws" | "wss") {
             return Some(normalize_path(url.path()));
         }
     }
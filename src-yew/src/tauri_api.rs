@@ -4,6 +4,10 @@ use js_sys::{
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
+use serde::Serialize;
+
+use crate::state::{AppSettings, FileInfo, HistorySummary, StoredCookie, WsHandshake};
+
 fn tauri_root() -> Result<JsValue, JsValue> {
     let window = web_sys::window().ok_or_else(|| JsValue::from_str("window not available"))?;
     Reflect::get(&window, &JsValue::from_str("__TAURI__"))
@@ -151,7 +155,20 @@ pub async fn fs_write_text_with_options(
     contents: &str,
     options_json: Option<&str>,
 ) -> Result<(), JsValue> {
-    let payload = Uint8Array::from(contents.as_bytes());
+    write_file_command("plugin:fs|write_text_file", path, contents.as_bytes(), options_json).await
+}
+
+pub async fn fs_write_binary(path: &str, contents: &[u8]) -> Result<(), JsValue> {
+    write_file_command("plugin:fs|write_file", path, contents, None).await
+}
+
+async fn write_file_command(
+    command: &str,
+    path: &str,
+    contents: &[u8],
+    options_json: Option<&str>,
+) -> Result<(), JsValue> {
+    let payload = Uint8Array::from(contents);
     let headers = Object::new();
     let encoded_path = encode_uri_component(path);
     Reflect::set(
@@ -169,7 +186,7 @@ pub async fn fs_write_text_with_options(
     let invoke_options = Object::new();
     Reflect::set(&invoke_options, &JsValue::from_str("headers"), &headers)?;
     let _ = invoke_with_options(
-        "plugin:fs|write_text_file",
+        command,
         payload.into(),
         Some(invoke_options.into()),
     )
@@ -188,6 +205,210 @@ pub async fn set_window_title(title: &str) -> Result<(), JsValue> {
     Ok(())
 }
 
+pub async fn list_cookies(server_url: &str) -> Result<Vec<StoredCookie>, JsValue> {
+    let value = invoke("list_cookies", server_payload(server_url)?).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+pub async fn save_cookie(
+    server_url: &str,
+    cookie: &StoredCookie,
+    previous: Option<&StoredCookie>,
+) -> Result<(), JsValue> {
+    let payload = server_payload(server_url)?;
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("cookie"),
+        &serde_wasm_bindgen::to_value(cookie)?,
+    )?;
+    if let Some(previous) = previous {
+        Reflect::set(
+            &payload,
+            &JsValue::from_str("previous"),
+            &serde_wasm_bindgen::to_value(previous)?,
+        )?;
+    }
+    let _ = invoke("save_cookie", payload).await?;
+    Ok(())
+}
+
+pub async fn delete_cookie(server_url: &str, cookie: &StoredCookie) -> Result<(), JsValue> {
+    let payload = server_payload(server_url)?;
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("cookie"),
+        &serde_wasm_bindgen::to_value(cookie)?,
+    )?;
+    let _ = invoke("delete_cookie", payload).await?;
+    Ok(())
+}
+
+pub async fn clear_cookies(server_url: &str) -> Result<(), JsValue> {
+    let _ = invoke("clear_cookies", server_payload(server_url)?).await?;
+    Ok(())
+}
+
+pub async fn file_info(path: &str) -> Result<FileInfo, JsValue> {
+    let payload = Object::new();
+    Reflect::set(&payload, &JsValue::from_str("path"), &JsValue::from_str(path))?;
+    let value = invoke("file_info", payload.into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+/// Copies a response body that the backend kept in a temp file.
+pub async fn save_response_file(source: &str, destination: &str) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(&payload, &JsValue::from_str("source"), &JsValue::from_str(source))?;
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("destination"),
+        &JsValue::from_str(destination),
+    )?;
+    let _ = invoke("save_response_file", payload.into()).await?;
+    Ok(())
+}
+
+/// Deletes the temp file of a response no tab holds anymore.
+pub async fn discard_response_file(path: &str) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(&payload, &JsValue::from_str("path"), &JsValue::from_str(path))?;
+    let _ = invoke("discard_response_file", payload.into()).await?;
+    Ok(())
+}
+
+pub async fn get_app_settings() -> Result<AppSettings, JsValue> {
+    let value = invoke("get_app_settings", Object::new().into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+pub async fn save_app_settings(settings: &AppSettings) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("settings"),
+        &serde_wasm_bindgen::to_value(settings)?,
+    )?;
+    let _ = invoke("save_app_settings", payload.into()).await?;
+    Ok(())
+}
+
+/// The workspace saved by the previous session, if there is a readable one.
+pub async fn load_workspace() -> Result<Option<serde_json::Value>, JsValue> {
+    let value = invoke("load_workspace", Object::new().into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+pub async fn save_workspace<T: Serialize>(workspace: &T) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("workspace"),
+        &workspace.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?,
+    )?;
+    let _ = invoke("save_workspace", payload.into()).await?;
+    Ok(())
+}
+
+/// The sealed secret vault, if one was created.
+pub async fn load_vault() -> Result<Option<serde_json::Value>, JsValue> {
+    let value = invoke("load_vault", Object::new().into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+pub async fn save_vault(vault: &serde_json::Value) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("vault"),
+        &vault.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?,
+    )?;
+    let _ = invoke("save_vault", payload.into()).await?;
+    Ok(())
+}
+
+pub async fn append_history<T: Serialize>(record: &T) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("record"),
+        &record.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?,
+    )?;
+    let _ = invoke("append_history", payload.into()).await?;
+    Ok(())
+}
+
+/// Recorded sends, newest first.
+pub async fn list_history() -> Result<Vec<HistorySummary>, JsValue> {
+    let value = invoke("list_history", Object::new().into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+/// Request and response of a recorded send, if it is still kept.
+pub async fn get_history_entry(id: &str) -> Result<Option<serde_json::Value>, JsValue> {
+    let payload = Object::new();
+    Reflect::set(&payload, &JsValue::from_str("id"), &JsValue::from_str(id))?;
+    let value = invoke("get_history_entry", payload.into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+pub async fn clear_history() -> Result<(), JsValue> {
+    let _ = invoke("clear_history", Object::new().into()).await?;
+    Ok(())
+}
+
+/// Opens a WebSocket connection; frames then arrive through `ws-message` events.
+pub async fn ws_connect<T: Serialize>(request: &T) -> Result<WsHandshake, JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("request"),
+        &request.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?,
+    )?;
+    let value = invoke("ws_connect", payload.into()).await?;
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+/// Queues a `text`, `binary` or `ping` frame; binary payloads are base64.
+pub async fn ws_send(connection_id: &str, kind: &str, data: &str) -> Result<(), JsValue> {
+    let message = Object::new();
+    Reflect::set(&message, &JsValue::from_str("kind"), &JsValue::from_str(kind))?;
+    Reflect::set(&message, &JsValue::from_str("data"), &JsValue::from_str(data))?;
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("connectionId"),
+        &JsValue::from_str(connection_id),
+    )?;
+    Reflect::set(&payload, &JsValue::from_str("message"), &message)?;
+    let _ = invoke("ws_send", payload.into()).await?;
+    Ok(())
+}
+
+pub async fn ws_close(connection_id: &str, code: Option<u16>, reason: &str) -> Result<(), JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("connectionId"),
+        &JsValue::from_str(connection_id),
+    )?;
+    if let Some(code) = code {
+        Reflect::set(&payload, &JsValue::from_str("code"), &JsValue::from_f64(code as f64))?;
+    }
+    Reflect::set(&payload, &JsValue::from_str("reason"), &JsValue::from_str(reason))?;
+    let _ = invoke("ws_close", payload.into()).await?;
+    Ok(())
+}
+
+fn server_payload(server_url: &str) -> Result<JsValue, JsValue> {
+    let payload = Object::new();
+    Reflect::set(
+        &payload,
+        &JsValue::from_str("serverUrl"),
+        &JsValue::from_str(server_url),
+    )?;
+    Ok(payload.into())
+}
+
 pub fn js_error_to_string(value: &JsValue) -> String {
     value
         .as_string()
@@ -0,0 +1,127 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use js_sys::Date;
+
+/// Oldest entries are dropped past this many.
+const UNDO_LIMIT: usize = 100;
+
+/// Edits to the same field less than this far apart are undone together, so typing a
+/// word takes one undo instead of one per keystroke.
+const COALESCE_MS: f64 = 1000.0;
+
+thread_local! {
+    static NEXT_STAMP: Cell<u64> = const { Cell::new(1) };
+}
+
+/// Order shared by every stack, so one undo reverts the newest change across the
+/// tree and the tabs.
+fn next_stamp() -> u64 {
+    NEXT_STAMP.with(|next| {
+        let stamp = next.get();
+        next.set(stamp + 1);
+        stamp
+    })
+}
+
+/// Identifies edits that coalesce: the field edited and the index of what holds it.
+pub type UndoGroup = (&'static str, usize);
+
+/// How a reducer action enters the undo history.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UndoStep {
+    Skip,
+    /// Undone on its own.
+    Single,
+    /// Undone together with neighbouring edits of the same group.
+    Grouped(UndoGroup),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct UndoEntry<T> {
+    /// Shared, so cloning the reducer state each action does not copy every snapshot.
+    snapshot: Rc<T>,
+    stamp: u64,
+    group: Option<UndoGroup>,
+    /// When the entry last absorbed an edit, in milliseconds since the epoch.
+    at: f64,
+}
+
+/// Undo and redo snapshots of one reducer's state, newest last.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UndoStack<T> {
+    undo: Vec<UndoEntry<T>>,
+    redo: Vec<UndoEntry<T>>,
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> UndoStack<T> {
+    /// Saves `before`, the state an edit replaced. Consecutive edits in the same
+    /// group keep the snapshot from before the first of them.
+    pub fn record(&mut self, before: T, step: UndoStep) {
+        let group = match step {
+            UndoStep::Skip => return,
+            UndoStep::Single => None,
+            UndoStep::Grouped(group) => Some(group),
+        };
+        let now = Date::now();
+        self.redo.clear();
+        if let Some(last) = self.undo.last_mut() {
+            if group.is_some() && last.group == group && now - last.at < COALESCE_MS {
+                last.at = now;
+                return;
+            }
+        }
+        self.undo.push(UndoEntry {
+            snapshot: Rc::new(before),
+            stamp: next_stamp(),
+            group,
+            at: now,
+        });
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    /// The snapshot to go back to, with `current` kept for redo.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let entry = self.undo.pop()?;
+        self.redo.push(UndoEntry {
+            snapshot: Rc::new(current),
+            stamp: next_stamp(),
+            group: None,
+            at: entry.at,
+        });
+        Some(Rc::unwrap_or_clone(entry.snapshot))
+    }
+
+    /// The snapshot an undo left, with `current` kept for undo.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let entry = self.redo.pop()?;
+        self.undo.push(UndoEntry {
+            snapshot: Rc::new(current),
+            stamp: next_stamp(),
+            group: None,
+            at: entry.at,
+        });
+        Some(Rc::unwrap_or_clone(entry.snapshot))
+    }
+
+    /// Order of the change the next undo reverts; higher is newer.
+    pub fn undo_stamp(&self) -> Option<u64> {
+        self.undo.last().map(|entry| entry.stamp)
+    }
+
+    /// Order of the undo the next redo reapplies; higher is newer.
+    pub fn redo_stamp(&self) -> Option<u64> {
+        self.redo.last().map(|entry| entry.stamp)
+    }
+}
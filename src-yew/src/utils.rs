@@ -1,3 +1,6 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
 use url::Url;
 
 use crate::state::Param;
@@ -112,6 +115,9 @@ pub fn url_from_path_params(url: &str, params: &[Param]) -> String {
     let mut chars = base.chars().peekable();
 
     while let Some(ch) = chars.next() {
+        if ch == '{' && copy_variable(&mut chars, &mut result) {
+            continue;
+        }
         if ch == '{' {
             let mut key = String::new();
             while let Some(next) = chars.next() {
@@ -172,6 +178,9 @@ fn extract_path_keys(url: &str) -> Vec<String> {
     let mut keys = Vec::new();
     let mut chars = base.chars().peekable();
     while let Some(ch) = chars.next() {
+        if ch == '{' && copy_variable(&mut chars, &mut String::new()) {
+            continue;
+        }
         if ch == '{' {
             let mut key = String::new();
             while let Some(next) = chars.next() {
@@ -189,6 +198,24 @@ fn extract_path_keys(url: &str) -> Vec<String> {
     keys
 }
 
+/// Copies an environment `{{name}}` placeholder into `out` as written, when the `{`
+/// just taken from `chars` opens one, so path parameter scanning passes over it.
+pub fn copy_variable(chars: &mut Peekable<Chars>, out: &mut String) -> bool {
+    if chars.peek() != Some(&'{') {
+        return false;
+    }
+    out.push('{');
+    let mut closing = false;
+    for next in chars.by_ref() {
+        out.push(next);
+        if next == '}' && closing {
+            break;
+        }
+        closing = next == '}';
+    }
+    true
+}
+
 fn split_url(url: &str) -> (String, Option<String>) {
     let trimmed = url.trim();
     if trimmed.is_empty() {
@@ -209,7 +236,7 @@ fn split_url(url: &str) -> (String, Option<String>) {
         None
     };
 
-    let normalized = if path.starts_with('/') || path.is_empty() {
+    let normalized = if path.starts_with('/') || path.starts_with("{{") || path.is_empty() {
         path
     } else {
         format!("/{}", path)
@@ -217,3 +244,35 @@ fn split_url(url: &str) -> (String, Option<String>) {
 
     (normalized, query)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(key: &str, value: &str) -> Param {
+        Param {
+            enable: true,
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn path_params_skip_environment_variables() {
+        let params = path_params_from_url("/a/{{x}}/{y}", &[]);
+        let keys: Vec<&str> = params.iter().map(|param| param.key.as_str()).collect();
+        assert_eq!(keys, ["y"]);
+    }
+
+    #[test]
+    fn renaming_a_path_param_keeps_environment_variables() {
+        let url = url_from_path_params("/a/{{x}}/{y}", &[param("z", "")]);
+        assert_eq!(url, "/a/{{x}}/{z}");
+    }
+
+    #[test]
+    fn leading_variable_is_not_prefixed_with_a_slash() {
+        let url = url_from_path_params("{{baseUrl}}/users/{id}", &[param("id", "")]);
+        assert_eq!(url, "{{baseUrl}}/users/{id}");
+    }
+}
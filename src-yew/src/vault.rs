@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::state::{Environment, ServerAuth, ServerEntry, ServerProxy};
+
+const VAULT_VERSION: u32 = 1;
+
+/// PBKDF2-HMAC-SHA256 rounds for new vaults; existing ones keep the count they were
+/// sealed with.
+const KDF_ITERATIONS: u32 = 310_000;
+
+/// Prefix that marks a value as a vault reference: `vault://<id>`. Unlike `{{name}}`
+/// placeholders, references are never filled in by environments.
+const REFERENCE_PREFIX: &str = "vault://";
+
+/// The vault as stored in `<app data>/vault.json`: the secrets map as JSON, sealed
+/// with AES-256-GCM under a key derived from the master passphrase.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SealedVault {
+    version: u32,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Unlocked vault: secret values by reference id, plus the key to seal them again.
+pub struct Vault {
+    key: [u8; 32],
+    salt: Vec<u8>,
+    iterations: u32,
+    secrets: BTreeMap<String, String>,
+}
+
+impl Vault {
+    pub fn create(passphrase: &str) -> Self {
+        let mut salt = vec![0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            key: derive_key(passphrase, &salt, KDF_ITERATIONS),
+            salt,
+            iterations: KDF_ITERATIONS,
+            secrets: BTreeMap::new(),
+        }
+    }
+
+    pub fn unlock(sealed: serde_json::Value, passphrase: &str) -> Result<Self, String> {
+        let sealed: SealedVault =
+            serde_json::from_value(sealed).map_err(|err| format!("Cofre inválido: {err}"))?;
+        if sealed.version > VAULT_VERSION {
+            return Err(format!(
+                "Cofre na versão {}; esta versão do app lê até a {VAULT_VERSION}.",
+                sealed.version
+            ));
+        }
+        let decode = |value: &str| {
+            STANDARD
+                .decode(value)
+                .map_err(|err| format!("Cofre inválido: {err}"))
+        };
+        let salt = decode(&sealed.salt)?;
+        let nonce = decode(&sealed.nonce)?;
+        let ciphertext = decode(&sealed.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err("Cofre inválido: nonce com tamanho errado.".to_string());
+        }
+        let key = derive_key(passphrase, &salt, sealed.iterations);
+        let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| "Senha mestra incorreta.".to_string())?;
+        let secrets =
+            serde_json::from_slice(&plaintext).map_err(|err| format!("Cofre inválido: {err}"))?;
+        Ok(Self {
+            key,
+            salt,
+            iterations: sealed.iterations,
+            secrets,
+        })
+    }
+
+    /// Encrypts the secrets under a fresh nonce.
+    pub fn seal(&self) -> Result<serde_json::Value, String> {
+        let plaintext = serde_json::to_vec(&self.secrets).map_err(|err| err.to_string())?;
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|_| "Falha ao cifrar o cofre.".to_string())?;
+        serde_json::to_value(SealedVault {
+            version: VAULT_VERSION,
+            iterations: self.iterations,
+            salt: STANDARD.encode(&self.salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+        .map_err(|err| err.to_string())
+    }
+
+    /// Stores `value` under `id`; `true` when that changed what the vault holds.
+    pub fn insert(&mut self, id: String, value: String) -> bool {
+        let previous = self.secrets.insert(id, value.clone());
+        previous.as_ref() != Some(&value)
+    }
+
+    fn get(&self, id: &str) -> Option<&str> {
+        self.secrets.get(id).map(String::as_str)
+    }
+}
+
+/// Copies of `servers` and `environments` with every secret replaced by a vault
+/// reference, and the secrets to store under those references. Values that already
+/// are references are left alone.
+pub fn extract_secrets(
+    servers: &[ServerEntry],
+    environments: &[Environment],
+) -> (Vec<ServerEntry>, Vec<Environment>, Vec<(String, String)>) {
+    let mut secrets = Vec::new();
+    let mut take = |id: String, value: &mut String| {
+        if value.is_empty() || referenced_id(value).is_some() {
+            return;
+        }
+        let secret = std::mem::replace(value, reference(&id));
+        secrets.push((id, secret));
+    };
+
+    let mut servers = servers.to_vec();
+    for server in servers.iter_mut() {
+        let url = server.url.clone();
+        for (field, value) in server_secrets(server) {
+            take(format!("server:{url}/{field}"), value);
+        }
+    }
+    let mut environments = environments.to_vec();
+    for environment in environments.iter_mut() {
+        for variable in environment
+            .variables
+            .iter_mut()
+            .filter(|variable| variable.secret)
+        {
+            take(
+                format!("env:{}/{}", environment.name, variable.key),
+                &mut variable.value,
+            );
+        }
+    }
+    (servers, environments, secrets)
+}
+
+/// Empties every secret of `servers` and `environments` that is not already a vault
+/// reference.
+pub fn blank_secrets(servers: &mut [ServerEntry], environments: &mut [Environment]) {
+    let blank = |value: &mut String| {
+        if referenced_id(value).is_none() {
+            value.clear();
+        }
+    };
+    for server in servers.iter_mut() {
+        for (_, value) in server_secrets(server) {
+            blank(value);
+        }
+    }
+    for variable in environments
+        .iter_mut()
+        .flat_map(|environment| environment.variables.iter_mut())
+        .filter(|variable| variable.secret)
+    {
+        blank(&mut variable.value);
+    }
+}
+
+pub fn has_references(servers: &[ServerEntry], environments: &[Environment]) -> bool {
+    let in_servers = servers.iter().cloned().any(|mut server| {
+        server_secrets(&mut server)
+            .into_iter()
+            .any(|(_, value)| referenced_id(value).is_some())
+    });
+    in_servers
+        || environments
+            .iter()
+            .flat_map(|environment| &environment.variables)
+            .any(|variable| referenced_id(&variable.value).is_some())
+}
+
+/// The secret values of `server` and `environment`, for redacting what is recorded
+/// of a send.
+pub fn secret_values(
+    server: Option<&ServerEntry>,
+    environment: Option<&Environment>,
+) -> Vec<String> {
+    let mut values: Vec<String> = server
+        .cloned()
+        .map(|mut server| {
+            server_secrets(&mut server)
+                .into_iter()
+                .map(|(_, value)| value.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    values.extend(
+        environment
+            .into_iter()
+            .flat_map(|environment| &environment.variables)
+            .filter(|variable| variable.secret)
+            .map(|variable| variable.value.clone()),
+    );
+    values.retain(|value| !value.is_empty());
+    values
+}
+
+/// Replaces references with their values from `vault`. References the vault does not
+/// hold are left as written.
+pub fn resolve_references(
+    servers: &mut [ServerEntry],
+    environments: &mut [Environment],
+    vault: &Vault,
+) {
+    let resolve = |value: &mut String| {
+        if let Some(secret) = referenced_id(value).and_then(|id| vault.get(id)) {
+            *value = secret.to_string();
+        }
+    };
+    for server in servers.iter_mut() {
+        for (_, value) in server_secrets(server) {
+            resolve(value);
+        }
+    }
+    for variable in environments
+        .iter_mut()
+        .flat_map(|environment| environment.variables.iter_mut())
+    {
+        resolve(&mut variable.value);
+    }
+}
+
+/// Credential fields of `server`, named for their reference ids.
+fn server_secrets(server: &mut ServerEntry) -> Vec<(&'static str, &mut String)> {
+    let mut fields = match &mut server.auth {
+        ServerAuth::None => Vec::new(),
+        ServerAuth::ApiKey { value, .. } => vec![("api-key", value)],
+        ServerAuth::HttpBasic { password, .. } => vec![("password", password)],
+        ServerAuth::HttpBearer { token, .. } => vec![("token", token)],
+        ServerAuth::OAuth2 { access_token, .. }
+        | ServerAuth::OpenIdConnect { access_token, .. } => {
+            vec![("access-token", access_token)]
+        }
+    };
+    fields.push(("pkcs12-password", &mut server.settings.tls.pkcs12_password));
+    if let ServerProxy::Custom(proxy) = &mut server.settings.proxy {
+        fields.push(("proxy-password", &mut proxy.password));
+    }
+    fields
+}
+
+pub fn is_reference(value: &str) -> bool {
+    referenced_id(value).is_some()
+}
+
+fn reference(id: &str) -> String {
+    format!("{REFERENCE_PREFIX}{id}")
+}
+
+fn referenced_id(value: &str) -> Option<&str> {
+    value.strip_prefix(REFERENCE_PREFIX)
+}
+
+/// PBKDF2-HMAC-SHA256 cut to its first block, which is exactly the 32-byte key.
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mac = <Hmac<Sha256> as Mac>::new_from_slice(passphrase.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    let mut first = mac.clone();
+    first.update(salt);
+    first.update(&1u32.to_be_bytes());
+    let mut block = first.finalize().into_bytes();
+    let mut key = block;
+    for _ in 1..iterations {
+        let mut next = mac.clone();
+        next.update(&block);
+        block = next.finalize().into_bytes();
+        for (out, byte) in key.iter_mut().zip(block.iter()) {
+            *out ^= byte;
+        }
+    }
+    key.into()
+}
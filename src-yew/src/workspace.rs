@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+use crate::state::{
+    Environment, Response, ServerEntry, Tab, TabContent, TabKind, TabState, TreeNode, TreeState,
+    WsComposer, WsSession,
+};
+use crate::vault::{self, Vault};
+
+/// Bumped when the saved shape changes in a way `serde(default)` cannot absorb.
+const WORKSPACE_VERSION: u32 = 1;
+
+/// Session state saved as it changes and restored on launch: open tabs, the tree and
+/// servers, and the current selection. Credentials and secret variables are written as
+/// vault references, as in collection files.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    version: u32,
+    tabs: Vec<SavedTab>,
+    active_tab: usize,
+    root: TreeNode,
+    servers: Vec<ServerEntry>,
+    selected_server: Option<usize>,
+    selected_path: Option<Vec<usize>>,
+    #[serde(default)]
+    environments: Vec<Environment>,
+    #[serde(default)]
+    active_environment: Option<usize>,
+}
+
+/// What `Workspace::seal_secrets` did with the secrets it found.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SealedSecrets {
+    None,
+    /// Put in the vault; `changed` when the vault needs writing again.
+    Stored {
+        changed: bool,
+    },
+    /// Left out, as the vault is locked.
+    Blanked,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedTab {
+    label: String,
+    content: TabContent,
+    dirty: bool,
+    /// Composer of a WebSocket tab; the connection itself is not restored.
+    #[serde(default)]
+    socket: Option<WsComposer>,
+}
+
+impl Workspace {
+    pub fn capture(tabs: &TabState, tree: &TreeState, include_responses: bool) -> Self {
+        let saved = tabs
+            .tabs
+            .iter()
+            .map(|tab| {
+                let mut content = tab.content.clone();
+                content.response = if include_responses {
+                    restorable(&content.response)
+                } else {
+                    Response::default()
+                };
+                SavedTab {
+                    label: tab.label.clone(),
+                    content,
+                    dirty: tab.dirty,
+                    socket: tab.socket().map(|session| session.composer.clone()),
+                }
+            })
+            .collect();
+        Self {
+            version: WORKSPACE_VERSION,
+            tabs: saved,
+            active_tab: tabs.active_tab_id,
+            root: tree.root.clone(),
+            servers: tree.servers.clone(),
+            selected_server: tree.selected_server,
+            selected_path: tree.selected_path.clone(),
+            environments: tree.environments.clone(),
+            active_environment: tree.active_environment,
+        }
+    }
+
+    /// Parses a saved workspace, rejecting ones written by a newer version.
+    pub fn from_value(value: serde_json::Value) -> Result<Self, String> {
+        let workspace: Self = serde_json::from_value(value).map_err(|err| err.to_string())?;
+        if workspace.version > WORKSPACE_VERSION {
+            return Err(format!(
+                "workspace version {} is newer than this app",
+                workspace.version
+            ));
+        }
+        Ok(workspace)
+    }
+
+    /// Moves secrets into `vault`, leaving references in their place. With the vault
+    /// locked they are blanked instead, so no reference points at a secret the vault
+    /// does not hold.
+    pub fn seal_secrets(&mut self, vault: Option<&mut Vault>) -> SealedSecrets {
+        let (servers, environments, secrets) =
+            vault::extract_secrets(&self.servers, &self.environments);
+        if secrets.is_empty() {
+            return SealedSecrets::None;
+        }
+        let Some(vault) = vault else {
+            vault::blank_secrets(&mut self.servers, &mut self.environments);
+            return SealedSecrets::Blanked;
+        };
+        self.servers = servers;
+        self.environments = environments;
+        let mut changed = false;
+        for (id, value) in secrets {
+            changed |= vault.insert(id, value);
+        }
+        SealedSecrets::Stored { changed }
+    }
+
+    pub fn has_references(&self) -> bool {
+        vault::has_references(&self.servers, &self.environments)
+    }
+
+    pub fn resolve_references(&mut self, vault: &Vault) {
+        vault::resolve_references(&mut self.servers, &mut self.environments, vault);
+    }
+
+    pub fn tabs(&self) -> Vec<Tab> {
+        self.tabs
+            .iter()
+            .map(|saved| Tab {
+                label: saved.label.clone(),
+                content: saved.content.clone(),
+                dirty: saved.dirty,
+                pending_request: None,
+                event_stream: None,
+                kind: match saved.socket.as_ref() {
+                    Some(composer) => TabKind::WebSocket(Box::new(WsSession {
+                        composer: composer.clone(),
+                        ..WsSession::default()
+                    })),
+                    None => TabKind::Http,
+                },
+            })
+            .collect()
+    }
+
+    pub fn active_tab(&self) -> usize {
+        self.active_tab
+    }
+
+    pub fn tree(&self) -> (TreeNode, Vec<ServerEntry>) {
+        (self.root.clone(), self.servers.clone())
+    }
+
+    pub fn selected_server(&self) -> Option<usize> {
+        self.selected_server
+            .filter(|index| *index < self.servers.len())
+    }
+
+    pub fn selected_path(&self) -> Option<Vec<usize>> {
+        self.selected_path.clone()
+    }
+
+    pub fn environments(&self) -> Vec<Environment> {
+        self.environments.clone()
+    }
+
+    pub fn active_environment(&self) -> Option<usize> {
+        self.active_environment
+    }
+}
+
+/// A response as it can be shown again after a restart: spooled bodies are deleted on
+/// launch and event streams are over, so only the in-memory part is kept. The sent
+/// request is dropped too, as its headers carry resolved credentials.
+fn restorable(response: &Response) -> Response {
+    Response {
+        body_file: None,
+        streaming: false,
+        request: None,
+        ..response.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{EnvVariable, ServerAuth};
+
+    #[test]
+    fn locked_vault_blanks_secrets_instead_of_referencing_them() {
+        let mut server = ServerEntry::new("https://api.example.com".to_string());
+        server.auth = ServerAuth::HttpBasic {
+            username: "admin".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let tree = TreeState {
+            servers: vec![server],
+            environments: vec![Environment {
+                name: "prod".to_string(),
+                variables: vec![EnvVariable {
+                    key: "token".to_string(),
+                    value: "s3cret".to_string(),
+                    secret: true,
+                    ..EnvVariable::default()
+                }],
+            }],
+            ..TreeState::default()
+        };
+        let mut workspace = Workspace::capture(&TabState::default(), &tree, false);
+
+        assert_eq!(workspace.seal_secrets(None), SealedSecrets::Blanked);
+        assert!(!workspace.has_references());
+        let saved = serde_json::to_string(&workspace).unwrap();
+        assert!(!saved.contains("hunter2"));
+        assert!(!saved.contains("s3cret"));
+        assert!(saved.contains("admin"));
+    }
+}